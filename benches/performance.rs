@@ -48,6 +48,13 @@ fn benchmark_math_operations(c: &mut Criterion) {
         })
     });
     
+    c.bench_function("safe_sub", |b| {
+        b.iter(|| {
+            let result = safe_sub(black_box(2000), black_box(1000));
+            black_box(result)
+        })
+    });
+
     c.bench_function("safe_mul", |b| {
         b.iter(|| {
             let result = safe_mul(black_box(1000), black_box(1000));
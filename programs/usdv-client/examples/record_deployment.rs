@@ -0,0 +1,79 @@
+//! Initialize a deployed USDv program and record it in a deployment manifest
+//!
+//! Run by `scripts/deploy.sh` after `solana program deploy`:
+//!
+//! ```text
+//! cargo run -p usdv_client --example record_deployment -- \
+//!     <cluster> <usdc-mint> <admin-keypair> <manifest> <profile>
+//! ```
+//!
+//! An already initialized program is left as is. The profile is added to
+//! the manifest, or replaced, and becomes its default if it has none.
+
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::read_keypair_file};
+use std::path::Path;
+use std::str::FromStr;
+use usdv_client::manifest::{DeploymentManifest, DeploymentProfile};
+use usdv_client::{USDvClient, USDvConfig};
+use usdv_utils::pda::derive_program_state_pda;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [cluster, usdc_mint, admin_keypair, manifest_path, profile] = args.as_slice() else {
+        return Err("usage: record_deployment <cluster> <usdc-mint> <admin-keypair> <manifest> <profile>".into());
+    };
+    let usdc_mint = Pubkey::from_str(usdc_mint)?;
+    let admin = read_keypair_file(admin_keypair)?;
+
+    let program_id = usdv_program::ID;
+    let config = USDvConfig::custom(
+        program_id,
+        derive_program_state_pda(&program_id).0,
+        usdc_mint,
+        Pubkey::default(),
+        cluster.clone(),
+    );
+    let rpc_client = RpcClient::new_with_commitment(config.rpc_url(), CommitmentConfig::confirmed());
+    let client = USDvClient::new(rpc_client, config);
+
+    let state = match client.get_program_state().await {
+        Ok(state) => {
+            println!("Program {} is already initialized", program_id);
+            state
+        }
+        Err(_) => {
+            let signature = client.initialize(&admin, &usdc_mint).await?;
+            println!("Initialized program {}: {}", program_id, signature);
+            client.get_program_state().await?
+        }
+    };
+    if state.usdc_mint != usdc_mint {
+        return Err(format!("Program was initialized with USDC mint {}", state.usdc_mint).into());
+    }
+
+    let manifest_path = Path::new(manifest_path);
+    let mut manifest = if manifest_path.exists() {
+        DeploymentManifest::load(manifest_path)?
+    } else {
+        DeploymentManifest::default()
+    };
+    let deployment = DeploymentProfile::new(cluster.clone(), program_id, state.usdc_mint, state.usdv_mint);
+    manifest.set_profile(profile.clone(), deployment);
+    manifest.default_profile.get_or_insert_with(|| profile.clone());
+    manifest.save(manifest_path)?;
+
+    // Read the manifest back the way clients will; the initialization may
+    // not be finalized yet
+    let config = USDvConfig::load(manifest_path, Some(profile))?;
+    let rpc_client = RpcClient::new_with_commitment(config.rpc_url(), CommitmentConfig::confirmed());
+    USDvClient::new(rpc_client, config).verify_deployment().await?;
+    println!(
+        "Recorded profile {:?} in {}: USDv mint {}",
+        profile,
+        manifest_path.display(),
+        state.usdv_mint
+    );
+    Ok(())
+}
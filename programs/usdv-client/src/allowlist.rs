@@ -0,0 +1,109 @@
+//! Allowlist import from CSV files
+//!
+//! The CSV must have a header row. Only the `wallet` column is required:
+//!
+//! ```text
+//! wallet,expires_at,action
+//! 9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin,2026-12-31T00:00:00Z,add
+//! 4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T,,remove
+//! ```
+//!
+//! - `expires_at`: unix timestamp in seconds or RFC 3339 date-time; empty
+//!   means the entry never expires
+//! - `action`: `add` (default) or `remove`
+
+use crate::types::AllowlistUpdate;
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::io::Read;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Maximum number of allowlist updates sent in a single transaction
+pub const ALLOWLIST_BATCH_SIZE: usize = 10;
+
+/// Errors raised while importing an allowlist CSV
+#[derive(Error, Debug)]
+pub enum AllowlistCsvError {
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Line {line}: invalid wallet address '{value}'")]
+    InvalidWallet { line: u64, value: String },
+
+    #[error("Line {line}: invalid expiry '{value}'")]
+    InvalidExpiry { line: u64, value: String },
+
+    #[error("Line {line}: invalid action '{value}', expected 'add' or 'remove'")]
+    InvalidAction { line: u64, value: String },
+}
+
+#[derive(Debug, Deserialize)]
+struct AllowlistRow {
+    wallet: String,
+    #[serde(default)]
+    expires_at: Option<String>,
+    #[serde(default)]
+    action: Option<String>,
+}
+
+/// Parse an allowlist CSV into program updates, in file order
+pub fn parse_allowlist_csv<R: Read>(reader: R) -> Result<Vec<AllowlistUpdate>, AllowlistCsvError> {
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(reader);
+
+    let mut updates = Vec::new();
+
+    for record in csv_reader.deserialize::<AllowlistRow>() {
+        let row = record?;
+        // Header is line 1, so the first record is line 2
+        let line = updates.len() as u64 + 2;
+
+        let wallet = Pubkey::from_str(&row.wallet).map_err(|_| AllowlistCsvError::InvalidWallet {
+            line,
+            value: row.wallet.clone(),
+        })?;
+
+        let action = row.action.as_deref().unwrap_or("add").to_ascii_lowercase();
+        let update = match action.as_str() {
+            "" | "add" => AllowlistUpdate::Add {
+                wallet,
+                expires_at: parse_expiry(row.expires_at.as_deref(), line)?,
+            },
+            "remove" => AllowlistUpdate::Remove { wallet },
+            _ => {
+                return Err(AllowlistCsvError::InvalidAction {
+                    line,
+                    value: action,
+                });
+            }
+        };
+
+        updates.push(update);
+    }
+
+    Ok(updates)
+}
+
+/// Parse an expiry as unix seconds or RFC 3339; empty means never
+fn parse_expiry(value: Option<&str>, line: u64) -> Result<i64, AllowlistCsvError> {
+    let value = match value {
+        None | Some("") => return Ok(0),
+        Some(value) => value,
+    };
+
+    if let Ok(timestamp) = value.parse::<i64>() {
+        if timestamp >= 0 {
+            return Ok(timestamp);
+        }
+    } else if let Ok(datetime) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(datetime.timestamp());
+    }
+
+    Err(AllowlistCsvError::InvalidExpiry {
+        line,
+        value: value.to_string(),
+    })
+}
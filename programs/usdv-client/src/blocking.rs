@@ -0,0 +1,330 @@
+//! Synchronous facade over [`crate::USDvClient`], for CLIs and scripts
+//!
+//! Enabled with the `blocking` feature. Each call runs the async client on
+//! a private current-thread runtime, so it must not be used from inside an
+//! async context; use [`crate::USDvClient`] there.
+//!
+//! ```rust,no_run
+//! use usdv_client::blocking::USDvClient;
+//! use usdv_client::USDvConfig;
+//! use solana_client::nonblocking::rpc_client::RpcClient;
+//!
+//! let rpc_client = RpcClient::new("https://api.devnet.solana.com".to_string());
+//! let client = USDvClient::new(rpc_client, USDvConfig::devnet()).unwrap();
+//! let state = client.get_program_state().unwrap();
+//! println!("Supply: {}", state.total_usdv_supply);
+//! ```
+
+use crate::types::{
+    AllowlistEntryInfo, AllowlistUpdate, BurnResult, ClientConfig, DepositResult, MinterInfo,
+    ProgramStateInfo, SystemHealth, UserAccountInfo,
+};
+use crate::cli_config::CliConfig;
+use crate::history::{HistoryRange, UserHistory};
+use crate::offline::NonceInfo;
+use crate::simulation::SimulationResult;
+use crate::utils::NetworkInfo;
+use crate::{Result, USDvConfig};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+use std::future::Future;
+use std::sync::Arc;
+use tokio::runtime::{Builder, Runtime};
+use usdv_results::USDvClientError;
+
+/// Blocking USDv client
+pub struct USDvClient {
+    inner: crate::USDvClient,
+    runtime: Runtime,
+}
+
+impl USDvClient {
+    /// Create a blocking client with the default client configuration
+    pub fn new(rpc_client: RpcClient, config: USDvConfig) -> std::io::Result<Self> {
+        Self::from_async(crate::USDvClient::new(rpc_client, config))
+    }
+
+    /// Create a blocking client with a custom client configuration
+    pub fn with_config(
+        rpc_client: RpcClient,
+        config: USDvConfig,
+        client_config: ClientConfig,
+    ) -> std::io::Result<Self> {
+        Self::from_async(crate::USDvClient::with_config(rpc_client, config, client_config))
+    }
+
+    /// See [`crate::USDvClient::from_cli_config`]
+    pub fn from_cli_config(cli_config: &CliConfig, config: USDvConfig) -> Result<Self> {
+        let inner = crate::USDvClient::from_cli_config(cli_config, config)?;
+        Self::from_async(inner).map_err(|e| USDvClientError::ConfigurationError(e.to_string()))
+    }
+
+    /// See [`crate::USDvClient::from_default_cli_config`]
+    pub fn from_default_cli_config(config: USDvConfig) -> Result<(Self, Keypair)> {
+        let (inner, signer) = crate::USDvClient::from_default_cli_config(config)?;
+        let client = Self::from_async(inner).map_err(|e| USDvClientError::ConfigurationError(e.to_string()))?;
+        Ok((client, signer))
+    }
+
+    /// Wrap an existing async client
+    pub fn from_async(inner: crate::USDvClient) -> std::io::Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { inner, runtime })
+    }
+
+    /// See [`crate::USDvClient::with_fee_payer`]
+    pub fn with_fee_payer(self, fee_payer: Arc<dyn Signer + Send + Sync>) -> Self {
+        Self {
+            inner: self.inner.with_fee_payer(fee_payer),
+            runtime: self.runtime,
+        }
+    }
+
+    /// The wrapped async client
+    pub fn inner(&self) -> &crate::USDvClient {
+        &self.inner
+    }
+
+    /// Get the program configuration
+    pub fn config(&self) -> &USDvConfig {
+        self.inner.config()
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// See [`crate::USDvClient::initialize`]
+    pub fn initialize<S: Signer + ?Sized>(&self, admin: &S, usdc_mint: &Pubkey) -> Result<Signature> {
+        self.block_on(self.inner.initialize(admin, usdc_mint))
+    }
+
+    /// See [`crate::USDvClient::deposit_and_mint`]
+    pub fn deposit_and_mint<S: Signer + ?Sized>(&self, user: &S, amount: u64) -> Result<DepositResult> {
+        self.block_on(self.inner.deposit_and_mint(user, amount))
+    }
+
+    /// See [`crate::USDvClient::burn_and_withdraw`]
+    pub fn burn_and_withdraw<S: Signer + ?Sized>(&self, user: &S, amount: u64) -> Result<BurnResult> {
+        self.block_on(self.inner.burn_and_withdraw(user, amount))
+    }
+
+    /// See [`crate::USDvClient::update_program_state`]
+    pub fn update_program_state<S: Signer + ?Sized>(
+        &self,
+        admin: &S,
+        new_admin: Option<&Pubkey>,
+    ) -> Result<Signature> {
+        self.block_on(self.inner.update_program_state(admin, new_admin))
+    }
+
+    /// See [`crate::USDvClient::initiate_wind_down`]
+    pub fn initiate_wind_down<S: Signer + ?Sized>(
+        &self,
+        admin: &S,
+        claim_period_seconds: i64,
+    ) -> Result<Signature> {
+        self.block_on(self.inner.initiate_wind_down(admin, claim_period_seconds))
+    }
+
+    /// See [`crate::USDvClient::sweep_unclaimed`]
+    pub fn sweep_unclaimed<S: Signer + ?Sized>(
+        &self,
+        admin: &S,
+        recovery_usdc_account: &Pubkey,
+    ) -> Result<Signature> {
+        self.block_on(self.inner.sweep_unclaimed(admin, recovery_usdc_account))
+    }
+
+    /// See [`crate::USDvClient::recover_tokens`]
+    pub fn recover_tokens<S: Signer + ?Sized>(
+        &self,
+        admin: &S,
+        source_token_account: &Pubkey,
+        destination_token_account: &Pubkey,
+    ) -> Result<Signature> {
+        self.block_on(self.inner.recover_tokens(
+            admin,
+            source_token_account,
+            destination_token_account,
+        ))
+    }
+
+    /// See [`crate::USDvClient::configure_minter`]
+    pub fn configure_minter<S: Signer + ?Sized>(
+        &self,
+        admin: &S,
+        minter_authority: &Pubkey,
+        allowance: u64,
+    ) -> Result<Signature> {
+        self.block_on(self.inner.configure_minter(admin, minter_authority, allowance))
+    }
+
+    /// See [`crate::USDvClient::mint_allowance`]
+    pub fn mint_allowance<S: Signer + ?Sized>(
+        &self,
+        minter: &S,
+        destination_owner: &Pubkey,
+        amount: u64,
+    ) -> Result<Signature> {
+        self.block_on(self.inner.mint_allowance(minter, destination_owner, amount))
+    }
+
+    /// See [`crate::USDvClient::burn_for_offchain_redemption`]
+    pub fn burn_for_offchain_redemption<S: Signer + ?Sized>(&self, minter: &S, amount: u64) -> Result<Signature> {
+        self.block_on(self.inner.burn_for_offchain_redemption(minter, amount))
+    }
+
+    /// See [`crate::USDvClient::get_minter_info`]
+    pub fn get_minter_info(&self, minter_authority: &Pubkey) -> Result<MinterInfo> {
+        self.block_on(self.inner.get_minter_info(minter_authority))
+    }
+
+    /// See [`crate::USDvClient::set_allowlist_mode`]
+    pub fn set_allowlist_mode<S: Signer + ?Sized>(&self, admin: &S, enabled: bool) -> Result<Signature> {
+        self.block_on(self.inner.set_allowlist_mode(admin, enabled))
+    }
+
+    /// See [`crate::USDvClient::update_allowlist`]
+    pub fn update_allowlist<S: Signer + ?Sized>(
+        &self,
+        admin: &S,
+        updates: &[AllowlistUpdate],
+    ) -> Result<Vec<Signature>> {
+        self.block_on(self.inner.update_allowlist(admin, updates))
+    }
+
+    /// See [`crate::USDvClient::import_allowlist_csv`]
+    pub fn import_allowlist_csv<S: Signer + ?Sized, R: std::io::Read>(
+        &self,
+        admin: &S,
+        csv: R,
+    ) -> Result<Vec<Signature>> {
+        self.block_on(self.inner.import_allowlist_csv(admin, csv))
+    }
+
+    /// See [`crate::USDvClient::get_allowlist_entry`]
+    pub fn get_allowlist_entry(&self, wallet: &Pubkey) -> Result<Option<AllowlistEntryInfo>> {
+        self.block_on(self.inner.get_allowlist_entry(wallet))
+    }
+
+    /// See [`crate::USDvClient::verify_deployment`]
+    pub fn verify_deployment(&self) -> Result<ProgramStateInfo> {
+        self.block_on(self.inner.verify_deployment())
+    }
+
+    /// See [`crate::USDvClient::get_program_state`]
+    pub fn get_program_state(&self) -> Result<ProgramStateInfo> {
+        self.block_on(self.inner.get_program_state())
+    }
+
+    /// See [`crate::USDvClient::get_usdv_balance`]
+    pub fn get_usdv_balance(&self, user: &Pubkey) -> Result<u64> {
+        self.block_on(self.inner.get_usdv_balance(user))
+    }
+
+    /// See [`crate::USDvClient::get_usdc_balance`]
+    pub fn get_usdc_balance(&self, user: &Pubkey) -> Result<u64> {
+        self.block_on(self.inner.get_usdc_balance(user))
+    }
+
+    /// See [`crate::USDvClient::get_user_account_info`]
+    pub fn get_user_account_info(&self, user: &Pubkey) -> Result<UserAccountInfo> {
+        self.block_on(self.inner.get_user_account_info(user))
+    }
+
+    /// See [`crate::USDvClient::get_user_history`]
+    pub fn get_user_history(&self, user: &Pubkey, range: HistoryRange) -> Result<UserHistory> {
+        self.block_on(self.inner.get_user_history(user, range))
+    }
+
+    /// See [`crate::USDvClient::get_system_health`]
+    pub fn get_system_health(&self) -> Result<SystemHealth> {
+        self.block_on(self.inner.get_system_health())
+    }
+
+    /// See [`crate::USDvClient::can_deposit`]
+    pub fn can_deposit(&self, user: &Pubkey, amount: u64) -> Result<bool> {
+        self.block_on(self.inner.can_deposit(user, amount))
+    }
+
+    /// See [`crate::USDvClient::can_burn`]
+    pub fn can_burn(&self, user: &Pubkey, amount: u64) -> Result<bool> {
+        self.block_on(self.inner.can_burn(user, amount))
+    }
+
+    /// See [`crate::USDvClient::estimate_fee`]
+    pub fn estimate_fee(&self, instruction: &Instruction) -> Result<u64> {
+        self.block_on(self.inner.estimate_fee(instruction))
+    }
+
+    /// See [`crate::USDvClient::create_nonce_account`]
+    pub fn create_nonce_account<S: Signer + ?Sized>(
+        &self,
+        funder: &S,
+        nonce_account: &Keypair,
+        nonce_authority: &Pubkey,
+    ) -> Result<Signature> {
+        self.block_on(self.inner.create_nonce_account(funder, nonce_account, nonce_authority))
+    }
+
+    /// See [`crate::USDvClient::advance_nonce`]
+    pub fn advance_nonce<S: Signer + ?Sized>(
+        &self,
+        nonce_authority: &S,
+        nonce_account: &Pubkey,
+    ) -> Result<Signature> {
+        self.block_on(self.inner.advance_nonce(nonce_authority, nonce_account))
+    }
+
+    /// See [`crate::USDvClient::get_nonce`]
+    pub fn get_nonce(&self, nonce_account: &Pubkey) -> Result<NonceInfo> {
+        self.block_on(self.inner.get_nonce(nonce_account))
+    }
+
+    /// See [`crate::USDvClient::prepare_offline_transaction`]
+    pub fn prepare_offline_transaction(
+        &self,
+        instructions: &[Instruction],
+        fee_payer: &Pubkey,
+        nonce_account: &Pubkey,
+    ) -> Result<Transaction> {
+        self.block_on(self.inner.prepare_offline_transaction(instructions, fee_payer, nonce_account))
+    }
+
+    /// See [`crate::USDvClient::submit_signed`]
+    pub fn submit_signed(&self, transaction: &Transaction) -> Result<Signature> {
+        self.block_on(self.inner.submit_signed(transaction))
+    }
+
+    /// See [`crate::USDvClient::simulate`]
+    pub fn simulate(&self, instructions: &[Instruction], payer: &Pubkey) -> Result<SimulationResult> {
+        self.block_on(self.inner.simulate(instructions, payer))
+    }
+
+    /// See [`crate::USDvClient::simulate_deposit_and_mint`]
+    pub fn simulate_deposit_and_mint(&self, user: &Pubkey, amount: u64) -> Result<SimulationResult> {
+        self.block_on(self.inner.simulate_deposit_and_mint(user, amount))
+    }
+
+    /// See [`crate::USDvClient::simulate_burn_and_withdraw`]
+    pub fn simulate_burn_and_withdraw(&self, user: &Pubkey, amount: u64) -> Result<SimulationResult> {
+        self.block_on(self.inner.simulate_burn_and_withdraw(user, amount))
+    }
+
+    /// See [`crate::USDvClient::health_check`]
+    pub fn health_check(&self) -> Result<()> {
+        self.block_on(self.inner.health_check())
+    }
+
+    /// See [`crate::USDvClient::get_network_info`]
+    pub fn get_network_info(&self) -> Result<NetworkInfo> {
+        self.block_on(self.inner.get_network_info())
+    }
+}
@@ -0,0 +1,282 @@
+//! Solana CLI configuration and signers
+//!
+//! [`CliConfig`] reads the Solana CLI's `~/.config/solana/cli/config.yml`
+//! for the RPC URL, websocket URL, commitment and default keypair, so a
+//! client can be set up the way `solana config set` left it; see
+//! `USDvClient::from_cli_config`.
+//!
+//! [`signer_from_path`] resolves a keypair path the way the CLI resolves
+//! signers:
+//!
+//! - a file path, or `file:<path>`; a leading `~/` is expanded
+//! - `-` or `stdin:` reads a keypair file's JSON from standard input
+//! - `ASK` prompts for a seed phrase and derives the legacy keypair from
+//!   the first 32 bytes of its seed
+//! - `prompt:` prompts for a seed phrase and derives `m/44'/501'`, or the
+//!   path given by `prompt:?key=<account>/<change>` or
+//!   `prompt:?full-path=m/44/501/...`
+//!
+//! Seed phrases must be valid English BIP39 mnemonics, and a passphrase is
+//! prompted for after them. Hardware wallets (`usb://`) are not supported,
+//! and public keys are rejected: they cannot sign.
+
+use crate::subscription;
+use crate::Result;
+use bip39::{Language, Mnemonic, Seed};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    derivation_path::DerivationPath,
+    pubkey::Pubkey,
+    signer::keypair::{keypair_from_seed, keypair_from_seed_and_derivation_path, read_keypair, read_keypair_file, Keypair},
+};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use usdv_results::USDvClientError;
+
+/// Signer path that prompts for a seed phrase with the legacy derivation
+pub const ASK_KEYWORD: &str = "ASK";
+
+/// RPC URL used when the CLI has no configuration
+const DEFAULT_JSON_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+
+/// The settings of a Solana CLI `config.yml` used by the client
+///
+/// Missing fields take the CLI's defaults; unknown fields, such as
+/// `address_labels`, are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CliConfig {
+    pub json_rpc_url: String,
+    /// Pubsub endpoint, empty to derive it from `json_rpc_url`
+    pub websocket_url: String,
+    /// Default signer, in any form accepted by [`signer_from_path`]
+    pub keypair_path: String,
+    /// `processed`, `confirmed` or `finalized`
+    pub commitment: String,
+}
+
+impl Default for CliConfig {
+    fn default() -> Self {
+        let keypair_path = home_dir()
+            .map(|home| home.join(".config/solana/id.json").display().to_string())
+            .unwrap_or_default();
+        Self {
+            json_rpc_url: DEFAULT_JSON_RPC_URL.to_string(),
+            websocket_url: String::new(),
+            keypair_path,
+            commitment: "confirmed".to_string(),
+        }
+    }
+}
+
+/// Where the Solana CLI keeps its configuration, `~/.config/solana/cli/config.yml`
+pub fn default_config_path() -> Option<PathBuf> {
+    home_dir().map(|home| home.join(".config/solana/cli/config.yml"))
+}
+
+fn home_dir() -> Option<PathBuf> {
+    dirs_next::home_dir()
+}
+
+impl CliConfig {
+    /// Parse a CLI configuration
+    pub fn from_yaml_str(config: &str) -> Result<Self> {
+        serde_yaml::from_str(config).map_err(|e| USDvClientError::ConfigurationError(e.to_string()))
+    }
+
+    /// Read the CLI configuration at `path`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let config = std::fs::read_to_string(path).map_err(|e| {
+            USDvClientError::ConfigurationError(format!("Cannot read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_str(&config)
+    }
+
+    /// Read the CLI configuration at [`default_config_path`]
+    ///
+    /// Like the CLI, falls back to the defaults when there is no file.
+    pub fn load_default() -> Result<Self> {
+        match default_config_path() {
+            Some(path) if path.exists() => Self::load(path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Commitment for RPC requests
+    pub fn commitment(&self) -> Result<CommitmentConfig> {
+        CommitmentConfig::from_str(&self.commitment).map_err(|_| {
+            USDvClientError::ConfigurationError(format!("Invalid commitment {:?}", self.commitment))
+        })
+    }
+
+    /// Pubsub endpoint, derived with [`subscription::websocket_url`] when unset
+    pub fn websocket_url(&self) -> String {
+        if self.websocket_url.is_empty() {
+            subscription::websocket_url(&self.json_rpc_url)
+        } else {
+            self.websocket_url.clone()
+        }
+    }
+
+    /// RPC client for `json_rpc_url` at the configured commitment
+    pub fn rpc_client(&self) -> Result<RpcClient> {
+        Ok(RpcClient::new_with_commitment(self.json_rpc_url.clone(), self.commitment()?))
+    }
+
+    /// The default signer, `keypair_path`; see [`signer_from_path`]
+    pub fn signer(&self) -> Result<Keypair> {
+        signer_from_path(&self.keypair_path, "keypair")
+    }
+}
+
+/// Where a signer comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerSource {
+    /// A keypair file
+    File(PathBuf),
+    /// A keypair file's JSON on standard input
+    Stdin,
+    /// A seed phrase typed at a prompt
+    Prompt {
+        /// Derivation path; `None` derives `m/44'/501'`, or the legacy keypair
+        derivation_path: Option<DerivationPath>,
+        /// Whether the keypair is the first 32 bytes of the seed, as for `ASK`
+        legacy: bool,
+    },
+}
+
+/// Parse a signer path; see the [module documentation](self)
+pub fn parse_signer_source(source: &str) -> Result<SignerSource> {
+    match split_scheme(source) {
+        Some((scheme, rest)) => match scheme.to_ascii_lowercase().as_str() {
+            "file" => Ok(SignerSource::File(expand_home(strip_authority(rest)))),
+            "stdin" => Ok(SignerSource::Stdin),
+            "prompt" => {
+                let query = rest.split_once('?').map_or("", |(_, query)| query);
+                Ok(SignerSource::Prompt {
+                    derivation_path: derivation_path_from_query(query)?,
+                    legacy: false,
+                })
+            }
+            "usb" => Err(signer_error(source, "hardware wallets are not supported")),
+            _ => Err(signer_error(source, "unrecognized signer source")),
+        },
+        None => match source {
+            "-" => Ok(SignerSource::Stdin),
+            ASK_KEYWORD => Ok(SignerSource::Prompt {
+                derivation_path: None,
+                legacy: true,
+            }),
+            _ if Pubkey::from_str(source).is_ok() => {
+                Err(signer_error(source, "a public key cannot sign; pass a keypair"))
+            }
+            _ => Ok(SignerSource::File(expand_home(source))),
+        },
+    }
+}
+
+/// Resolve a signer path, prompting on the terminal for seed phrases
+///
+/// `keypair_name` labels the prompts, as in `[keypair] seed phrase: `.
+/// Input is not echoed.
+pub fn signer_from_path(path: &str, keypair_name: &str) -> Result<Keypair> {
+    signer_from_path_with_prompt(path, keypair_name, |prompt| rpassword::prompt_password(prompt))
+}
+
+/// Resolve a signer path, reading seed phrases and passphrases from `prompt`
+pub fn signer_from_path_with_prompt(
+    path: &str,
+    keypair_name: &str,
+    mut prompt: impl FnMut(&str) -> std::io::Result<String>,
+) -> Result<Keypair> {
+    match parse_signer_source(path)? {
+        SignerSource::File(file) => read_keypair_file(&file)
+            .map_err(|e| signer_error(&file.display().to_string(), &e.to_string())),
+        SignerSource::Stdin => read_keypair(&mut std::io::stdin()).map_err(|e| signer_error(path, &e.to_string())),
+        SignerSource::Prompt { derivation_path, legacy } => {
+            let seed_phrase = prompt(&format!("[{}] seed phrase: ", keypair_name))
+                .map_err(|e| signer_error(path, &e.to_string()))?;
+            // Checked before asking for the passphrase
+            let mnemonic = parse_mnemonic(&seed_phrase)?;
+            let passphrase = prompt(&format!(
+                "[{}] If this seed phrase has an associated passphrase, enter it now. Otherwise, press ENTER to continue: ",
+                keypair_name
+            ))
+            .map_err(|e| signer_error(path, &e.to_string()))?;
+            keypair_from_mnemonic(&mnemonic, &passphrase, derivation_path, legacy)
+        }
+    }
+}
+
+/// Keypair of a seed phrase, as the CLI derives it for `prompt:` or, with `legacy`, `ASK`
+pub fn keypair_from_seed_phrase(
+    seed_phrase: &str,
+    passphrase: &str,
+    derivation_path: Option<DerivationPath>,
+    legacy: bool,
+) -> Result<Keypair> {
+    keypair_from_mnemonic(&parse_mnemonic(seed_phrase)?, passphrase, derivation_path, legacy)
+}
+
+fn parse_mnemonic(seed_phrase: &str) -> Result<Mnemonic> {
+    let sanitized = seed_phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+    Mnemonic::from_phrase(&sanitized, Language::English)
+        .map_err(|e| USDvClientError::SigningError(format!("Invalid seed phrase: {}", e)))
+}
+
+fn keypair_from_mnemonic(
+    mnemonic: &Mnemonic,
+    passphrase: &str,
+    derivation_path: Option<DerivationPath>,
+    legacy: bool,
+) -> Result<Keypair> {
+    let seed = Seed::new(mnemonic, passphrase);
+    let keypair = if legacy {
+        keypair_from_seed(seed.as_bytes())
+    } else {
+        keypair_from_seed_and_derivation_path(seed.as_bytes(), derivation_path)
+    };
+    keypair.map_err(|e| USDvClientError::SigningError(format!("Cannot derive keypair: {}", e)))
+}
+
+/// `scheme` and the rest of `source`, if it starts with a URI scheme
+fn split_scheme(source: &str) -> Option<(&str, &str)> {
+    let (scheme, rest) = source.split_once(':')?;
+    let mut chars = scheme.chars();
+    let is_scheme = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    is_scheme.then_some((scheme, rest))
+}
+
+/// `rest` without an empty `//` authority, so `file:///a` is `/a`
+fn strip_authority(rest: &str) -> &str {
+    rest.strip_prefix("//").unwrap_or(rest)
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn derivation_path_from_query(query: &str) -> Result<Option<DerivationPath>> {
+    let mut derivation_path = None;
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let parsed = match key {
+            "key" => DerivationPath::from_key_str(value),
+            "full-path" => DerivationPath::from_absolute_path_str(value),
+            _ => return Err(signer_error(query, &format!("unknown query key {:?}", key))),
+        };
+        derivation_path = Some(parsed.map_err(|e| signer_error(value, &e.to_string()))?);
+    }
+    Ok(derivation_path)
+}
+
+fn signer_error(source: &str, reason: &str) -> USDvClientError {
+    USDvClientError::SigningError(format!("Cannot load signer {:?}: {}", source, reason))
+}
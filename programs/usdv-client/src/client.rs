@@ -1,21 +1,53 @@
 //! Main client implementation for USDv stablecoin operations
 
-// use crate::{USDvConfig, USDvError, Result, InstructionBuilder, ProgramStateInfo, utils};
-use usdv_results::USDvError;
+use usdv_results::USDvClientError;
+use crate::cli_config::CliConfig;
 use crate::config::USDvConfig;
-use crate::types::ProgramStateInfo;
-use solana_client::rpc_client::RpcClient;
+use crate::instructions::InstructionBuilder;
+use crate::simulation::{self, SimulationResult};
+use crate::events::USDvEvent;
+use crate::history::{self, HistoryRange, UserHistory};
+use crate::receipts;
+use crate::submission::{SubmissionConfig, SubmissionEngine, SubmissionOutcome};
+use crate::subscription::{self, EventStream, SubscriptionConfig};
+use crate::watcher::{self, StateChangeStream, WatcherConfig};
+use crate::offline::{self, NonceInfo};
+use crate::signer::TransactionSigners;
+use crate::utils;
+use crate::Result;
+use crate::allowlist::{parse_allowlist_csv, ALLOWLIST_BATCH_SIZE};
+use crate::types::{
+    AllowlistEntryInfo, AllowlistUpdate, BurnResult, DepositResult, MinterInfo, ProgramStateInfo,
+    TransactionInfo, TransactionStatus,
+};
+use anchor_lang::AccountDeserialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_nonce::state::State as NonceState;
 use solana_sdk::{
-    commitment_config::CommitmentConfig,
     instruction::Instruction,
     pubkey::Pubkey,
-    signature::{Keypair, Signature, Signer},
+    signature::{Keypair, Signature},
+    signer::{signers::Signers, Signer},
     transaction::Transaction,
 };
-use spl_associated_token_account::get_associated_token_address;
+use solana_system_interface::instruction::{advance_nonce_account, create_nonce_account};
+use usdv_utils::pda::{
+    derive_allowlist_entry_pda, derive_associated_token_account, derive_minter_pda,
+    derive_vault_usdc_account,
+};
+use std::path::Path;
 use std::sync::Arc;
 
 /// Main client for interacting with USDv stablecoin program
+///
+/// Backed by the nonblocking RPC client, so no call blocks the async
+/// executor. The client is `Send + Sync`; share one instance across tasks
+/// with an `Arc`. The `blocking` feature adds a synchronous facade in
+/// `usdv_client::blocking`.
+///
+/// Operations accept any [`Signer`] as the token authority; see
+/// [`crate::signer`]. The authority pays the transaction fee unless a
+/// separate fee payer is set with [`Self::with_fee_payer`].
 pub struct USDvClient {
     /// Solana RPC client
     rpc_client: Arc<RpcClient>,
@@ -25,6 +57,8 @@ pub struct USDvClient {
     instruction_builder: InstructionBuilder,
     /// Client configuration
     client_config: crate::types::ClientConfig,
+    /// Pays transaction fees instead of the token authority
+    fee_payer: Option<Arc<dyn Signer + Send + Sync>>,
 }
 
 impl USDvClient {
@@ -37,6 +71,7 @@ impl USDvClient {
             config,
             instruction_builder,
             client_config: Default::default(),
+            fee_payer: None,
         }
     }
 
@@ -53,9 +88,52 @@ impl USDvClient {
             config,
             instruction_builder,
             client_config,
+            fee_payer: None,
         }
     }
 
+    /// Create a client for a profile of the deployment manifest at `path`
+    ///
+    /// Connects to the profile's cluster and checks the deployment with
+    /// [`Self::verify_deployment`]; see [`USDvConfig::load`].
+    pub async fn from_manifest(path: impl AsRef<Path>, profile: Option<&str>) -> Result<Self> {
+        let config = USDvConfig::load(path, profile)?;
+        let client = Self::new(RpcClient::new(config.rpc_url()), config);
+        client.verify_deployment().await?;
+        Ok(client)
+    }
+
+    /// Create a client with the RPC URL and commitment of a Solana CLI configuration
+    ///
+    /// Resolve its default signer with [`CliConfig::signer`]; see
+    /// [`crate::cli_config`].
+    pub fn from_cli_config(cli_config: &CliConfig, config: USDvConfig) -> Result<Self> {
+        Ok(Self::new(cli_config.rpc_client()?, config))
+    }
+
+    /// Create a client from `~/.config/solana/cli/config.yml`, with its default signer
+    ///
+    /// Prompts on the terminal when the signer is a seed phrase.
+    pub fn from_default_cli_config(config: USDvConfig) -> Result<(Self, Keypair)> {
+        let cli_config = CliConfig::load_default()?;
+        let signer = cli_config.signer()?;
+        Ok((Self::from_cli_config(&cli_config, config)?, signer))
+    }
+
+    /// Pay transaction fees with `fee_payer` instead of the token authority
+    ///
+    /// Accounts created by the program, such as a user's USDv token
+    /// account, are still funded by the authority.
+    pub fn with_fee_payer(mut self, fee_payer: Arc<dyn Signer + Send + Sync>) -> Self {
+        self.fee_payer = Some(fee_payer);
+        self
+    }
+
+    /// The separate fee payer, if one is set
+    pub fn fee_payer(&self) -> Option<&Arc<dyn Signer + Send + Sync>> {
+        self.fee_payer.as_ref()
+    }
+
     /// Get the RPC client
     pub fn rpc_client(&self) -> &RpcClient {
         &self.rpc_client
@@ -66,137 +144,391 @@ impl USDvClient {
         &self.config
     }
 
+    /// Builder for every program instruction, for transactions assembled by hand
+    pub fn instruction_builder(&self) -> &InstructionBuilder {
+        &self.instruction_builder
+    }
+
     /// Initialize the USDv program (admin only)
-    pub async fn initialize(
+    pub async fn initialize<S: Signer + ?Sized>(
         &self,
-        admin: &Keypair,
+        admin: &S,
         usdc_mint: &Pubkey,
     ) -> Result<Signature> {
-        // Generate keypairs for new accounts
-        let program_state = Keypair::new();
+        // The USDv mint is a fresh keypair, the rest are PDAs
         let usdv_mint = Keypair::new();
 
-        // Derive vault authority PDA
-        let (vault_authority, _) = Pubkey::find_program_address(
-            &[b"vault_authority"],
-            &self.config.program_id,
-        );
-
         // Build initialize instruction
         let instruction = self.instruction_builder.initialize(
-            &admin.pubkey(),
-            &program_state.pubkey(),
+            &signer_pubkey(admin)?,
             &usdv_mint.pubkey(),
-            &vault_authority,
             usdc_mint,
-        )?;
+        );
 
         // Send transaction
-        self.send_transaction_with_signers(
-            &[instruction],
-            &[admin, &program_state, &usdv_mint],
-        ).await
+        self.send_transaction_with_signers(&[instruction], admin, &[&usdv_mint])
+            .await
     }
 
     /// Deposit USDC and mint USDv tokens
-    pub async fn deposit_and_mint(
+    ///
+    /// Once the transaction lands, its metadata is fetched to fill in the
+    /// result; if that fails, the result carries only the signature and
+    /// slot. See [`crate::receipts`].
+    pub async fn deposit_and_mint<S: Signer + ?Sized>(
         &self,
-        user: &Keypair,
+        user: &S,
         amount: u64,
-    ) -> Result<Signature> {
+    ) -> Result<DepositResult> {
         // Validate amount first
-        usdv_utils::validate_deposit_amount(amount)
-            .map_err(|e| USDvError::InvalidAmount(e.to_string()))?;
+        usdv_utils::validate_deposit_amount(amount)?;
 
         // Build instruction
-        let instruction = self.instruction_builder.deposit_and_mint(
-            &self.config.program_state,
-            &self.config.usdc_mint,
-            &self.config.usdv_mint,
-            &user.pubkey(),
-            amount,
-        )?;
-
-        // Send transaction
-        self.send_transaction_with_signers(&[instruction], &[user]).await
+        let user_pubkey = signer_pubkey(user)?;
+        let instruction = self.deposit_and_mint_instruction(&user_pubkey, amount).await?;
+
+        // Send transaction and read back what it did
+        let outcome = self.submit_with_signers(&[instruction], user, &[]).await?;
+        let transaction = self.landed_transaction_info(outcome).await?;
+
+        let deposited = transaction
+            .events
+            .iter()
+            .find_map(|event| match event {
+                USDvEvent::Deposited(deposited) => Some(deposited.amount),
+                _ => None,
+            })
+            .unwrap_or(amount);
+        let (new_usdc_balance, new_usdv_balance) = self.new_balances(&transaction, &user_pubkey);
+
+        Ok(DepositResult {
+            transaction,
+            usdc_deposited: deposited,
+            usdv_minted: deposited,
+            new_usdc_balance,
+            new_usdv_balance,
+        })
     }
 
     /// Burn USDv tokens and withdraw USDC
-    pub async fn burn_and_withdraw(
+    ///
+    /// The result is filled in like that of [`Self::deposit_and_mint`].
+    pub async fn burn_and_withdraw<S: Signer + ?Sized>(
         &self,
-        user: &Keypair,
+        user: &S,
         amount: u64,
-    ) -> Result<Signature> {
+    ) -> Result<BurnResult> {
+        let user_pubkey = signer_pubkey(user)?;
+
         // Get user's current USDv balance for validation
-        let usdv_balance = self.get_usdv_balance(&user.pubkey()).await?;
+        let usdv_balance = self.get_usdv_balance(&user_pubkey).await?;
         
         // Validate burn amount
-        usdv_utils::validate_burn_amount(amount, usdv_balance)
-            .map_err(|e| USDvError::InvalidAmount(e.to_string()))?;
+        usdv_utils::validate_burn_amount(amount, usdv_balance)?;
 
         // Build instruction
-        let instruction = self.instruction_builder.burn_and_withdraw(
-            &self.config.program_state,
-            &self.config.usdv_mint,
-            &self.config.usdc_mint,
-            &user.pubkey(),
-            amount,
-        )?;
-
-        // Send transaction
-        self.send_transaction_with_signers(&[instruction], &[user]).await
+        let instruction = self.burn_and_withdraw_instruction(&user_pubkey, amount).await?;
+
+        // Send transaction and read back what it did
+        let outcome = self.submit_with_signers(&[instruction], user, &[]).await?;
+        let transaction = self.landed_transaction_info(outcome).await?;
+
+        let burned = transaction
+            .events
+            .iter()
+            .find_map(|event| match event {
+                USDvEvent::Burned(burned) => Some(burned.amount),
+                _ => None,
+            })
+            .unwrap_or(amount);
+        let (new_usdc_balance, new_usdv_balance) = self.new_balances(&transaction, &user_pubkey);
+
+        Ok(BurnResult {
+            transaction,
+            usdv_burned: burned,
+            usdc_withdrawn: burned,
+            new_usdc_balance,
+            new_usdv_balance,
+        })
     }
 
     /// Update program state (admin only)
-    pub async fn update_program_state(
+    pub async fn update_program_state<S: Signer + ?Sized>(
         &self,
-        admin: &Keypair,
+        admin: &S,
         new_admin: Option<&Pubkey>,
     ) -> Result<Signature> {
         // Build instruction
         let instruction = self.instruction_builder.update_program_state(
-            &self.config.program_state,
-            &admin.pubkey(),
-            new_admin,
-        )?;
+            &signer_pubkey(admin)?,
+            new_admin.copied(),
+        );
 
         // Send transaction
-        self.send_transaction_with_signers(&[instruction], &[admin]).await
+        self.send_transaction_with_signers(&[instruction], admin, &[]).await
     }
 
-    /// Get program state information
-    pub async fn get_program_state(&self) -> Result<ProgramStateInfo> {
+    /// Put the program into wind-down (admin only, one-way)
+    pub async fn initiate_wind_down<S: Signer + ?Sized>(
+        &self,
+        admin: &S,
+        claim_period_seconds: i64,
+    ) -> Result<Signature> {
+        let instruction = self.instruction_builder.initiate_wind_down(
+            &signer_pubkey(admin)?,
+            claim_period_seconds,
+        );
+
+        self.send_transaction_with_signers(&[instruction], admin, &[]).await
+    }
+
+    /// Sweep unclaimed USDC after the wind-down deadline (admin only)
+    pub async fn sweep_unclaimed<S: Signer + ?Sized>(
+        &self,
+        admin: &S,
+        recovery_usdc_account: &Pubkey,
+    ) -> Result<Signature> {
+        let instruction = self.instruction_builder.sweep_unclaimed(
+            &signer_pubkey(admin)?,
+            &self.config.usdc_mint,
+            recovery_usdc_account,
+        );
+
+        self.send_transaction_with_signers(&[instruction], admin, &[]).await
+    }
+
+    /// Recover non-collateral tokens sent to the vault authority (admin only)
+    pub async fn recover_tokens<S: Signer + ?Sized>(
+        &self,
+        admin: &S,
+        source_token_account: &Pubkey,
+        destination_token_account: &Pubkey,
+    ) -> Result<Signature> {
+        let instruction = self.instruction_builder.recover_tokens(
+            &signer_pubkey(admin)?,
+            source_token_account,
+            destination_token_account,
+        );
+
+        self.send_transaction_with_signers(&[instruction], admin, &[]).await
+    }
+
+    /// Register a minter or set its remaining allowance (admin only)
+    pub async fn configure_minter<S: Signer + ?Sized>(
+        &self,
+        admin: &S,
+        minter_authority: &Pubkey,
+        allowance: u64,
+    ) -> Result<Signature> {
+        let instruction = self.instruction_builder.configure_minter(
+            &signer_pubkey(admin)?,
+            minter_authority,
+            allowance,
+        );
+
+        self.send_transaction_with_signers(&[instruction], admin, &[]).await
+    }
+
+    /// Mint USDv against off-chain collateral from a minter's allowance
+    pub async fn mint_allowance<S: Signer + ?Sized>(
+        &self,
+        minter: &S,
+        destination_owner: &Pubkey,
+        amount: u64,
+    ) -> Result<Signature> {
+        let destination = derive_associated_token_account(destination_owner, &self.config.usdv_mint);
+
+        let instruction = self.instruction_builder.mint_allowance(
+            &signer_pubkey(minter)?,
+            &self.config.usdv_mint,
+            &destination,
+            amount,
+        );
+
+        self.send_transaction_with_signers(&[instruction], minter, &[]).await
+    }
+
+    /// Burn a minter's USDv for an off-chain redemption
+    pub async fn burn_for_offchain_redemption<S: Signer + ?Sized>(
+        &self,
+        minter: &S,
+        amount: u64,
+    ) -> Result<Signature> {
+        let instruction = self.instruction_builder.burn_for_offchain_redemption(
+            &signer_pubkey(minter)?,
+            &self.config.usdv_mint,
+            amount,
+        );
+
+        self.send_transaction_with_signers(&[instruction], minter, &[]).await
+    }
+
+    /// Get a registered minter's allowance and lifetime totals
+    pub async fn get_minter_info(&self, minter_authority: &Pubkey) -> Result<MinterInfo> {
+        let (minter, _) = derive_minter_pda(&self.config.program_id, minter_authority);
+
         let account = self.rpc_client
-            .get_account(&self.config.program_state)
-            .map_err(USDvError::SolanaClientError)?;
-            
-        // Skip the 8-byte discriminator and deserialize
-        if account.data.len() < 8 {
-            return Err(USDvError::SerializationError("Account data too short".to_string()));
+            .get_account(&minter)
+            .await
+            .map_err(USDvClientError::from)?;
+
+        // Checks the account discriminator as well as the layout
+        MinterInfo::try_deserialize(&mut account.data.as_slice())
+            .map_err(|e| USDvClientError::SerializationError(e.to_string()))
+    }
+
+    /// Turn allowlist-gated minting and redemption on or off (admin only)
+    pub async fn set_allowlist_mode<S: Signer + ?Sized>(
+        &self,
+        admin: &S,
+        enabled: bool,
+    ) -> Result<Signature> {
+        let instruction = self.instruction_builder.set_allowlist_mode(&signer_pubkey(admin)?, enabled);
+
+        self.send_transaction_with_signers(&[instruction], admin, &[]).await
+    }
+
+    /// Apply allowlist updates, split into as many transactions as needed (admin only)
+    pub async fn update_allowlist<S: Signer + ?Sized>(
+        &self,
+        admin: &S,
+        updates: &[AllowlistUpdate],
+    ) -> Result<Vec<Signature>> {
+        let admin_pubkey = signer_pubkey(admin)?;
+        let mut signatures = Vec::new();
+
+        for batch in updates.chunks(ALLOWLIST_BATCH_SIZE) {
+            let instruction = self.instruction_builder.update_allowlist(&admin_pubkey, batch);
+
+            signatures.push(
+                self.send_transaction_with_signers(&[instruction], admin, &[]).await?,
+            );
         }
 
-        let state_data = &account.data[8..];
-        let state: ProgramStateInfo = borsh::from_slice(state_data)
-            .map_err(|e| USDvError::SerializationError(e.to_string()))?;
+        Ok(signatures)
+    }
+
+    /// Import an allowlist from CSV and apply it (admin only)
+    ///
+    /// See [`crate::allowlist`] for the expected format.
+    pub async fn import_allowlist_csv<S: Signer + ?Sized, R: std::io::Read>(
+        &self,
+        admin: &S,
+        csv: R,
+    ) -> Result<Vec<Signature>> {
+        let updates = parse_allowlist_csv(csv)
+            .map_err(|e| USDvClientError::SerializationError(e.to_string()))?;
+
+        self.update_allowlist(admin, &updates).await
+    }
+
+    /// Get a wallet's allowlist entry, if it has one
+    pub async fn get_allowlist_entry(&self, wallet: &Pubkey) -> Result<Option<AllowlistEntryInfo>> {
+        let (entry, _) = derive_allowlist_entry_pda(&self.config.program_id, wallet);
+
+        let account = match self.rpc_client
+            .get_account_with_commitment(&entry, self.rpc_client.commitment())
+            .await
+            .map_err(USDvClientError::from)?
+            .value
+        {
+            Some(account) => account,
+            None => return Ok(None),
+        };
+
+        AllowlistEntryInfo::try_deserialize(&mut account.data.as_slice())
+            .map(Some)
+            .map_err(|e| USDvClientError::SerializationError(e.to_string()))
+    }
+
+    /// Check the configuration against the cluster
+    ///
+    /// Besides [`USDvConfig::validate`], the program must be deployed and
+    /// executable, and the program state must exist, be owned by the
+    /// program, decode and name the configured mints. Returns the state.
+    pub async fn verify_deployment(&self) -> Result<ProgramStateInfo> {
+        self.config.validate()?;
+
+        let accounts = self
+            .rpc_client
+            .get_multiple_accounts(&[self.config.program_id, self.config.program_state])
+            .await
+            .map_err(USDvClientError::from)?;
+        let (program, state) = match accounts.as_slice() {
+            [program, state] => (program.as_ref(), state.as_ref()),
+            _ => return Err(USDvClientError::SerializationError("Unexpected account count".to_string())),
+        };
+
+        match program {
+            Some(program) if program.executable => {}
+            Some(_) => {
+                return Err(USDvClientError::ConfigurationError(format!(
+                    "Program {} is not executable",
+                    self.config.program_id
+                )))
+            }
+            None => {
+                return Err(USDvClientError::ConfigurationError(format!(
+                    "Program {} is not deployed on {}",
+                    self.config.program_id, self.config.cluster
+                )))
+            }
+        }
 
+        let state_account = state.ok_or_else(|| {
+            USDvClientError::ConfigurationError(format!(
+                "Program state {} does not exist; the program is not initialized",
+                self.config.program_state
+            ))
+        })?;
+        if state_account.owner != self.config.program_id {
+            return Err(USDvClientError::ConfigurationError(format!(
+                "Program state {} is owned by {}, not the program",
+                self.config.program_state, state_account.owner
+            )));
+        }
+        let state = ProgramStateInfo::from_account_data(&state_account.data)?;
+
+        if state.usdc_mint != self.config.usdc_mint {
+            return Err(USDvClientError::ConfigurationError(format!(
+                "Configured USDC mint {} does not match the deployed {}",
+                self.config.usdc_mint, state.usdc_mint
+            )));
+        }
+        if state.usdv_mint != self.config.usdv_mint {
+            return Err(USDvClientError::ConfigurationError(format!(
+                "Configured USDv mint {} does not match the deployed {}",
+                self.config.usdv_mint, state.usdv_mint
+            )));
+        }
         Ok(state)
     }
 
+    /// Get program state information
+    pub async fn get_program_state(&self) -> Result<ProgramStateInfo> {
+        let account = self.rpc_client
+            .get_account(&self.config.program_state)
+            .await
+            .map_err(USDvClientError::from)?;
+
+        ProgramStateInfo::from_account_data(&account.data)
+    }
+
     /// Get user's USDv token balance
     pub async fn get_usdv_balance(&self, user: &Pubkey) -> Result<u64> {
-        let token_account = get_associated_token_address(user, &self.config.usdv_mint);
+        let token_account = derive_associated_token_account(user, &self.config.usdv_mint);
         
-        match self.rpc_client.get_token_account_balance(&token_account) {
+        match self.rpc_client.get_token_account_balance(&token_account).await {
             Ok(balance) => {
                 balance.amount.parse()
-                    .map_err(|_| USDvError::SerializationError("Invalid balance format".to_string()))
+                    .map_err(|_| USDvClientError::SerializationError("Invalid balance format".to_string()))
             }
             Err(e) => {
                 // Check if it's an account not found error
                 if e.to_string().contains("could not find account") {
                     Ok(0) // Account doesn't exist, balance is 0
                 } else {
-                    Err(USDvError::SolanaClientError(e))
+                    Err(e.into())
                 }
             }
         }
@@ -204,19 +536,19 @@ impl USDvClient {
 
     /// Get user's USDC token balance
     pub async fn get_usdc_balance(&self, user: &Pubkey) -> Result<u64> {
-        let token_account = get_associated_token_address(user, &self.config.usdc_mint);
+        let token_account = derive_associated_token_account(user, &self.config.usdc_mint);
         
-        match self.rpc_client.get_token_account_balance(&token_account) {
+        match self.rpc_client.get_token_account_balance(&token_account).await {
             Ok(balance) => {
                 balance.amount.parse()
-                    .map_err(|_| USDvError::SerializationError("Invalid balance format".to_string()))
+                    .map_err(|_| USDvClientError::SerializationError("Invalid balance format".to_string()))
             }
             Err(e) => {
                 // Check if it's an account not found error
                 if e.to_string().contains("could not find account") {
                     Ok(0) // Account doesn't exist, balance is 0
                 } else {
-                    Err(USDvError::SolanaClientError(e))
+                    Err(e.into())
                 }
             }
         }
@@ -227,8 +559,8 @@ impl USDvClient {
         let usdc_balance = self.get_usdc_balance(user).await?;
         let usdv_balance = self.get_usdv_balance(user).await?;
 
-        let usdc_account = get_associated_token_address(user, &self.config.usdc_mint);
-        let usdv_account = get_associated_token_address(user, &self.config.usdv_mint);
+        let usdc_account = derive_associated_token_account(user, &self.config.usdc_mint);
+        let usdv_account = derive_associated_token_account(user, &self.config.usdv_mint);
 
         let usdc_token_balance = crate::types::TokenBalance::new(
             self.config.usdc_mint,
@@ -251,6 +583,58 @@ impl USDvClient {
         ))
     }
 
+    /// Deposits, burns and off-chain mints and redemptions of `user` over `range`
+    ///
+    /// Pages through every signature of the user's USDC and USDv token
+    /// accounts in the range and reads each transaction back, so a long
+    /// range costs many RPC calls. See [`crate::history`].
+    pub async fn get_user_history(&self, user: &Pubkey, range: HistoryRange) -> Result<UserHistory> {
+        history::fetch_user_history(
+            &self.rpc_client,
+            &self.config,
+            user,
+            range,
+            self.rpc_client.commitment(),
+        )
+        .await
+    }
+
+    /// Stream the program's events live, over the pubsub endpoint of the RPC URL
+    ///
+    /// The websocket URL is derived with [`subscription::websocket_url`] and
+    /// the RPC client's commitment is used; see [`crate::subscription`].
+    pub async fn subscribe_events(&self) -> Result<EventStream> {
+        let mut config = SubscriptionConfig::new(subscription::websocket_url(&self.rpc_client.url()));
+        config.commitment = self.rpc_client.commitment();
+        self.subscribe_events_with_config(config).await
+    }
+
+    /// Stream the program's events live with explicit subscription settings
+    pub async fn subscribe_events_with_config(&self, config: SubscriptionConfig) -> Result<EventStream> {
+        subscription::subscribe_events(self.rpc_client.clone(), self.config.program_id, config).await
+    }
+
+    /// Stream changes to the program state and vault balance
+    ///
+    /// Subscribes on the websocket URL derived from the RPC URL, falling
+    /// back to polling while it is unreachable; see [`crate::watcher`].
+    pub async fn watch_program_state(&self) -> Result<StateChangeStream> {
+        let mut config = WatcherConfig::new(subscription::websocket_url(&self.rpc_client.url()));
+        config.commitment = self.rpc_client.commitment();
+        self.watch_program_state_with_config(config).await
+    }
+
+    /// Stream changes to the program state and vault balance with explicit watcher settings
+    pub async fn watch_program_state_with_config(&self, config: WatcherConfig) -> Result<StateChangeStream> {
+        watcher::watch_program_state(
+            self.rpc_client.clone(),
+            self.config.program_state,
+            derive_vault_usdc_account(&self.config.program_id, &self.config.usdc_mint),
+            config,
+        )
+        .await
+    }
+
     /// Get system health information
     pub async fn get_system_health(&self) -> Result<crate::types::SystemHealth> {
         let program_state = self.get_program_state().await?;
@@ -272,7 +656,7 @@ impl USDvClient {
 
         // Check system health
         let health = self.get_system_health().await?;
-        if !health.is_healthy {
+        if !health.is_healthy || !health.program_state.is_minting_enabled() {
             return Ok(false);
         }
 
@@ -306,10 +690,11 @@ impl USDvClient {
         let payer = Keypair::new(); // Dummy payer for estimation
         let recent_blockhash = self.rpc_client
             .get_latest_blockhash()
-            .map_err(USDvError::SolanaClientError)?;
+            .await
+            .map_err(USDvClientError::from)?;
 
         let transaction = Transaction::new_signed_with_payer(
-            &[instruction.clone()],
+            std::slice::from_ref(instruction),
             Some(&payer.pubkey()),
             &[&payer],
             recent_blockhash,
@@ -328,38 +713,264 @@ impl USDvClient {
         utils::get_network_info(&self.rpc_client).await
     }
 
+    /// Sign, send and confirm `instructions`, paid by the first signer
+    ///
+    /// Honors the retry, timeout, preflight and commitment settings of
+    /// [`ClientConfig`](crate::types::ClientConfig); see [`crate::submission`].
+    /// The client's fee payer is not added: include it first in `signers`
+    /// to have it pay.
+    pub async fn submit<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        signers: &T,
+    ) -> Result<SubmissionOutcome> {
+        let payer = *signers
+            .try_pubkeys()
+            .map_err(|e| USDvClientError::SigningError(e.to_string()))?
+            .first()
+            .ok_or_else(|| USDvClientError::SigningError("no fee payer".to_string()))?;
+
+        SubmissionEngine::new(&self.rpc_client, (&self.client_config).into())
+            .submit(instructions, &payer, signers)
+            .await
+    }
+
+    /// Create a durable nonce account advanced by `nonce_authority`
+    ///
+    /// `funder` pays its rent-exempt balance. See [`crate::offline`].
+    pub async fn create_nonce_account<S: Signer + ?Sized>(
+        &self,
+        funder: &S,
+        nonce_account: &Keypair,
+        nonce_authority: &Pubkey,
+    ) -> Result<Signature> {
+        let lamports = self.rpc_client
+            .get_minimum_balance_for_rent_exemption(NonceState::size())
+            .await
+            .map_err(USDvClientError::from)?;
+
+        let instructions = create_nonce_account(
+            &signer_pubkey(funder)?,
+            &nonce_account.pubkey(),
+            nonce_authority,
+            lamports,
+        );
+
+        self.send_transaction_with_signers(&instructions, funder, &[nonce_account]).await
+    }
+
+    /// Advance a durable nonce, invalidating transactions signed against it
+    pub async fn advance_nonce<S: Signer + ?Sized>(
+        &self,
+        nonce_authority: &S,
+        nonce_account: &Pubkey,
+    ) -> Result<Signature> {
+        let instruction = advance_nonce_account(nonce_account, &signer_pubkey(nonce_authority)?);
+
+        self.send_transaction_with_signers(&[instruction], nonce_authority, &[]).await
+    }
+
+    /// Get the current value and authority of a durable nonce account
+    pub async fn get_nonce(&self, nonce_account: &Pubkey) -> Result<NonceInfo> {
+        let account = self.rpc_client
+            .get_account(nonce_account)
+            .await
+            .map_err(USDvClientError::from)?;
+
+        NonceInfo::from_account(nonce_account, &account)
+    }
+
+    /// Build an unsigned transaction against the current value of `nonce_account`
+    ///
+    /// The result can be exported, signed offline and submitted later with
+    /// [`Self::submit_signed`]; see [`crate::offline`]. No ComputeBudget
+    /// instructions are added: include them in `instructions` if needed.
+    pub async fn prepare_offline_transaction(
+        &self,
+        instructions: &[Instruction],
+        fee_payer: &Pubkey,
+        nonce_account: &Pubkey,
+    ) -> Result<Transaction> {
+        let nonce = self.get_nonce(nonce_account).await?;
+        Ok(offline::nonce_transaction(instructions, fee_payer, &nonce))
+    }
+
+    /// Send and confirm a transaction that was signed elsewhere
+    ///
+    /// See [`SubmissionEngine::submit_signed`].
+    pub async fn submit_signed(&self, transaction: &Transaction) -> Result<Signature> {
+        SubmissionEngine::new(&self.rpc_client, (&self.client_config).into())
+            .submit_signed(transaction)
+            .await?
+            .into_result(&self.config.program_id)
+    }
+
+    /// Simulate `instructions` paid by `payer`, without signing or sending
+    ///
+    /// See [`crate::simulation`].
+    pub async fn simulate(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+    ) -> Result<SimulationResult> {
+        simulation::simulate(
+            &self.rpc_client,
+            &self.config.program_id,
+            instructions,
+            payer,
+            self.rpc_client.commitment(),
+        )
+        .await
+    }
+
+    /// Dry-run a deposit of `amount` USDC by `user`
+    ///
+    /// Amount and balance checks are left to the program, so a failure is
+    /// reported as a decoded [`USDvError`](usdv_results::USDvError). The
+    /// balances cover the user's USDC and USDv accounts and the vault.
+    pub async fn simulate_deposit_and_mint(
+        &self,
+        user: &Pubkey,
+        amount: u64,
+    ) -> Result<SimulationResult> {
+        let instruction = self.deposit_and_mint_instruction(user, amount).await?;
+        self.simulate(&[instruction], user).await
+    }
+
+    /// Dry-run a burn of `amount` USDv by `user`
+    ///
+    /// Like [`Self::simulate_deposit_and_mint`], checks are left to the
+    /// program.
+    pub async fn simulate_burn_and_withdraw(
+        &self,
+        user: &Pubkey,
+        amount: u64,
+    ) -> Result<SimulationResult> {
+        let instruction = self.burn_and_withdraw_instruction(user, amount).await?;
+        self.simulate(&[instruction], user).await
+    }
+
     // Private helper methods
 
-    /// Send a transaction with signers and proper error handling
-    async fn send_transaction_with_signers(
+    /// Deposit instruction for `user`, including its allowlist entry if it has one
+    async fn deposit_and_mint_instruction(&self, user: &Pubkey, amount: u64) -> Result<Instruction> {
+        let with_allowlist_entry = self.has_allowlist_entry(user).await?;
+        Ok(self.instruction_builder.deposit_and_mint(
+            user,
+            &self.config.usdc_mint,
+            &self.config.usdv_mint,
+            with_allowlist_entry,
+            amount,
+        ))
+    }
+
+    /// Burn instruction for `user`, including its allowlist entry if it has one
+    async fn burn_and_withdraw_instruction(&self, user: &Pubkey, amount: u64) -> Result<Instruction> {
+        let with_allowlist_entry = self.has_allowlist_entry(user).await?;
+        Ok(self.instruction_builder.burn_and_withdraw(
+            user,
+            &self.config.usdc_mint,
+            &self.config.usdv_mint,
+            with_allowlist_entry,
+            amount,
+        ))
+    }
+
+    /// Whether `wallet` has an allowlist entry account
+    async fn has_allowlist_entry(&self, wallet: &Pubkey) -> Result<bool> {
+        let (entry, _) = derive_allowlist_entry_pda(&self.config.program_id, wallet);
+
+        Ok(self.rpc_client
+            .get_account_with_commitment(&entry, self.rpc_client.commitment())
+            .await
+            .map_err(USDvClientError::from)?
+            .value
+            .is_some())
+    }
+
+    /// Send a transaction signed by `authority`, `additional` and the fee payer
+    async fn send_transaction_with_signers<S: Signer + ?Sized>(
         &self,
         instructions: &[Instruction],
-        signers: &[&Keypair],
+        authority: &S,
+        additional: &[&(dyn Signer + Send + Sync)],
     ) -> Result<Signature> {
-        let recent_blockhash = self.rpc_client
-            .get_latest_blockhash()
-            .map_err(USDvError::SolanaClientError)?;
-        
-        let mut transaction = Transaction::new_with_payer(
-            instructions,
-            Some(&signers[0].pubkey()),
-        );
-        
-        transaction.sign(signers, recent_blockhash);
-        
-        // Use retry mechanism for robustness
-        let commitment = if self.client_config.use_confirmed_commitment {
-            CommitmentConfig::confirmed()
-        } else {
-            CommitmentConfig::processed()
+        self.submit_with_signers(instructions, authority, additional)
+            .await?
+            .into_result(&self.config.program_id)
+    }
+
+    /// Like [`Self::send_transaction_with_signers`], keeping the full outcome
+    async fn submit_with_signers<S: Signer + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        authority: &S,
+        additional: &[&(dyn Signer + Send + Sync)],
+    ) -> Result<SubmissionOutcome> {
+        let signers = TransactionSigners {
+            fee_payer: self.fee_payer.as_deref(),
+            authority,
+            additional,
         };
+        let payer = signers
+            .fee_payer_pubkey()
+            .map_err(|e| USDvClientError::SigningError(e.to_string()))?;
+
+        SubmissionEngine::new(&self.rpc_client, (&self.client_config).into())
+            .submit(instructions, &payer, &signers)
+            .await
+    }
 
-        usdv_utils::send_and_confirm_transaction_with_retries(
+    /// Metadata of a confirmed transaction, or the error of any other outcome
+    ///
+    /// The transaction has landed by then, so a failure to fetch its
+    /// metadata is not an error: only the signature and slot are reported.
+    async fn landed_transaction_info(&self, outcome: SubmissionOutcome) -> Result<TransactionInfo> {
+        let (signature, slot) = match outcome {
+            SubmissionOutcome::Confirmed { signature, slot } => (signature, slot),
+            outcome => {
+                return Err(outcome
+                    .into_result(&self.config.program_id)
+                    .expect_err("only confirmed transactions succeed"))
+            }
+        };
+
+        let commitment = SubmissionConfig::from(&self.client_config).commitment;
+        match receipts::fetch_transaction_info(
             &self.rpc_client,
-            &transaction,
+            &signature,
+            &self.config.program_id,
             commitment,
-            self.client_config.max_retries,
-        ).await
+        )
+        .await
+        {
+            Ok(info) => Ok(info),
+            Err(_) => {
+                let status = if commitment.is_finalized() {
+                    TransactionStatus::Finalized
+                } else {
+                    TransactionStatus::Confirmed
+                };
+                Ok(TransactionInfo::landed(signature.to_string(), slot, status))
+            }
+        }
+    }
+
+    /// `user`'s USDC and USDv balances after `transaction`, if it reports them
+    fn new_balances(&self, transaction: &TransactionInfo, user: &Pubkey) -> (Option<u64>, Option<u64>) {
+        let balance = |mint: &Pubkey| {
+            transaction
+                .balance_change(&derive_associated_token_account(user, mint))
+                .and_then(|change| change.post)
+        };
+        (balance(&self.config.usdc_mint), balance(&self.config.usdv_mint))
     }
 }
 
+/// Public key of `signer`, which may have to ask a device or service for it
+fn signer_pubkey<S: Signer + ?Sized>(signer: &S) -> Result<Pubkey> {
+    signer
+        .try_pubkey()
+        .map_err(|e| USDvClientError::SigningError(e.to_string()))
+}
+
@@ -0,0 +1,207 @@
+//! Priority fees and compute unit limits
+//!
+//! [`SubmissionEngine`](crate::submission::SubmissionEngine) prepends
+//! ComputeBudget instructions to every transaction according to the
+//! [`PriorityFeeStrategy`] and [`ComputeUnitLimit`] in
+//! [`ClientConfig`](crate::types::ClientConfig). Transactions that already
+//! carry ComputeBudget instructions are sent unchanged.
+
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_compute_budget_interface::{self as compute_budget, ComputeBudgetInstruction};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    transaction::Transaction,
+};
+use usdv_results::USDvClientError;
+
+/// Largest compute unit limit a transaction can request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// How the compute unit price, in micro-lamports per unit, is chosen
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PriorityFeeStrategy {
+    /// No priority fee
+    None,
+    /// A fixed price
+    Fixed { micro_lamports: u64 },
+    /// A percentile (0-100) of the recent prioritization fees paid by
+    /// transactions that wrote to the same accounts
+    Percentile { percentile: u8 },
+    /// Another strategy, never paying more than `max_micro_lamports`
+    Capped {
+        strategy: Box<PriorityFeeStrategy>,
+        max_micro_lamports: u64,
+    },
+}
+
+impl Default for PriorityFeeStrategy {
+    /// The median recent fee, capped at 1,000,000 micro-lamports per unit
+    fn default() -> Self {
+        PriorityFeeStrategy::Capped {
+            strategy: Box::new(PriorityFeeStrategy::Percentile { percentile: 50 }),
+            max_micro_lamports: 1_000_000,
+        }
+    }
+}
+
+impl PriorityFeeStrategy {
+    /// Compute unit price for a transaction writing `writable_accounts`
+    pub async fn resolve(&self, rpc_client: &RpcClient, writable_accounts: &[Pubkey]) -> Result<u64> {
+        let mut strategy = self;
+        let mut cap = u64::MAX;
+        while let PriorityFeeStrategy::Capped { strategy: inner, max_micro_lamports } = strategy {
+            cap = cap.min(*max_micro_lamports);
+            strategy = inner;
+        }
+
+        let micro_lamports = match strategy {
+            PriorityFeeStrategy::None => 0,
+            PriorityFeeStrategy::Fixed { micro_lamports } => *micro_lamports,
+            PriorityFeeStrategy::Percentile { percentile } => {
+                let fees: Vec<u64> = rpc_client
+                    .get_recent_prioritization_fees(writable_accounts)
+                    .await
+                    .map_err(USDvClientError::from)?
+                    .into_iter()
+                    .map(|fee| fee.prioritization_fee)
+                    .collect();
+                percentile_fee(&fees, *percentile)
+            }
+            PriorityFeeStrategy::Capped { .. } => unreachable!("caps are unwrapped above"),
+        };
+
+        Ok(micro_lamports.min(cap))
+    }
+}
+
+/// How the compute unit limit is chosen
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComputeUnitLimit {
+    /// Leave the runtime default of 200,000 units per instruction
+    Default,
+    /// A fixed limit
+    Fixed { units: u32 },
+    /// Units consumed in simulation plus `headroom_percent`; falls back to
+    /// the runtime default when simulation reports no usage
+    Simulated { headroom_percent: u32 },
+}
+
+impl Default for ComputeUnitLimit {
+    fn default() -> Self {
+        ComputeUnitLimit::Simulated { headroom_percent: 10 }
+    }
+}
+
+/// Nearest-rank `percentile` of `fees`, 0 when there are none
+pub fn percentile_fee(fees: &[u64], percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+
+    let mut sorted = fees.to_vec();
+    sorted.sort_unstable();
+    let percentile = usize::from(percentile.min(100));
+    let rank = (percentile * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// `units_consumed` plus `headroom_percent`, capped at [`MAX_COMPUTE_UNIT_LIMIT`]
+pub fn limit_with_headroom(units_consumed: u64, headroom_percent: u32) -> u32 {
+    let headroom = units_consumed.saturating_mul(u64::from(headroom_percent)) / 100;
+    let limit = units_consumed.saturating_add(headroom);
+    u32::try_from(limit)
+        .unwrap_or(MAX_COMPUTE_UNIT_LIMIT)
+        .min(MAX_COMPUTE_UNIT_LIMIT)
+}
+
+/// Accounts written by `instructions`, without duplicates
+pub fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = Vec::new();
+    for meta in instructions.iter().flat_map(|ix| &ix.accounts) {
+        if meta.is_writable && !accounts.contains(&meta.pubkey) {
+            accounts.push(meta.pubkey);
+        }
+    }
+    accounts
+}
+
+/// `instructions` with ComputeBudget instructions prepended
+///
+/// Returns `instructions` unchanged if they already include a ComputeBudget
+/// instruction.
+pub async fn with_compute_budget(
+    rpc_client: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    priority_fee: &PriorityFeeStrategy,
+    compute_unit_limit: &ComputeUnitLimit,
+    commitment: CommitmentConfig,
+) -> Result<Vec<Instruction>> {
+    if instructions
+        .iter()
+        .any(|ix| compute_budget::check_id(&ix.program_id))
+    {
+        return Ok(instructions.to_vec());
+    }
+
+    let mut budget = Vec::new();
+
+    let micro_lamports = priority_fee
+        .resolve(rpc_client, &writable_accounts(instructions))
+        .await?;
+    if micro_lamports > 0 {
+        budget.push(ComputeBudgetInstruction::set_compute_unit_price(micro_lamports));
+    }
+
+    let units = match compute_unit_limit {
+        ComputeUnitLimit::Default => None,
+        ComputeUnitLimit::Fixed { units } => Some(*units),
+        ComputeUnitLimit::Simulated { headroom_percent } => {
+            // Simulate with the final instruction set and the maximum limit,
+            // so the budget instructions' own cost is counted
+            let mut simulated = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+                MAX_COMPUTE_UNIT_LIMIT,
+            )];
+            simulated.extend(budget.iter().cloned());
+            simulated.extend(instructions.iter().cloned());
+
+            simulate_units_consumed(rpc_client, &simulated, payer, commitment)
+                .await?
+                .map(|units| limit_with_headroom(units, *headroom_percent))
+        }
+    };
+    if let Some(units) = units {
+        budget.insert(0, ComputeBudgetInstruction::set_compute_unit_limit(units));
+    }
+
+    budget.extend(instructions.iter().cloned());
+    Ok(budget)
+}
+
+/// Units consumed by an unsigned simulation of `instructions`
+async fn simulate_units_consumed(
+    rpc_client: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    commitment: CommitmentConfig,
+) -> Result<Option<u64>> {
+    let transaction = Transaction::new_with_payer(instructions, Some(payer));
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(commitment),
+        ..RpcSimulateTransactionConfig::default()
+    };
+
+    Ok(rpc_client
+        .simulate_transaction_with_config(&transaction, config)
+        .await
+        .map_err(USDvClientError::from)?
+        .value
+        .units_consumed)
+}
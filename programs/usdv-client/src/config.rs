@@ -1,6 +1,16 @@
 
+use crate::manifest::{DeploymentManifest, DEFAULT_MANIFEST_PATH, MANIFEST_ENV};
+use crate::Result;
 pub use solana_sdk::pubkey::Pubkey;
-use std::str::FromStr;
+use std::path::Path;
+use usdv_results::USDvClientError;
+use usdv_utils::pda::derive_program_state_pda;
+
+/// Circle's USDC mint on mainnet-beta
+const USDC_MINT_MAINNET: Pubkey = solana_sdk::pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+/// Circle's USDC mint on devnet
+const USDC_MINT_DEVNET: Pubkey = solana_sdk::pubkey!("4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU");
 
 /// Configuration for USDv stablecoin program
 #[derive(Debug, Clone)]
@@ -13,42 +23,83 @@ pub struct USDvConfig {
     pub usdc_mint: Pubkey,
     /// USDv mint public key
     pub usdv_mint: Pubkey,
-    /// Network cluster name
+    /// Network cluster name, or an RPC URL
     pub cluster: String,
 }
 
 impl USDvConfig {
     /// Create configuration for mainnet deployment
+    ///
+    /// The cluster presets know the program and USDC mint, but not the USDv
+    /// mint, which is a fresh keypair chosen at initialization: it is left
+    /// as `Pubkey::default()` and fails [`USDvConfig::validate`]. Load a
+    /// deployment with [`USDvConfig::load`] instead.
     pub fn mainnet() -> Self {
-        Self {
-            program_id: Pubkey::from_str("USDvCoinProgram11111111111111111111111111111").unwrap(),
-            program_state: Pubkey::from_str("USDvState1111111111111111111111111111111111").unwrap(),
-            usdc_mint: Pubkey::from_str("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap(), // Real USDC mint
-            usdv_mint: Pubkey::from_str("USDvMint11111111111111111111111111111111111").unwrap(),
-            cluster: "mainnet-beta".to_string(),
-        }
+        Self::preset(USDC_MINT_MAINNET, "mainnet-beta")
     }
 
-    /// Create configuration for devnet deployment
+    /// Create configuration for devnet deployment; see [`USDvConfig::mainnet`]
     pub fn devnet() -> Self {
-        Self {
-            program_id: Pubkey::from_str("USDvCoinProgram11111111111111111111111111111").unwrap(),
-            program_state: Pubkey::from_str("USDvState1111111111111111111111111111111111").unwrap(),
-            usdc_mint: Pubkey::from_str("4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU").unwrap(), // Devnet USDC
-            usdv_mint: Pubkey::from_str("USDvMint11111111111111111111111111111111111").unwrap(),
-            cluster: "devnet".to_string(),
-        }
+        Self::preset(USDC_MINT_DEVNET, "devnet")
     }
 
-    /// Create configuration for local testing
+    /// Create configuration for local testing; see [`USDvConfig::mainnet`]
+    ///
+    /// Uses the mainnet USDC mint, as cloned into a local validator with
+    /// `solana-test-validator --clone EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v`.
     pub fn localnet() -> Self {
+        Self::preset(USDC_MINT_MAINNET, "localnet")
+    }
+
+    fn preset(usdc_mint: Pubkey, cluster: &str) -> Self {
         Self {
-            program_id: Pubkey::from_str("USDvCoinProgram11111111111111111111111111111").unwrap(),
-            program_state: Pubkey::from_str("USDvState1111111111111111111111111111111111").unwrap(),
-            usdc_mint: Pubkey::from_str("So11111111111111111111111111111111111111112").unwrap(), // Wrapped SOL for testing
-            usdv_mint: Pubkey::from_str("USDvMint11111111111111111111111111111111111").unwrap(),
-            cluster: "localnet".to_string(),
+            program_id: usdv_program::ID,
+            program_state: derive_program_state_pda(&usdv_program::ID).0,
+            usdc_mint,
+            usdv_mint: Pubkey::default(),
+            cluster: cluster.to_string(),
+        }
+    }
+
+    /// Load profile `profile` of the deployment manifest at `path`
+    ///
+    /// Falls back to the manifest's `default_profile`. Environment
+    /// variables override the manifest; see [`crate::manifest`].
+    pub fn load(path: impl AsRef<Path>, profile: Option<&str>) -> Result<Self> {
+        DeploymentManifest::load(path)?.config(profile, |name| std::env::var(name).ok())
+    }
+
+    /// Load the manifest named by `USDV_MANIFEST`, or `usdv.toml`
+    pub fn from_env() -> Result<Self> {
+        let path = std::env::var(MANIFEST_ENV).unwrap_or_else(|_| DEFAULT_MANIFEST_PATH.to_string());
+        Self::load(path, None)
+    }
+
+    /// Check the addresses for consistency, without contacting the cluster
+    ///
+    /// The program state must be the PDA of the program, and the mints must
+    /// be set and distinct. `USDvClient::verify_deployment` checks them
+    /// against the cluster.
+    pub fn validate(&self) -> Result<()> {
+        let expected_state = derive_program_state_pda(&self.program_id).0;
+        if self.program_state != expected_state {
+            return Err(USDvClientError::ConfigurationError(format!(
+                "Program state {} is not the state PDA {} of program {}",
+                self.program_state, expected_state, self.program_id
+            )));
+        }
+        if self.usdc_mint == Pubkey::default() {
+            return Err(USDvClientError::ConfigurationError("USDC mint is not set".to_string()));
+        }
+        if self.usdv_mint == Pubkey::default() {
+            return Err(USDvClientError::ConfigurationError("USDv mint is not set".to_string()));
+        }
+        if self.usdc_mint == self.usdv_mint {
+            return Err(USDvClientError::ConfigurationError(
+                "USDC and USDv mints are the same account".to_string(),
+            ));
         }
+        Ok(())
     }
 
     /// Create custom configuration
@@ -0,0 +1,86 @@
+//! Decoding of events emitted by the USDv program
+//!
+//! Anchor emits an event as a `Program data:` log line holding the base64
+//! of its 8-byte discriminator followed by its Borsh encoding. Both the
+//! discriminators and the event types are the program's own, so a change
+//! to an event in the program cannot silently break decoding here.
+
+use crate::logs::LogEntry;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+pub use usdv_program::events::{
+    AllowlistModeChanged, AllowlistUpdated, Burned, Deposited, MinterConfigured, OffchainMint,
+    OffchainRedemption, TokensRecovered, UnclaimedCollateralSwept, WindDownInitiated,
+};
+
+/// Any event emitted by the USDv program
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum USDvEvent {
+    Deposited(Deposited),
+    Burned(Burned),
+    WindDownInitiated(WindDownInitiated),
+    UnclaimedCollateralSwept(UnclaimedCollateralSwept),
+    TokensRecovered(TokensRecovered),
+    MinterConfigured(MinterConfigured),
+    OffchainMint(OffchainMint),
+    OffchainRedemption(OffchainRedemption),
+    AllowlistModeChanged(AllowlistModeChanged),
+    AllowlistUpdated(AllowlistUpdated),
+}
+
+impl USDvEvent {
+    /// Decode an event from its discriminator and Borsh encoding
+    ///
+    /// Returns `None` for data that is not a USDv event.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, body) = data.split_at(8);
+
+        macro_rules! decode {
+            ($($variant:ident),* $(,)?) => {
+                $(
+                    if discriminator == $variant::DISCRIMINATOR {
+                        return $variant::try_from_slice(body).ok().map(USDvEvent::$variant);
+                    }
+                )*
+            };
+        }
+        decode!(
+            Deposited,
+            Burned,
+            WindDownInitiated,
+            UnclaimedCollateralSwept,
+            TokensRecovered,
+            MinterConfigured,
+            OffchainMint,
+            OffchainRedemption,
+            AllowlistModeChanged,
+            AllowlistUpdated,
+        );
+        None
+    }
+}
+
+/// Events emitted by `program_id`, in log order
+///
+/// Only `Program data:` lines written while `program_id` was executing are
+/// considered, so another program cannot forge USDv events.
+pub fn decode_events(entries: &[LogEntry], program_id: &Pubkey) -> Vec<USDvEvent> {
+    entries
+        .iter()
+        .filter_map(|entry| match entry {
+            LogEntry::Data {
+                program_id: Some(emitter),
+                data,
+            } if emitter == program_id => data.first(),
+            _ => None,
+        })
+        .filter_map(|encoded| STANDARD.decode(encoded).ok())
+        .filter_map(|data| USDvEvent::decode(&data))
+        .collect()
+}
@@ -0,0 +1,443 @@
+//! User transaction history and statements
+//!
+//! [`fetch_user_history`] pages through the signatures of a user's USDC and
+//! USDv associated token accounts, reads each successful transaction back
+//! and keeps only the USDv program instructions that moved the user's
+//! funds: deposits, burns, and off-chain mints and redemptions. Each becomes
+//! a [`HistoryRecord`] carrying the event the program emitted for it and
+//! the user's balances after the transaction.
+//!
+//! A [`UserHistory`] exports as a CSV or JSON statement, oldest first.
+//! Instructions reached through a CPI from another program are not listed.
+
+use crate::config::USDvConfig;
+use crate::events::USDvEvent;
+use crate::receipts::{self, account_keys};
+use crate::Result;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
+use std::collections::HashSet;
+use std::str::FromStr;
+use usdv_program::instruction as program;
+use usdv_results::USDvClientError;
+use usdv_utils::pda::derive_associated_token_account;
+
+/// Signatures requested per `getSignaturesForAddress` page, the RPC maximum
+pub const SIGNATURES_PAGE_SIZE: usize = 1_000;
+
+/// Time window of a history, in unix seconds
+///
+/// `start` is inclusive and `end` exclusive; `None` leaves that side open.
+/// Transactions without a block time are only included when both sides
+/// are open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryRange {
+    /// Earliest block time included
+    pub start: Option<i64>,
+    /// Block time at which the history stops
+    pub end: Option<i64>,
+}
+
+impl HistoryRange {
+    /// Every transaction ever made
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Transactions with `start <= block_time < end`
+    pub fn between(start: i64, end: i64) -> Self {
+        Self {
+            start: Some(start),
+            end: Some(end),
+        }
+    }
+
+    /// Transactions from `start` onwards
+    pub fn since(start: i64) -> Self {
+        Self {
+            start: Some(start),
+            end: None,
+        }
+    }
+
+    /// Whether a transaction with `block_time` falls inside the range
+    pub fn contains(&self, block_time: Option<i64>) -> bool {
+        match block_time {
+            Some(time) => {
+                self.start.is_none_or(|start| time >= start) && self.end.is_none_or(|end| time < end)
+            }
+            None => self.start.is_none() && self.end.is_none(),
+        }
+    }
+
+    /// Whether `block_time` is older than the range, ending a newest-first scan
+    fn is_before_start(&self, block_time: Option<i64>) -> bool {
+        matches!((self.start, block_time), (Some(start), Some(time)) if time < start)
+    }
+}
+
+/// What a history record did to the user's funds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryAction {
+    /// `deposit_and_mint`: USDC in, USDv minted
+    Deposit,
+    /// `burn_and_withdraw`: USDv burned, USDC out
+    Burn,
+    /// `mint_allowance` by an institutional minter into the user's USDv account
+    OffchainMint,
+    /// `burn_for_offchain_redemption` from the user's USDv account
+    OffchainRedemption,
+}
+
+/// One USDv program instruction that moved the user's funds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    /// Transaction signature
+    pub signature: String,
+    /// Slot the transaction landed in
+    pub slot: u64,
+    /// Block time, if the cluster reported one
+    pub block_time: Option<i64>,
+    /// What the instruction did
+    pub action: HistoryAction,
+    /// Amount passed to the instruction
+    pub amount: u64,
+    /// Change to the user's USDC balance
+    pub usdc_change: i128,
+    /// Change to the user's USDv balance
+    pub usdv_change: i128,
+    /// USDC balance after the transaction, `None` if unknown
+    pub usdc_balance: Option<u64>,
+    /// USDv balance after the transaction, `None` if unknown
+    pub usdv_balance: Option<u64>,
+    /// Event the program emitted for the instruction, if found
+    pub event: Option<USDvEvent>,
+}
+
+/// A user's history over a [`HistoryRange`], oldest first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserHistory {
+    /// User wallet
+    pub user: String,
+    /// Range the history covers
+    pub range: HistoryRange,
+    /// Records in the order their transactions landed
+    pub records: Vec<HistoryRecord>,
+}
+
+/// One CSV statement line; events are left to the JSON statement
+#[derive(Serialize)]
+struct StatementRow<'a> {
+    signature: &'a str,
+    slot: u64,
+    block_time: Option<i64>,
+    action: HistoryAction,
+    amount: u64,
+    usdc_change: i128,
+    usdv_change: i128,
+    usdc_balance: Option<u64>,
+    usdv_balance: Option<u64>,
+}
+
+impl UserHistory {
+    /// Total USDv minted to minus burned from the user over the range
+    pub fn net_usdv_change(&self) -> i128 {
+        self.records.iter().map(|record| record.usdv_change).sum()
+    }
+
+    /// CSV statement with a header row and one line per record
+    ///
+    /// Unknown balances are left empty.
+    pub fn to_csv(&self) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for record in &self.records {
+            writer
+                .serialize(StatementRow {
+                    signature: &record.signature,
+                    slot: record.slot,
+                    block_time: record.block_time,
+                    action: record.action,
+                    amount: record.amount,
+                    usdc_change: record.usdc_change,
+                    usdv_change: record.usdv_change,
+                    usdc_balance: record.usdc_balance,
+                    usdv_balance: record.usdv_balance,
+                })
+                .map_err(|e| USDvClientError::SerializationError(e.to_string()))?;
+        }
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| USDvClientError::SerializationError(e.to_string()))?;
+        String::from_utf8(bytes).map_err(|e| USDvClientError::SerializationError(e.to_string()))
+    }
+
+    /// JSON statement, including the event behind each record
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| USDvClientError::SerializationError(e.to_string()))
+    }
+}
+
+/// History of `user` over `range`
+///
+/// Failed transactions are skipped. Balances come from the transaction
+/// metadata; where the cluster did not report one, it is carried forward
+/// from the previous record when that one is known.
+pub async fn fetch_user_history(
+    rpc_client: &RpcClient,
+    config: &USDvConfig,
+    user: &Pubkey,
+    range: HistoryRange,
+    commitment: CommitmentConfig,
+) -> Result<UserHistory> {
+    let commitment = receipts::transaction_commitment(commitment);
+    let user_usdc = derive_associated_token_account(user, &config.usdc_mint);
+    let user_usdv = derive_associated_token_account(user, &config.usdv_mint);
+
+    let usdc_signatures = signatures_in_range(rpc_client, &user_usdc, range, commitment).await?;
+    let usdv_signatures = signatures_in_range(rpc_client, &user_usdv, range, commitment).await?;
+    let mut signatures = merge_newest_first(usdc_signatures, usdv_signatures);
+    signatures.reverse();
+
+    let mut records = Vec::new();
+    for (_, signature) in signatures {
+        let transaction = receipts::fetch_transaction(rpc_client, &signature, commitment).await?;
+        records.extend(transaction_records(
+            &signature,
+            &transaction,
+            &config.program_id,
+            user,
+            &user_usdc,
+            &user_usdv,
+            commitment,
+        ));
+    }
+    carry_balances_forward(&mut records);
+
+    Ok(UserHistory {
+        user: user.to_string(),
+        range,
+        records,
+    })
+}
+
+/// Successful transactions touching `address` within `range`, with their
+/// slots, newest first
+async fn signatures_in_range(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+    range: HistoryRange,
+    commitment: CommitmentConfig,
+) -> Result<Vec<(u64, Signature)>> {
+    let mut found = Vec::new();
+    let mut before = None;
+
+    'pages: loop {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            until: None,
+            limit: Some(SIGNATURES_PAGE_SIZE),
+            commitment: Some(commitment),
+        };
+        let page = rpc_client
+            .get_signatures_for_address_with_config(address, config)
+            .await
+            .map_err(USDvClientError::from)?;
+        let full_page = page.len() == SIGNATURES_PAGE_SIZE;
+
+        // Pages are newest first
+        for status in page {
+            if range.is_before_start(status.block_time) {
+                break 'pages;
+            }
+            let signature = Signature::from_str(&status.signature)
+                .map_err(|e| USDvClientError::SerializationError(e.to_string()))?;
+            before = Some(signature);
+            if status.err.is_none() && range.contains(status.block_time) {
+                found.push((status.slot, signature));
+            }
+        }
+
+        if !full_page {
+            break;
+        }
+    }
+
+    Ok(found)
+}
+
+/// Merge two newest-first signature lists into one, listing each
+/// transaction once
+///
+/// Slots alone do not order transactions within a slot. There each list
+/// keeps the cluster's order, and a transaction both lists share pins the
+/// other list's head: whatever one list has ahead of a shared transaction
+/// is newer than it.
+fn merge_newest_first(
+    first: Vec<(u64, Signature)>,
+    second: Vec<(u64, Signature)>,
+) -> Vec<(u64, Signature)> {
+    let in_first: HashSet<Signature> = first.iter().map(|(_, signature)| *signature).collect();
+    let in_second: HashSet<Signature> = second.iter().map(|(_, signature)| *signature).collect();
+    let mut first = first.into_iter().peekable();
+    let mut second = second.into_iter().peekable();
+    let mut seen = HashSet::new();
+    let mut merged = Vec::new();
+
+    loop {
+        let take_first = match (first.peek(), second.peek()) {
+            (None, None) => break,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (Some(&(first_slot, first_signature)), Some(&(second_slot, second_signature))) => {
+                if first_slot != second_slot {
+                    first_slot > second_slot
+                } else if first_signature == second_signature {
+                    second.next();
+                    true
+                } else {
+                    // The head of `second` is still to come in `first`, so
+                    // the head of `first` landed after it
+                    in_first.contains(&second_signature) || !in_second.contains(&first_signature)
+                }
+            }
+        };
+        let next = if take_first { first.next() } else { second.next() };
+        if let Some((slot, signature)) = next {
+            if seen.insert(signature) {
+                merged.push((slot, signature));
+            }
+        }
+    }
+    merged
+}
+
+/// Records for the USDv instructions of `transaction` that touch the user's accounts
+fn transaction_records(
+    signature: &Signature,
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    program_id: &Pubkey,
+    user: &Pubkey,
+    user_usdc: &Pubkey,
+    user_usdv: &Pubkey,
+    commitment: CommitmentConfig,
+) -> Vec<HistoryRecord> {
+    let Some(meta) = transaction.transaction.meta.as_ref() else {
+        return Vec::new();
+    };
+    let Some(decoded) = transaction.transaction.transaction.decode() else {
+        return Vec::new();
+    };
+    let Some(keys) = account_keys(&transaction.transaction.transaction, meta) else {
+        return Vec::new();
+    };
+    let info = receipts::transaction_info(signature, transaction, program_id, commitment);
+    let balance = |account: &Pubkey| info.balance_change(account).and_then(|change| change.post);
+    let mut events: Vec<Option<USDvEvent>> = info.events.iter().cloned().map(Some).collect();
+
+    let mut records = Vec::new();
+    for instruction in decoded.message.instructions() {
+        if keys.get(usize::from(instruction.program_id_index)) != Some(program_id) {
+            continue;
+        }
+        let Some((action, amount)) = decode_instruction(&instruction.data) else {
+            continue;
+        };
+        let touches = |account: &Pubkey| {
+            instruction
+                .accounts
+                .iter()
+                .any(|index| keys.get(usize::from(*index)) == Some(account))
+        };
+        let signed = i128::from(amount);
+        let (usdc_change, usdv_change) = match action {
+            HistoryAction::Deposit if touches(user_usdc) || touches(user_usdv) => (-signed, signed),
+            HistoryAction::Burn if touches(user_usdc) || touches(user_usdv) => (signed, -signed),
+            HistoryAction::OffchainMint if touches(user_usdv) => (0, signed),
+            HistoryAction::OffchainRedemption if touches(user_usdv) => (0, -signed),
+            _ => continue,
+        };
+
+        records.push(HistoryRecord {
+            signature: signature.to_string(),
+            slot: transaction.slot,
+            block_time: transaction.block_time,
+            action,
+            amount,
+            usdc_change,
+            usdv_change,
+            usdc_balance: balance(user_usdc),
+            usdv_balance: balance(user_usdv),
+            event: take_event(&mut events, action, user, user_usdv),
+        });
+    }
+    records
+}
+
+/// Action and amount of a USDv instruction that can move a user's funds
+fn decode_instruction(data: &[u8]) -> Option<(HistoryAction, u64)> {
+    let (discriminator, body) = data.split_at_checked(8)?;
+
+    if discriminator == program::DepositAndMint::DISCRIMINATOR {
+        let instruction = program::DepositAndMint::try_from_slice(body).ok()?;
+        Some((HistoryAction::Deposit, instruction.amount))
+    } else if discriminator == program::BurnAndWithdraw::DISCRIMINATOR {
+        let instruction = program::BurnAndWithdraw::try_from_slice(body).ok()?;
+        Some((HistoryAction::Burn, instruction.amount))
+    } else if discriminator == program::MintAllowance::DISCRIMINATOR {
+        let instruction = program::MintAllowance::try_from_slice(body).ok()?;
+        Some((HistoryAction::OffchainMint, instruction.amount))
+    } else if discriminator == program::BurnForOffchainRedemption::DISCRIMINATOR {
+        let instruction = program::BurnForOffchainRedemption::try_from_slice(body).ok()?;
+        Some((HistoryAction::OffchainRedemption, instruction.amount))
+    } else {
+        None
+    }
+}
+
+/// Take the first unclaimed event emitted for the user by an instruction of `action`
+///
+/// Events are logged in instruction order, so pairing the n-th instruction
+/// of a kind with the n-th matching event for the same user lines them up,
+/// even when other users' instructions share the transaction.
+fn take_event(
+    events: &mut [Option<USDvEvent>],
+    action: HistoryAction,
+    user: &Pubkey,
+    user_usdv: &Pubkey,
+) -> Option<USDvEvent> {
+    events
+        .iter_mut()
+        .find(|event| match (action, event.as_ref()) {
+            (HistoryAction::Deposit, Some(USDvEvent::Deposited(deposit))) => deposit.user == *user,
+            (HistoryAction::Burn, Some(USDvEvent::Burned(burn))) => burn.user == *user,
+            (HistoryAction::OffchainMint, Some(USDvEvent::OffchainMint(mint))) => {
+                mint.destination == *user_usdv
+            }
+            (HistoryAction::OffchainRedemption, Some(USDvEvent::OffchainRedemption(redemption))) => {
+                redemption.minter == *user
+            }
+            _ => false,
+        })
+        .and_then(Option::take)
+}
+
+/// Fill unknown balances from the previous record's balance and this record's change
+fn carry_balances_forward(records: &mut [HistoryRecord]) {
+    let mut usdc = None;
+    let mut usdv = None;
+    for record in records {
+        record.usdc_balance = record.usdc_balance.or_else(|| apply(usdc, record.usdc_change));
+        record.usdv_balance = record.usdv_balance.or_else(|| apply(usdv, record.usdv_change));
+        usdc = record.usdc_balance;
+        usdv = record.usdv_balance;
+    }
+}
+
+fn apply(balance: Option<u64>, change: i128) -> Option<u64> {
+    balance.and_then(|balance| u64::try_from(i128::from(balance) + change).ok())
+}
@@ -0,0 +1,330 @@
+//! Instruction builders for every USDv program instruction
+//!
+//! Account lists and instruction data come from the Anchor-generated
+//! `usdv_program::accounts` and `usdv_program::instruction` types, so
+//! account order, signer/writable flags, discriminators and Borsh argument
+//! encoding always match the deployed program's `#[derive(Accounts)]`
+//! structs. PDAs and ATAs are derived with [`usdv_utils::pda`].
+
+use crate::types::AllowlistUpdate;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use usdv_utils::constants::{ASSOCIATED_TOKEN_PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID};
+use usdv_utils::pda::{
+    derive_allowlist_entry_pda, derive_associated_token_account, derive_minter_pda,
+    derive_program_state_pda, derive_vault_authority_pda,
+};
+
+/// Builds USDv program instructions for one deployment
+#[derive(Debug, Clone)]
+pub struct InstructionBuilder {
+    program_id: Pubkey,
+    program_state: Pubkey,
+    vault_authority: Pubkey,
+}
+
+impl InstructionBuilder {
+    /// Create a builder for the program deployed at `program_id`
+    pub fn new(program_id: Pubkey) -> Self {
+        Self {
+            program_id,
+            program_state: derive_program_state_pda(&program_id).0,
+            vault_authority: derive_vault_authority_pda(&program_id).0,
+        }
+    }
+
+    /// Program the instructions are built for
+    pub fn program_id(&self) -> &Pubkey {
+        &self.program_id
+    }
+
+    /// Program state PDA of this deployment
+    pub fn program_state(&self) -> &Pubkey {
+        &self.program_state
+    }
+
+    /// Vault authority PDA of this deployment
+    pub fn vault_authority(&self) -> &Pubkey {
+        &self.vault_authority
+    }
+
+    /// Vault USDC account, the vault authority's ATA
+    pub fn vault_usdc_account(&self, usdc_mint: &Pubkey) -> Pubkey {
+        derive_associated_token_account(&self.vault_authority, usdc_mint)
+    }
+
+    /// Allowlist entry PDA of `wallet`
+    pub fn allowlist_entry(&self, wallet: &Pubkey) -> Pubkey {
+        derive_allowlist_entry_pda(&self.program_id, wallet).0
+    }
+
+    /// Minter PDA of `minter_authority`
+    pub fn minter(&self, minter_authority: &Pubkey) -> Pubkey {
+        derive_minter_pda(&self.program_id, minter_authority).0
+    }
+
+    /// `initialize`: `admin` and the fresh `usdv_mint` keypair must sign
+    pub fn initialize(
+        &self,
+        admin: &Pubkey,
+        usdv_mint: &Pubkey,
+        usdc_mint: &Pubkey,
+    ) -> Instruction {
+        self.instruction(
+            usdv_program::accounts::Initialize {
+                program_state: self.program_state,
+                usdv_mint: *usdv_mint,
+                vault_authority: self.vault_authority,
+                admin: *admin,
+                token_program: TOKEN_PROGRAM_ID,
+                system_program: SYSTEM_PROGRAM_ID,
+            },
+            usdv_program::instruction::Initialize {
+                usdc_mint_key: *usdc_mint,
+            },
+        )
+    }
+
+    /// `deposit_and_mint` from and to `user`'s ATAs
+    ///
+    /// Pass `with_allowlist_entry` when the user has an allowlist entry;
+    /// it is required while allowlist mode is on.
+    pub fn deposit_and_mint(
+        &self,
+        user: &Pubkey,
+        usdc_mint: &Pubkey,
+        usdv_mint: &Pubkey,
+        with_allowlist_entry: bool,
+        amount: u64,
+    ) -> Instruction {
+        self.instruction(
+            usdv_program::accounts::DepositAndMint {
+                program_state: self.program_state,
+                usdc_mint: *usdc_mint,
+                usdv_mint: *usdv_mint,
+                user_usdc_account: derive_associated_token_account(user, usdc_mint),
+                user_usdv_account: derive_associated_token_account(user, usdv_mint),
+                vault_usdc_account: self.vault_usdc_account(usdc_mint),
+                vault_authority: self.vault_authority,
+                user: *user,
+                token_program: TOKEN_PROGRAM_ID,
+                associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+                system_program: SYSTEM_PROGRAM_ID,
+                allowlist_entry: with_allowlist_entry.then(|| self.allowlist_entry(user)),
+            },
+            usdv_program::instruction::DepositAndMint { amount },
+        )
+    }
+
+    /// `burn_and_withdraw` from and to `user`'s ATAs
+    ///
+    /// See [`Self::deposit_and_mint`] for `with_allowlist_entry`.
+    pub fn burn_and_withdraw(
+        &self,
+        user: &Pubkey,
+        usdc_mint: &Pubkey,
+        usdv_mint: &Pubkey,
+        with_allowlist_entry: bool,
+        amount: u64,
+    ) -> Instruction {
+        self.instruction(
+            usdv_program::accounts::BurnAndWithdraw {
+                program_state: self.program_state,
+                usdv_mint: *usdv_mint,
+                user_usdv_account: derive_associated_token_account(user, usdv_mint),
+                user_usdc_account: derive_associated_token_account(user, usdc_mint),
+                vault_usdc_account: self.vault_usdc_account(usdc_mint),
+                vault_authority: self.vault_authority,
+                user: *user,
+                token_program: TOKEN_PROGRAM_ID,
+                allowlist_entry: with_allowlist_entry.then(|| self.allowlist_entry(user)),
+            },
+            usdv_program::instruction::BurnAndWithdraw { amount },
+        )
+    }
+
+    /// `update_program_state` (admin only)
+    pub fn update_program_state(&self, admin: &Pubkey, new_admin: Option<Pubkey>) -> Instruction {
+        self.instruction(
+            usdv_program::accounts::UpdateProgramState {
+                program_state: self.program_state,
+                admin: *admin,
+            },
+            usdv_program::instruction::UpdateProgramState { new_admin },
+        )
+    }
+
+    /// `initiate_wind_down` (admin only)
+    pub fn initiate_wind_down(&self, admin: &Pubkey, claim_period_seconds: i64) -> Instruction {
+        self.instruction(
+            usdv_program::accounts::InitiateWindDown {
+                program_state: self.program_state,
+                admin: *admin,
+            },
+            usdv_program::instruction::InitiateWindDown {
+                claim_period_seconds,
+            },
+        )
+    }
+
+    /// `sweep_unclaimed` into `recovery_usdc_account` (admin only)
+    pub fn sweep_unclaimed(
+        &self,
+        admin: &Pubkey,
+        usdc_mint: &Pubkey,
+        recovery_usdc_account: &Pubkey,
+    ) -> Instruction {
+        self.instruction(
+            usdv_program::accounts::SweepUnclaimed {
+                program_state: self.program_state,
+                vault_usdc_account: self.vault_usdc_account(usdc_mint),
+                recovery_usdc_account: *recovery_usdc_account,
+                vault_authority: self.vault_authority,
+                admin: *admin,
+                token_program: TOKEN_PROGRAM_ID,
+            },
+            usdv_program::instruction::SweepUnclaimed {},
+        )
+    }
+
+    /// `recover_tokens` from a vault-authority-owned account (admin only)
+    pub fn recover_tokens(
+        &self,
+        admin: &Pubkey,
+        source_token_account: &Pubkey,
+        destination_token_account: &Pubkey,
+    ) -> Instruction {
+        self.instruction(
+            usdv_program::accounts::RecoverTokens {
+                program_state: self.program_state,
+                source_token_account: *source_token_account,
+                destination_token_account: *destination_token_account,
+                vault_authority: self.vault_authority,
+                admin: *admin,
+                token_program: TOKEN_PROGRAM_ID,
+            },
+            usdv_program::instruction::RecoverTokens {},
+        )
+    }
+
+    /// `configure_minter` (admin only)
+    pub fn configure_minter(
+        &self,
+        admin: &Pubkey,
+        minter_authority: &Pubkey,
+        allowance: u64,
+    ) -> Instruction {
+        self.instruction(
+            usdv_program::accounts::ConfigureMinter {
+                program_state: self.program_state,
+                minter: self.minter(minter_authority),
+                minter_authority: *minter_authority,
+                admin: *admin,
+                system_program: SYSTEM_PROGRAM_ID,
+            },
+            usdv_program::instruction::ConfigureMinter { allowance },
+        )
+    }
+
+    /// `mint_allowance` into `destination_usdv_account` (registered minters only)
+    pub fn mint_allowance(
+        &self,
+        minter_authority: &Pubkey,
+        usdv_mint: &Pubkey,
+        destination_usdv_account: &Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        self.instruction(
+            usdv_program::accounts::MintAllowance {
+                program_state: self.program_state,
+                minter: self.minter(minter_authority),
+                usdv_mint: *usdv_mint,
+                destination_usdv_account: *destination_usdv_account,
+                vault_authority: self.vault_authority,
+                minter_authority: *minter_authority,
+                token_program: TOKEN_PROGRAM_ID,
+            },
+            usdv_program::instruction::MintAllowance { amount },
+        )
+    }
+
+    /// `burn_for_offchain_redemption` from the minter's USDv ATA (registered minters only)
+    pub fn burn_for_offchain_redemption(
+        &self,
+        minter_authority: &Pubkey,
+        usdv_mint: &Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        self.instruction(
+            usdv_program::accounts::BurnForOffchainRedemption {
+                program_state: self.program_state,
+                minter: self.minter(minter_authority),
+                usdv_mint: *usdv_mint,
+                minter_usdv_account: derive_associated_token_account(minter_authority, usdv_mint),
+                minter_authority: *minter_authority,
+                token_program: TOKEN_PROGRAM_ID,
+            },
+            usdv_program::instruction::BurnForOffchainRedemption { amount },
+        )
+    }
+
+    /// `set_allowlist_mode` (admin only)
+    pub fn set_allowlist_mode(&self, admin: &Pubkey, enabled: bool) -> Instruction {
+        self.instruction(
+            usdv_program::accounts::SetAllowlistMode {
+                program_state: self.program_state,
+                admin: *admin,
+            },
+            usdv_program::instruction::SetAllowlistMode { enabled },
+        )
+    }
+
+    /// `update_allowlist` (admin only)
+    ///
+    /// Each update's allowlist entry PDA is appended as a writable remaining
+    /// account, in the same order as `updates`. Keep batches within
+    /// [`crate::allowlist::ALLOWLIST_BATCH_SIZE`].
+    pub fn update_allowlist(&self, admin: &Pubkey, updates: &[AllowlistUpdate]) -> Instruction {
+        let mut instruction = self.instruction(
+            usdv_program::accounts::UpdateAllowlist {
+                program_state: self.program_state,
+                admin: *admin,
+                system_program: SYSTEM_PROGRAM_ID,
+            },
+            usdv_program::instruction::UpdateAllowlist {
+                updates: updates.to_vec(),
+            },
+        );
+        instruction.accounts.extend(
+            updates
+                .iter()
+                .map(|update| AccountMeta::new(self.allowlist_entry(update.wallet()), false)),
+        );
+        instruction
+    }
+
+    fn instruction(
+        &self,
+        accounts: impl ToAccountMetas,
+        data: impl InstructionData,
+    ) -> Instruction {
+        let mut accounts = accounts.to_account_metas(None);
+
+        // Anchor encodes an absent optional account as the program's own ID,
+        // which is `usdv_program::ID` in the generated code
+        if self.program_id != usdv_program::ID {
+            for meta in accounts.iter_mut().filter(|meta| meta.pubkey == usdv_program::ID) {
+                meta.pubkey = self.program_id;
+            }
+        }
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: data.data(),
+        }
+    }
+}
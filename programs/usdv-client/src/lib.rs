@@ -6,14 +6,22 @@
 //!
 //! - **Easy Integration**: Simple API for common operations
 //! - **Type Safety**: Rust type system ensures correctness
-//! - **Async/Await**: Modern async programming support
+//! - **Async/Await**: Built on the nonblocking RPC client; never blocks the executor
+//! - **Blocking facade**: Synchronous `blocking::USDvClient` behind the `blocking` feature
+//! - **Any signer**: Keypairs, presigners, `&dyn Signer` and remote signing services, with an optional separate fee payer
+//! - **Rich results**: Deposits and burns report fees, token balance changes and decoded program events
+//! - **History**: A user's deposits and burns as typed records, exported as CSV or JSON statements
+//! - **Live events**: A reconnecting `Stream` of decoded program events over websockets, backfilling gaps
+//! - **State watcher**: Typed diffs of the program state and vault balance, polling when websockets are unavailable
+//! - **Solana CLI integration**: RPC URL, commitment and default signer from the CLI's `config.yml`; keypair files, `ASK` and `prompt:` seed phrases resolved like the CLI
+//! - **Deployment manifests**: `USDvConfig` loaded from TOML profiles with environment overrides, validated against the cluster
 //! - **Comprehensive Testing**: Full test coverage
 //!
 //! ## Quick Start
 //!
 //! ```rust,no_run
 //! use usdv_client::{USDvClient, USDvConfig};
-//! use solana_client::rpc_client::RpcClient;
+//! use solana_client::nonblocking::rpc_client::RpcClient;
 //! use solana_sdk::signature::Keypair;
 //!
 //! #[tokio::main]
@@ -25,24 +33,47 @@
 //!     let client = USDvClient::new(rpc_client, config);
 //!     
 //!     // Deposit 100 USDC and mint 100 USDv
-//!     let signature = client.deposit_and_mint(&payer, 100_000_000).await?;
-//!     println!("Transaction: {}", signature);
+//!     let result = client.deposit_and_mint(&payer, 100_000_000).await?;
+//!     println!("Transaction: {}", result.transaction.signature);
 //!     
 //!     Ok(())
 //! }
 //! ```
 
+pub mod allowlist;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cli_config;
 pub mod client;
+pub mod compute_budget;
 pub mod config;  
+pub mod events;
+pub mod history;
+pub mod instructions;
+pub mod logs;
+pub mod manifest;
+pub mod offline;
+pub mod receipts;
+pub mod signer;
+pub mod simulation;
+pub mod submission;
+pub mod subscription;
 pub mod types;
+pub mod utils;
+pub mod watcher;
 pub use client::*;
+pub use config::USDvConfig;
+pub use instructions::InstructionBuilder;
+
+/// Result type returned by client operations
+pub type Result<T> = std::result::Result<T, usdv_results::USDvClientError>;
 
 /// Current version of the USDv client library
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// USDv program ID on mainnet
-pub const MAINNET_PROGRAM_ID: &str = "USDVCoinProgram11111111111111111111111111111";
+pub const MAINNET_PROGRAM_ID: &str = "USDvCoinProgram1111111111111111111111111111";
 
 /// USDv program ID on devnet  
-pub const DEVNET_PROGRAM_ID: &str = "USDVCoinProgram11111111111111111111111111111";
+pub const DEVNET_PROGRAM_ID: &str = "USDvCoinProgram1111111111111111111111111111";
 
@@ -0,0 +1,135 @@
+//! Parsing of transaction log messages
+//!
+//! The runtime writes one line per event: program invocations and their
+//! results, `msg!` output, Anchor event data and compute usage. [`parse_logs`]
+//! turns those lines into [`LogEntry`] values and attributes each message to
+//! the program that was executing when it was written.
+
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// One parsed log line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogEntry {
+    /// `Program <id> invoke [<depth>]`
+    Invoke { program_id: Pubkey, depth: usize },
+    /// `Program log: <message>`
+    Log {
+        program_id: Option<Pubkey>,
+        message: String,
+    },
+    /// `Program data: <base64> ...`, as emitted by Anchor events
+    Data {
+        program_id: Option<Pubkey>,
+        data: Vec<String>,
+    },
+    /// `Program <id> consumed <consumed> of <limit> compute units`
+    ComputeUnits {
+        program_id: Pubkey,
+        consumed: u64,
+        limit: u64,
+    },
+    /// `Program <id> success`
+    Success { program_id: Pubkey },
+    /// `Program <id> failed: <error>`
+    Failure { program_id: Pubkey, error: String },
+    /// Any other line
+    Other(String),
+}
+
+impl LogEntry {
+    /// Program the entry belongs to, if known
+    pub fn program_id(&self) -> Option<&Pubkey> {
+        match self {
+            LogEntry::Invoke { program_id, .. }
+            | LogEntry::ComputeUnits { program_id, .. }
+            | LogEntry::Success { program_id }
+            | LogEntry::Failure { program_id, .. } => Some(program_id),
+            LogEntry::Log { program_id, .. } | LogEntry::Data { program_id, .. } => {
+                program_id.as_ref()
+            }
+            LogEntry::Other(_) => None,
+        }
+    }
+}
+
+/// Parse `logs`, tracking the invocation stack
+pub fn parse_logs(logs: &[String]) -> Vec<LogEntry> {
+    let mut stack: Vec<Pubkey> = Vec::new();
+
+    logs.iter()
+        .map(|line| {
+            let entry = parse_line(line, stack.last().copied());
+            match &entry {
+                LogEntry::Invoke { program_id, .. } => stack.push(*program_id),
+                LogEntry::Success { .. } | LogEntry::Failure { .. } => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+            entry
+        })
+        .collect()
+}
+
+/// `msg!` output written by `program_id`
+pub fn program_messages<'a>(
+    entries: &'a [LogEntry],
+    program_id: &'a Pubkey,
+) -> impl Iterator<Item = &'a str> + 'a {
+    entries.iter().filter_map(move |entry| match entry {
+        LogEntry::Log {
+            program_id: Some(id),
+            message,
+        } if id == program_id => Some(message.as_str()),
+        _ => None,
+    })
+}
+
+fn parse_line(line: &str, current: Option<Pubkey>) -> LogEntry {
+    if let Some(message) = line.strip_prefix("Program log: ") {
+        return LogEntry::Log {
+            program_id: current,
+            message: message.to_string(),
+        };
+    }
+    if let Some(data) = line.strip_prefix("Program data: ") {
+        return LogEntry::Data {
+            program_id: current,
+            data: data.split_whitespace().map(str::to_string).collect(),
+        };
+    }
+
+    parse_program_line(line).unwrap_or_else(|| LogEntry::Other(line.to_string()))
+}
+
+/// Lines of the form `Program <id> ...`
+fn parse_program_line(line: &str) -> Option<LogEntry> {
+    let rest = line.strip_prefix("Program ")?;
+    let (id, rest) = rest.split_once(' ')?;
+    let program_id = Pubkey::from_str(id).ok()?;
+
+    if let Some(depth) = rest.strip_prefix("invoke [") {
+        let depth = depth.strip_suffix(']')?.parse().ok()?;
+        return Some(LogEntry::Invoke { program_id, depth });
+    }
+    if rest == "success" {
+        return Some(LogEntry::Success { program_id });
+    }
+    if let Some(error) = rest.strip_prefix("failed: ") {
+        return Some(LogEntry::Failure {
+            program_id,
+            error: error.to_string(),
+        });
+    }
+    if let Some(usage) = rest.strip_prefix("consumed ") {
+        let (consumed, limit) = usage.strip_suffix(" compute units")?.split_once(" of ")?;
+        return Some(LogEntry::ComputeUnits {
+            program_id,
+            consumed: consumed.parse().ok()?,
+            limit: limit.parse().ok()?,
+        });
+    }
+
+    None
+}
@@ -0,0 +1,227 @@
+//! Deployment manifests
+//!
+//! A deployment manifest is a TOML file with one named profile per
+//! deployment, written by `scripts/deploy.sh` once the program is
+//! initialized:
+//!
+//! ```toml
+//! default_profile = "devnet"
+//!
+//! [profiles.devnet]
+//! cluster = "devnet"
+//! program_id = "USDvCoinProgram1111111111111111111111111111"
+//! usdc_mint = "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"
+//! usdv_mint = "..."
+//! ```
+//!
+//! `cluster` is a cluster name or an RPC URL, as in [`USDvConfig::cluster`].
+//! `program_state` may be omitted; it is then derived from `program_id`.
+//!
+//! [`DeploymentManifest::config`] picks a profile and applies environment
+//! overrides: [`PROFILE_ENV`] selects the profile, and [`CLUSTER_ENV`],
+//! [`PROGRAM_ID_ENV`], [`PROGRAM_STATE_ENV`], [`USDC_MINT_ENV`] and
+//! [`USDV_MINT_ENV`] replace single fields.
+
+use crate::config::USDvConfig;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+use usdv_results::USDvClientError;
+use usdv_utils::pda::derive_program_state_pda;
+
+/// Manifest read when [`MANIFEST_ENV`] is unset
+pub const DEFAULT_MANIFEST_PATH: &str = "usdv.toml";
+
+/// Path of the manifest
+pub const MANIFEST_ENV: &str = "USDV_MANIFEST";
+/// Profile to load, instead of the manifest's `default_profile`
+pub const PROFILE_ENV: &str = "USDV_PROFILE";
+/// Overrides `cluster`
+pub const CLUSTER_ENV: &str = "USDV_CLUSTER";
+/// Overrides `program_id`
+pub const PROGRAM_ID_ENV: &str = "USDV_PROGRAM_ID";
+/// Overrides `program_state`
+pub const PROGRAM_STATE_ENV: &str = "USDV_PROGRAM_STATE";
+/// Overrides `usdc_mint`
+pub const USDC_MINT_ENV: &str = "USDV_USDC_MINT";
+/// Overrides `usdv_mint`
+pub const USDV_MINT_ENV: &str = "USDV_USDV_MINT";
+
+/// Named deployment profiles
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeploymentManifest {
+    /// Profile used when none is requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, DeploymentProfile>,
+}
+
+/// Addresses of one deployment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeploymentProfile {
+    /// Cluster name or RPC URL
+    pub cluster: String,
+    #[serde(with = "pubkey_string")]
+    pub program_id: Pubkey,
+    /// Program state account, derived from `program_id` when `None`
+    #[serde(default, with = "option_pubkey_string", skip_serializing_if = "Option::is_none")]
+    pub program_state: Option<Pubkey>,
+    #[serde(with = "pubkey_string")]
+    pub usdc_mint: Pubkey,
+    #[serde(with = "pubkey_string")]
+    pub usdv_mint: Pubkey,
+}
+
+impl DeploymentProfile {
+    /// Profile with a derived program state
+    pub fn new(cluster: impl Into<String>, program_id: Pubkey, usdc_mint: Pubkey, usdv_mint: Pubkey) -> Self {
+        Self {
+            cluster: cluster.into(),
+            program_id,
+            program_state: None,
+            usdc_mint,
+            usdv_mint,
+        }
+    }
+
+    /// Client configuration for this deployment
+    pub fn to_config(&self) -> USDvConfig {
+        USDvConfig::custom(
+            self.program_id,
+            self.program_state
+                .unwrap_or_else(|| derive_program_state_pda(&self.program_id).0),
+            self.usdc_mint,
+            self.usdv_mint,
+            self.cluster.clone(),
+        )
+    }
+
+    /// Replace the fields set in `env`
+    ///
+    /// Overriding `program_id` alone re-derives the program state.
+    pub fn apply_overrides(&mut self, env: impl Fn(&str) -> Option<String>) -> Result<()> {
+        if let Some(cluster) = env(CLUSTER_ENV) {
+            self.cluster = cluster;
+        }
+        if let Some(program_id) = env_pubkey(&env, PROGRAM_ID_ENV)? {
+            self.program_id = program_id;
+            self.program_state = None;
+        }
+        if let Some(program_state) = env_pubkey(&env, PROGRAM_STATE_ENV)? {
+            self.program_state = Some(program_state);
+        }
+        if let Some(usdc_mint) = env_pubkey(&env, USDC_MINT_ENV)? {
+            self.usdc_mint = usdc_mint;
+        }
+        if let Some(usdv_mint) = env_pubkey(&env, USDV_MINT_ENV)? {
+            self.usdv_mint = usdv_mint;
+        }
+        Ok(())
+    }
+}
+
+impl DeploymentManifest {
+    /// Parse a manifest
+    pub fn from_toml_str(manifest: &str) -> Result<Self> {
+        toml::from_str(manifest).map_err(|e| USDvClientError::ConfigurationError(e.to_string()))
+    }
+
+    /// Render the manifest as TOML
+    pub fn to_toml_string(&self) -> Result<String> {
+        toml::to_string_pretty(self).map_err(|e| USDvClientError::SerializationError(e.to_string()))
+    }
+
+    /// Read the manifest at `path`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let manifest = std::fs::read_to_string(path).map_err(|e| {
+            USDvClientError::ConfigurationError(format!("Cannot read {}: {}", path.display(), e))
+        })?;
+        Self::from_toml_str(&manifest)
+    }
+
+    /// Write the manifest to `path`, replacing it
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_toml_string()?).map_err(|e| {
+            USDvClientError::ConfigurationError(format!("Cannot write {}: {}", path.display(), e))
+        })
+    }
+
+    /// Profile `name`
+    pub fn profile(&self, name: &str) -> Result<&DeploymentProfile> {
+        self.profiles.get(name).ok_or_else(|| {
+            USDvClientError::ConfigurationError(format!("Unknown deployment profile {:?}", name))
+        })
+    }
+
+    /// Add or replace profile `name`
+    pub fn set_profile(&mut self, name: impl Into<String>, profile: DeploymentProfile) {
+        self.profiles.insert(name.into(), profile);
+    }
+
+    /// Validated configuration of a profile, with overrides from `env`
+    ///
+    /// The profile is [`PROFILE_ENV`] if set, else `profile`, else the
+    /// manifest's `default_profile`. See [`USDvConfig::validate`].
+    pub fn config(&self, profile: Option<&str>, env: impl Fn(&str) -> Option<String>) -> Result<USDvConfig> {
+        let name = env(PROFILE_ENV)
+            .or_else(|| profile.map(str::to_string))
+            .or_else(|| self.default_profile.clone())
+            .ok_or_else(|| {
+                USDvClientError::ConfigurationError("No deployment profile selected".to_string())
+            })?;
+
+        let mut profile = self.profile(&name)?.clone();
+        profile.apply_overrides(env)?;
+
+        let config = profile.to_config();
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+fn env_pubkey(env: &impl Fn(&str) -> Option<String>, name: &str) -> Result<Option<Pubkey>> {
+    env(name)
+        .map(|value| {
+            Pubkey::from_str(&value)
+                .map_err(|e| USDvClientError::ConfigurationError(format!("{}: {}", name, e)))
+        })
+        .transpose()
+}
+
+/// Base58 strings instead of byte arrays
+mod pubkey_string {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(pubkey)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let pubkey = String::deserialize(deserializer)?;
+        Pubkey::from_str(&pubkey).map_err(D::Error::custom)
+    }
+}
+
+mod option_pubkey_string {
+    use serde::{Deserializer, Serializer};
+    use solana_sdk::pubkey::Pubkey;
+
+    pub fn serialize<S: Serializer>(pubkey: &Option<Pubkey>, serializer: S) -> Result<S::Ok, S::Error> {
+        match pubkey {
+            Some(pubkey) => super::pubkey_string::serialize(pubkey, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Pubkey>, D::Error> {
+        super::pubkey_string::deserialize(deserializer).map(Some)
+    }
+}
@@ -0,0 +1,149 @@
+//! Offline signing with durable nonces
+//!
+//! Keys that never touch an online machine sign transactions built against
+//! a durable nonce instead of a recent blockhash, so the transaction stays
+//! valid for as long as the signatures take to collect. The workflow:
+//!
+//! 1. Online: create a nonce account once with
+//!    [`USDvClient::create_nonce_account`](crate::USDvClient::create_nonce_account).
+//! 2. Online: build the unsigned transaction with
+//!    [`USDvClient::prepare_offline_transaction`](crate::USDvClient::prepare_offline_transaction),
+//!    or with [`nonce_transaction`] from a [`NonceInfo`], and export it with
+//!    [`encode_transaction_base64`] or [`serialize_transaction`].
+//! 3. Offline: import it, check it, and add signatures with
+//!    [`sign_offline`] or [`add_signature`]; [`missing_signers`] lists who
+//!    still has to sign.
+//! 4. Online: submit the finished transaction with
+//!    [`USDvClient::submit_signed`](crate::USDvClient::submit_signed).
+//!
+//! Landing the transaction advances the nonce, so each nonce value signs
+//! exactly one transaction. Advancing it by hand with
+//! [`USDvClient::advance_nonce`](crate::USDvClient::advance_nonce)
+//! invalidates a transaction that should no longer be submitted.
+
+use crate::Result;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_rpc_client_nonce_utils::nonblocking::data_from_account;
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::signers::Signers,
+    transaction::Transaction,
+};
+use solana_system_interface::instruction::advance_nonce_account;
+use usdv_results::USDvClientError;
+
+/// Current value and authority of a durable nonce account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonceInfo {
+    /// Nonce account address
+    pub account: Pubkey,
+    /// Signer allowed to advance the nonce
+    pub authority: Pubkey,
+    /// Stored nonce, used in place of a recent blockhash
+    pub blockhash: Hash,
+}
+
+impl NonceInfo {
+    /// Read the nonce stored in `account`, found at `address`
+    pub fn from_account(address: &Pubkey, account: &Account) -> Result<Self> {
+        let data = data_from_account(account)
+            .map_err(|e| USDvClientError::SerializationError(format!("nonce account {}: {}", address, e)))?;
+
+        Ok(Self {
+            account: *address,
+            authority: data.authority,
+            blockhash: data.blockhash(),
+        })
+    }
+}
+
+/// Unsigned transaction paying from `fee_payer` and using `nonce`
+///
+/// The instruction advancing the nonce is prepended, as the runtime
+/// requires, so the nonce authority must sign as well.
+pub fn nonce_transaction(
+    instructions: &[Instruction],
+    fee_payer: &Pubkey,
+    nonce: &NonceInfo,
+) -> Transaction {
+    let mut nonced = Vec::with_capacity(instructions.len() + 1);
+    nonced.push(advance_nonce_account(&nonce.account, &nonce.authority));
+    nonced.extend_from_slice(instructions);
+
+    let mut transaction = Transaction::new_with_payer(&nonced, Some(fee_payer));
+    transaction.message.recent_blockhash = nonce.blockhash;
+    transaction
+}
+
+/// Add the signatures of `signers`, keeping any already present
+///
+/// Every signer must be a required signer of the transaction.
+pub fn sign_offline<T: Signers + ?Sized>(transaction: &mut Transaction, signers: &T) -> Result<()> {
+    let blockhash = transaction.message.recent_blockhash;
+    transaction
+        .try_partial_sign(signers, blockhash)
+        .map_err(|e| USDvClientError::SigningError(e.to_string()))
+}
+
+/// Add a signature made elsewhere by `pubkey`
+///
+/// The signature is verified against the transaction message first.
+pub fn add_signature(
+    transaction: &mut Transaction,
+    pubkey: &Pubkey,
+    signature: Signature,
+) -> Result<()> {
+    let position = transaction
+        .get_signing_keypair_positions(&[*pubkey])
+        .map_err(|e| USDvClientError::SigningError(e.to_string()))?[0]
+        .ok_or_else(|| USDvClientError::SigningError(format!("{} is not a required signer", pubkey)))?;
+
+    if !signature.verify(pubkey.as_ref(), &transaction.message_data()) {
+        return Err(USDvClientError::SigningError(format!(
+            "signature does not match the message for {}",
+            pubkey
+        )));
+    }
+
+    transaction.signatures[position] = signature;
+    Ok(())
+}
+
+/// Required signers that have not signed yet
+pub fn missing_signers(transaction: &Transaction) -> Vec<Pubkey> {
+    transaction
+        .message
+        .signer_keys()
+        .into_iter()
+        .zip(&transaction.signatures)
+        .filter(|(_, signature)| **signature == Signature::default())
+        .map(|(pubkey, _)| *pubkey)
+        .collect()
+}
+
+/// Bincode wire format of `transaction`, as sent to the cluster
+pub fn serialize_transaction(transaction: &Transaction) -> Result<Vec<u8>> {
+    bincode::serialize(transaction).map_err(|e| USDvClientError::SerializationError(e.to_string()))
+}
+
+/// Transaction from its bincode wire format
+pub fn deserialize_transaction(bytes: &[u8]) -> Result<Transaction> {
+    bincode::deserialize(bytes).map_err(|e| USDvClientError::SerializationError(e.to_string()))
+}
+
+/// Base64 of the bincode wire format of `transaction`
+pub fn encode_transaction_base64(transaction: &Transaction) -> Result<String> {
+    Ok(STANDARD.encode(serialize_transaction(transaction)?))
+}
+
+/// Transaction from the base64 of its bincode wire format
+pub fn decode_transaction_base64(encoded: &str) -> Result<Transaction> {
+    let bytes = STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| USDvClientError::SerializationError(e.to_string()))?;
+    deserialize_transaction(&bytes)
+}
@@ -0,0 +1,173 @@
+//! Details of landed transactions
+//!
+//! [`fetch_transaction_info`] reads a landed transaction back from the
+//! cluster and turns its metadata into a [`TransactionInfo`]: the slot,
+//! block time and fee, the balance of every token account it touched
+//! before and after, and the USDv events it emitted. Callers can reconcile
+//! from the result without further RPC round trips.
+
+use crate::events::{decode_events, USDvEvent};
+use crate::logs::parse_logs;
+use crate::simulation::TokenBalanceChange;
+use crate::types::{TransactionInfo, TransactionStatus};
+use crate::Result;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status_client_types::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiMessage,
+    UiLoadedAddresses, UiTransactionEncoding, UiTransactionStatusMeta, UiTransactionTokenBalance,
+};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use usdv_results::USDvClientError;
+
+/// Fetch the landed transaction `signature` and summarize it
+///
+/// Events are only decoded from logs written by `program_id`.
+pub async fn fetch_transaction_info(
+    rpc_client: &RpcClient,
+    signature: &Signature,
+    program_id: &Pubkey,
+    commitment: CommitmentConfig,
+) -> Result<TransactionInfo> {
+    let commitment = transaction_commitment(commitment);
+    let transaction = fetch_transaction(rpc_client, signature, commitment).await?;
+
+    Ok(transaction_info(signature, &transaction, program_id, commitment))
+}
+
+/// Landed transaction `signature` in base64 encoding, with its metadata
+pub(crate) async fn fetch_transaction(
+    rpc_client: &RpcClient,
+    signature: &Signature,
+    commitment: CommitmentConfig,
+) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(transaction_commitment(commitment)),
+        max_supported_transaction_version: Some(0),
+    };
+    rpc_client
+        .get_transaction_with_config(signature, config)
+        .await
+        .map_err(USDvClientError::from)
+}
+
+/// `commitment`, raised to confirmed: `getTransaction` and
+/// `getSignaturesForAddress` do not accept processed
+pub(crate) fn transaction_commitment(commitment: CommitmentConfig) -> CommitmentConfig {
+    if commitment.is_finalized() {
+        CommitmentConfig::finalized()
+    } else {
+        CommitmentConfig::confirmed()
+    }
+}
+
+/// Summary of a transaction returned by `getTransaction`
+///
+/// Token balances are only reported when the transaction carries its
+/// account keys, i.e. in binary or raw JSON encoding.
+pub fn transaction_info(
+    signature: &Signature,
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    program_id: &Pubkey,
+    commitment: CommitmentConfig,
+) -> TransactionInfo {
+    let meta = transaction.transaction.meta.as_ref();
+
+    let status = match meta.and_then(|meta| meta.err.as_ref()) {
+        Some(error) => TransactionStatus::Failed(error.to_string()),
+        None if commitment.is_finalized() => TransactionStatus::Finalized,
+        None => TransactionStatus::Confirmed,
+    };
+
+    let (token_balances, events) = match meta {
+        Some(meta) => {
+            let token_balances = account_keys(&transaction.transaction.transaction, meta)
+                .map(|keys| token_balance_changes(&keys, meta))
+                .unwrap_or_default();
+            (token_balances, events(meta, program_id))
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+
+    TransactionInfo {
+        signature: signature.to_string(),
+        status,
+        slot: Some(transaction.slot),
+        block_height: None,
+        confirmation_time: transaction.block_time,
+        fee: meta.map(|meta| meta.fee),
+        token_balances,
+        events,
+    }
+}
+
+/// Every account key of the message, followed by those loaded from lookup tables
+pub(crate) fn account_keys(
+    transaction: &EncodedTransaction,
+    meta: &UiTransactionStatusMeta,
+) -> Option<Vec<Pubkey>> {
+    let mut keys = match transaction {
+        EncodedTransaction::Json(transaction) => match &transaction.message {
+            UiMessage::Raw(message) => parse_pubkeys(&message.account_keys)?,
+            UiMessage::Parsed(message) => {
+                // Parsed messages already include loaded addresses
+                return message
+                    .account_keys
+                    .iter()
+                    .map(|account| Pubkey::from_str(&account.pubkey).ok())
+                    .collect();
+            }
+        },
+        EncodedTransaction::Accounts(_) => return None,
+        encoded => encoded.decode()?.message.static_account_keys().to_vec(),
+    };
+
+    let loaded: Option<&UiLoadedAddresses> = meta.loaded_addresses.as_ref().into();
+    if let Some(loaded) = loaded {
+        keys.extend(parse_pubkeys(&loaded.writable)?);
+        keys.extend(parse_pubkeys(&loaded.readonly)?);
+    }
+    Some(keys)
+}
+
+fn parse_pubkeys(keys: &[String]) -> Option<Vec<Pubkey>> {
+    keys.iter().map(|key| Pubkey::from_str(key).ok()).collect()
+}
+
+/// Balance changes of every token account in the metadata, by account index
+fn token_balance_changes(keys: &[Pubkey], meta: &UiTransactionStatusMeta) -> Vec<TokenBalanceChange> {
+    let mut balances: BTreeMap<u8, (Option<u64>, Option<u64>)> = BTreeMap::new();
+
+    let pre: Option<&Vec<UiTransactionTokenBalance>> = meta.pre_token_balances.as_ref().into();
+    for balance in pre.into_iter().flatten() {
+        balances.entry(balance.account_index).or_default().0 = token_amount(balance);
+    }
+    let post: Option<&Vec<UiTransactionTokenBalance>> = meta.post_token_balances.as_ref().into();
+    for balance in post.into_iter().flatten() {
+        balances.entry(balance.account_index).or_default().1 = token_amount(balance);
+    }
+
+    balances
+        .into_iter()
+        .filter_map(|(index, (pre, post))| {
+            keys.get(usize::from(index)).map(|account| TokenBalanceChange {
+                account: *account,
+                pre,
+                post,
+            })
+        })
+        .collect()
+}
+
+fn token_amount(balance: &UiTransactionTokenBalance) -> Option<u64> {
+    balance.ui_token_amount.amount.parse().ok()
+}
+
+fn events(meta: &UiTransactionStatusMeta, program_id: &Pubkey) -> Vec<USDvEvent> {
+    let logs: Option<&Vec<String>> = meta.log_messages.as_ref().into();
+    logs.map(|logs| decode_events(&parse_logs(logs), program_id))
+        .unwrap_or_default()
+}
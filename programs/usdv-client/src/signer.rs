@@ -0,0 +1,144 @@
+//! Signers for client operations
+//!
+//! Every [`crate::USDvClient`] operation accepts any
+//! [`Signer`](solana_sdk::signer::Signer) as the token authority: a
+//! `Keypair`, a `Presigner`, a hardware wallet, a `&dyn Signer`, or a
+//! [`RemoteSignerAdapter`] around a signing service. The transaction fee
+//! can be paid by a different signer; see
+//! [`crate::USDvClient::with_fee_payer`].
+//!
+//! The `Signer` trait is synchronous, so a [`RemoteSigner`] backed by an
+//! HTTP service blocks the calling thread while it signs. Use a blocking
+//! HTTP client, and call the async client from a multi-threaded runtime or
+//! wrap the call in `tokio::task::block_in_place`.
+
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::{signers::Signers, Signer, SignerError},
+};
+
+/// A signing service holding a single key
+///
+/// Implement this for the transport (HTTP, gRPC, a KMS) and wrap it in a
+/// [`RemoteSignerAdapter`] to use it wherever a `Signer` is expected.
+pub trait RemoteSigner: Send + Sync {
+    /// Public key of the key held by the service
+    fn pubkey(&self) -> Result<Pubkey, SignerError>;
+
+    /// Sign a serialized transaction message
+    fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError>;
+}
+
+/// [`Signer`] backed by a [`RemoteSigner`]
+///
+/// The public key is fetched once, on construction. Signatures returned by
+/// the service are verified against it, so a misconfigured service fails
+/// here instead of as a rejected transaction.
+pub struct RemoteSignerAdapter<R> {
+    remote: R,
+    pubkey: Pubkey,
+}
+
+impl<R: RemoteSigner> RemoteSignerAdapter<R> {
+    /// Wrap `remote`, fetching its public key
+    pub fn new(remote: R) -> Result<Self, SignerError> {
+        let pubkey = remote.pubkey()?;
+        Ok(Self { remote, pubkey })
+    }
+
+    /// The wrapped signing service
+    pub fn remote(&self) -> &R {
+        &self.remote
+    }
+}
+
+impl<R: RemoteSigner> Signer for RemoteSignerAdapter<R> {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        Ok(self.pubkey)
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let signature = self.remote.sign_message(message)?;
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err(SignerError::Custom(format!(
+                "remote signer returned an invalid signature for {}",
+                self.pubkey
+            )));
+        }
+        Ok(signature)
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+/// Fee payer, token authority and any extra signers of one transaction
+///
+/// Generic over the authority so that a `Sync` authority keeps the
+/// client's futures `Send`.
+pub(crate) struct TransactionSigners<'a, A: ?Sized> {
+    pub fee_payer: Option<&'a (dyn Signer + Send + Sync)>,
+    pub authority: &'a A,
+    pub additional: &'a [&'a (dyn Signer + Send + Sync)],
+}
+
+impl<A: Signer + ?Sized> TransactionSigners<'_, A> {
+    /// Every distinct signer, so a fee payer that is also the authority
+    /// signs once
+    fn distinct(&self) -> Result<Vec<&dyn Signer>, SignerError> {
+        let mut signers: Vec<&dyn Signer> = Vec::new();
+        let mut pubkeys = Vec::new();
+        let candidates = self
+            .fee_payer
+            .map(|payer| payer as &dyn Signer)
+            .into_iter()
+            // `A` may be unsized, so coerce the reference to it instead
+            .chain(std::iter::once(&self.authority as &dyn Signer))
+            .chain(self.additional.iter().map(|signer| *signer as &dyn Signer));
+
+        for signer in candidates {
+            let pubkey = signer.try_pubkey()?;
+            if !pubkeys.contains(&pubkey) {
+                pubkeys.push(pubkey);
+                signers.push(signer);
+            }
+        }
+        Ok(signers)
+    }
+
+    /// Pubkey paying the transaction fee
+    pub fn fee_payer_pubkey(&self) -> Result<Pubkey, SignerError> {
+        match self.fee_payer {
+            Some(payer) => payer.try_pubkey(),
+            None => self.authority.try_pubkey(),
+        }
+    }
+}
+
+impl<A: Signer + ?Sized> Signers for TransactionSigners<'_, A> {
+    fn pubkeys(&self) -> Vec<Pubkey> {
+        self.distinct().map(|signers| signers.pubkeys()).unwrap_or_default()
+    }
+
+    fn try_pubkeys(&self) -> Result<Vec<Pubkey>, SignerError> {
+        self.distinct()?.try_pubkeys()
+    }
+
+    fn sign_message(&self, message: &[u8]) -> Vec<Signature> {
+        self.distinct()
+            .map(|signers| signers.sign_message(message))
+            .unwrap_or_default()
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Vec<Signature>, SignerError> {
+        self.distinct()?.try_sign_message(message)
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.distinct()
+            .map(|signers| signers.is_interactive())
+            .unwrap_or(false)
+    }
+}
@@ -0,0 +1,183 @@
+//! Transaction simulation and dry runs
+//!
+//! [`simulate`] runs instructions against the cluster without signing or
+//! sending them, and reports what would happen: logs, the decoded
+//! [`USDvError`] on failure, compute units consumed, and the balance of
+//! every token account the transaction writes before and after.
+
+use crate::compute_budget::writable_accounts;
+use crate::logs::{parse_logs, program_messages, LogEntry};
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig};
+use solana_sdk::{
+    account::Account,
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    transaction::{Transaction, TransactionError},
+};
+use usdv_results::{decode_custom_error, USDvClientError, USDvError};
+use usdv_utils::constants::TOKEN_PROGRAM_ID;
+
+/// Size of an SPL token account
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Offset of the `amount` field in an SPL token account
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+/// Balance of one token account before and after a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenBalanceChange {
+    /// Token account
+    pub account: Pubkey,
+    /// Balance before, `None` if the account did not exist
+    pub pre: Option<u64>,
+    /// Balance after, `None` if the account does not exist afterwards
+    pub post: Option<u64>,
+}
+
+impl TokenBalanceChange {
+    /// Signed change in balance, treating a missing account as empty
+    pub fn delta(&self) -> i128 {
+        i128::from(self.post.unwrap_or(0)) - i128::from(self.pre.unwrap_or(0))
+    }
+}
+
+/// What a transaction would do if sent now
+#[derive(Debug, Clone)]
+pub struct SimulationResult {
+    /// Raw log lines
+    pub logs: Vec<String>,
+    /// Parsed log lines
+    pub parsed_logs: Vec<LogEntry>,
+    /// Transaction error, if it would fail
+    pub error: Option<TransactionError>,
+    /// The USDv program error behind `error`, if any
+    pub program_error: Option<USDvError>,
+    /// Compute units consumed
+    pub units_consumed: Option<u64>,
+    /// Token accounts written by the transaction, in instruction order
+    pub token_balances: Vec<TokenBalanceChange>,
+}
+
+impl SimulationResult {
+    /// Whether the transaction would succeed
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Balance change of `account`, if the transaction writes it
+    pub fn balance_change(&self, account: &Pubkey) -> Option<&TokenBalanceChange> {
+        self.token_balances.iter().find(|change| change.account == *account)
+    }
+
+    /// `msg!` output written by `program_id`
+    pub fn program_messages<'a>(
+        &'a self,
+        program_id: &'a Pubkey,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        program_messages(&self.parsed_logs, program_id)
+    }
+}
+
+/// Simulate `instructions` paid by `payer`
+///
+/// Signatures are not verified and the blockhash is replaced, so nothing
+/// needs to be signed. Failures of `program_id` are decoded into
+/// [`USDvError`]. Pre-balances are read just before the simulation, so a
+/// transaction landing in between can skew them.
+pub async fn simulate(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    commitment: CommitmentConfig,
+) -> Result<SimulationResult> {
+    let accounts = writable_accounts(instructions);
+    let pre_accounts = rpc_client
+        .get_multiple_accounts_with_commitment(&accounts, commitment)
+        .await
+        .map_err(USDvClientError::from)?
+        .value;
+
+    let transaction = Transaction::new_with_payer(instructions, Some(payer));
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(commitment),
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: accounts.iter().map(Pubkey::to_string).collect(),
+        }),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = rpc_client
+        .simulate_transaction_with_config(&transaction, config)
+        .await
+        .map_err(USDvClientError::from)?
+        .value;
+
+    let post_accounts: Vec<Option<Account>> = match result.accounts {
+        Some(post) => post
+            .into_iter()
+            .map(|account| account.and_then(|account| account.decode()))
+            .collect(),
+        None => vec![None; accounts.len()],
+    };
+
+    let token_balances = accounts
+        .iter()
+        .zip(pre_accounts.iter().zip(post_accounts.iter()))
+        .filter_map(|(account, (pre, post))| {
+            let pre = pre.as_ref().and_then(token_amount);
+            let post = post.as_ref().and_then(token_amount);
+            (pre.is_some() || post.is_some()).then_some(TokenBalanceChange {
+                account: *account,
+                pre,
+                post,
+            })
+        })
+        .collect();
+
+    let logs = result.logs.unwrap_or_default();
+    let program_error = result
+        .err
+        .as_ref()
+        .and_then(|error| decode_program_error(error, instructions, program_id));
+
+    Ok(SimulationResult {
+        parsed_logs: parse_logs(&logs),
+        logs,
+        error: result.err,
+        program_error,
+        units_consumed: result.units_consumed,
+        token_balances,
+    })
+}
+
+/// The [`USDvError`] behind `error`, if an instruction of `program_id` failed with one
+pub fn decode_program_error(
+    error: &TransactionError,
+    instructions: &[Instruction],
+    program_id: &Pubkey,
+) -> Option<USDvError> {
+    let (index, program_error) = decode_custom_error(error)?;
+    instructions
+        .get(usize::from(index))
+        .filter(|ix| ix.program_id == *program_id)
+        .map(|_| program_error)
+}
+
+/// Amount held by an SPL token account
+pub(crate) fn token_amount(account: &Account) -> Option<u64> {
+    if account.owner != TOKEN_PROGRAM_ID || account.data.len() != TOKEN_ACCOUNT_LEN {
+        return None;
+    }
+    let amount = account
+        .data
+        .get(TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)?;
+    Some(u64::from_le_bytes(amount.try_into().ok()?))
+}
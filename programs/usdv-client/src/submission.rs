@@ -0,0 +1,619 @@
+//! Transaction submission engine
+//!
+//! [`SubmissionEngine::submit`] signs, sends and confirms a transaction
+//! according to the [`ClientConfig`]:
+//!
+//! - Each signing round uses a fresh blockhash. When the blockhash expires
+//!   before the transaction lands, the transaction is re-signed and resent.
+//! - Failed sends are retried with exponential backoff and jitter, up to
+//!   `max_retries` times, when their [`Retryability`] allows it. Preflight
+//!   rejections that cannot be fixed by retrying end the submission at once.
+//! - Signature statuses are polled until the transaction reaches the
+//!   configured commitment or `timeout_seconds` runs out.
+//! - ComputeBudget instructions are prepended once, before the first
+//!   signing round; see [`crate::compute_budget`].
+//! - Every signature sent is tracked and polled, and re-signing with an
+//!   unchanged blockhash reproduces the same signature, so a resent
+//!   transaction is never counted twice.
+//! - [`SubmissionEngine::submit_signed`] sends a transaction that was
+//!   signed elsewhere, such as offline against a durable nonce, and resends
+//!   it unchanged.
+//!
+//! The result is a [`SubmissionOutcome`] that separates a transaction that
+//! landed and failed from one that never landed.
+//!
+//! [`Retryability`]: usdv_results::Retryability
+
+use crate::compute_budget::{self, ComputeUnitLimit, PriorityFeeStrategy};
+use crate::types::ClientConfig;
+use crate::Result;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcSendTransactionConfig, RpcTransactionConfig};
+use solana_client::rpc_request::{RpcError, RpcResponseErrorData};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::signers::Signers,
+    transaction::{Transaction, TransactionError},
+};
+use std::fmt;
+use std::time::Duration;
+use solana_transaction_status_client_types::UiTransactionEncoding;
+use tokio::time::Instant;
+use usdv_results::{decode_custom_error, transaction_error_retryability, USDvClientError};
+
+/// Upper bound on a single backoff delay
+pub const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Default interval between signature status polls
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Submission settings, usually derived from [`ClientConfig`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmissionConfig {
+    /// Re-signs or resends allowed after the first send
+    pub max_retries: u32,
+    /// Base delay for exponential backoff
+    pub retry_delay: Duration,
+    /// Total time allowed for the transaction to land
+    pub timeout: Duration,
+    /// Skip the RPC node's preflight simulation
+    pub skip_preflight: bool,
+    /// Commitment the transaction must reach
+    pub commitment: CommitmentConfig,
+    /// Interval between signature status polls
+    pub poll_interval: Duration,
+    /// Compute unit price added to the transaction
+    pub priority_fee: PriorityFeeStrategy,
+    /// Compute unit limit set on the transaction
+    pub compute_unit_limit: ComputeUnitLimit,
+}
+
+impl From<&ClientConfig> for SubmissionConfig {
+    fn from(config: &ClientConfig) -> Self {
+        Self {
+            max_retries: config.max_retries,
+            retry_delay: Duration::from_millis(config.retry_delay_ms),
+            timeout: Duration::from_secs(config.timeout_seconds),
+            skip_preflight: config.skip_preflight,
+            commitment: if config.use_confirmed_commitment {
+                CommitmentConfig::confirmed()
+            } else {
+                CommitmentConfig::processed()
+            },
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            priority_fee: config.priority_fee.clone(),
+            compute_unit_limit: config.compute_unit_limit.clone(),
+        }
+    }
+}
+
+/// Why a transaction never landed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotLandedReason {
+    /// The RPC node rejected it in preflight simulation
+    Rejected {
+        error: TransactionError,
+        /// Program of the failing instruction, if an instruction failed
+        failed_program: Option<Pubkey>,
+        logs: Vec<String>,
+    },
+    /// `timeout` elapsed without any signature reaching the commitment
+    TimedOut,
+    /// All retries were used without any signature reaching the commitment
+    RetriesExhausted,
+}
+
+impl fmt::Display for NotLandedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotLandedReason::Rejected { error, .. } => {
+                write!(f, "rejected in preflight: {}", error)
+            }
+            NotLandedReason::TimedOut => write!(f, "timed out"),
+            NotLandedReason::RetriesExhausted => write!(f, "retries exhausted"),
+        }
+    }
+}
+
+/// Final result of submitting a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    /// Landed and succeeded
+    Confirmed {
+        signature: Signature,
+        slot: u64,
+    },
+    /// Landed and failed; the fee was charged and the error is final
+    Failed {
+        signature: Signature,
+        slot: u64,
+        error: TransactionError,
+        /// Program of the failing instruction, if an instruction failed
+        failed_program: Option<Pubkey>,
+        /// Logs of the landed transaction, empty if they could not be fetched
+        logs: Vec<String>,
+    },
+    /// Never landed; no fee was charged and no state changed
+    NotLanded {
+        /// Every distinct signature that was sent
+        signatures: Vec<Signature>,
+        reason: NotLandedReason,
+    },
+}
+
+impl SubmissionOutcome {
+    /// Signature of the landed transaction, if it succeeded
+    ///
+    /// Custom errors raised by `program_id` become
+    /// [`USDvClientError::Program`] with the exact [`USDvError`] variant.
+    ///
+    /// [`USDvError`]: usdv_results::USDvError
+    pub fn into_result(self, program_id: &Pubkey) -> Result<Signature> {
+        match self {
+            SubmissionOutcome::Confirmed { signature, .. } => Ok(signature),
+            SubmissionOutcome::Failed {
+                signature,
+                error,
+                failed_program,
+                logs,
+                ..
+            } => Err(program_error(Some(signature), &error, failed_program, program_id, &logs)
+                .unwrap_or_else(|| USDvClientError::TransactionFailed {
+                    signature,
+                    error,
+                    logs,
+                })),
+            SubmissionOutcome::NotLanded {
+                signatures,
+                reason:
+                    NotLandedReason::Rejected {
+                        error,
+                        failed_program,
+                        logs,
+                    },
+            } => Err(program_error(None, &error, failed_program, program_id, &logs)
+                .unwrap_or_else(|| USDvClientError::TransactionNotLanded {
+                    signatures,
+                    reason: format!("rejected in preflight: {}", error),
+                    error: Some(error),
+                    logs,
+                })),
+            SubmissionOutcome::NotLanded { signatures, reason } => {
+                Err(USDvClientError::TransactionNotLanded {
+                    signatures,
+                    reason: reason.to_string(),
+                    error: None,
+                    logs: Vec::new(),
+                })
+            }
+        }
+    }
+}
+
+/// [`USDvClientError::Program`] if `error` is a custom error raised by `program_id`
+fn program_error(
+    signature: Option<Signature>,
+    error: &TransactionError,
+    failed_program: Option<Pubkey>,
+    program_id: &Pubkey,
+    logs: &[String],
+) -> Option<USDvClientError> {
+    if failed_program != Some(*program_id) {
+        return None;
+    }
+    let (instruction_index, error) = decode_custom_error(error)?;
+    Some(USDvClientError::Program {
+        error,
+        instruction_index,
+        signature,
+        logs: logs.to_vec(),
+    })
+}
+
+/// Backoff before retry number `attempt` (0-based), with "equal jitter"
+///
+/// The exponential delay `retry_delay * 2^attempt`, capped at
+/// [`MAX_BACKOFF`], is split in half: one half is always waited, the other
+/// is scaled by `jitter` in `[0, 1)`.
+pub fn backoff_delay(retry_delay: Duration, attempt: u32, jitter: f64) -> Duration {
+    let exponential = retry_delay
+        .saturating_mul(2_u32.saturating_pow(attempt.min(16)))
+        .min(MAX_BACKOFF);
+    let half = exponential / 2;
+    half + half.mul_f64(jitter.clamp(0.0, 1.0))
+}
+
+/// Result of one signing round
+enum Poll {
+    Landed(SubmissionOutcome),
+    /// The blockhash expired or the send failed; back off and re-sign
+    Resend,
+    TimedOut,
+}
+
+/// Signs, sends and confirms transactions
+pub struct SubmissionEngine<'a> {
+    rpc_client: &'a RpcClient,
+    config: SubmissionConfig,
+}
+
+impl<'a> SubmissionEngine<'a> {
+    /// Create an engine submitting through `rpc_client`
+    pub fn new(rpc_client: &'a RpcClient, config: SubmissionConfig) -> Self {
+        Self { rpc_client, config }
+    }
+
+    /// Submission settings in use
+    pub fn config(&self) -> &SubmissionConfig {
+        &self.config
+    }
+
+    /// Sign `instructions` with `signers`, send and confirm
+    ///
+    /// `payer` pays the fee and must be one of `signers`. Errors are
+    /// returned for failures that happen before anything is sent, such as
+    /// fee estimation or signing, and for RPC failures that retrying
+    /// cannot fix; everything else is a [`SubmissionOutcome`].
+    pub async fn submit<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+    ) -> Result<SubmissionOutcome> {
+        let instructions = &self.with_compute_budget(instructions, payer).await?;
+        let deadline = Instant::now() + self.config.timeout;
+        let mut signatures: Vec<Signature> = Vec::new();
+        let mut attempt = 0;
+
+        loop {
+            let round = self
+                .send_round(instructions, payer, signers, &mut signatures, deadline)
+                .await?;
+
+            if let Some(outcome) = self.after_round(round, &signatures, &mut attempt, deadline).await {
+                return Ok(outcome);
+            }
+        }
+    }
+
+    /// Send an already signed transaction and confirm it
+    ///
+    /// For transactions signed elsewhere, typically offline against a
+    /// durable nonce; see [`crate::offline`]. The transaction is resent
+    /// unchanged, since it cannot be re-signed, and is only abandoned on
+    /// timeout or when retries run out. Errors are returned for unsigned
+    /// transactions and RPC failures that retrying cannot fix.
+    pub async fn submit_signed(&self, transaction: &Transaction) -> Result<SubmissionOutcome> {
+        if !transaction.is_signed() {
+            return Err(USDvClientError::SigningError(
+                "transaction is missing signatures".to_string(),
+            ));
+        }
+
+        let instructions = &decompile(&transaction.message);
+        let deadline = Instant::now() + self.config.timeout;
+        let signatures = vec![transaction.signatures[0]];
+        let mut attempt = 0;
+
+        loop {
+            // A durable nonce does not expire with the block height
+            let round = self
+                .send_and_poll(transaction, instructions, &signatures, None, deadline)
+                .await?;
+
+            if let Some(outcome) = self.after_round(round, &signatures, &mut attempt, deadline).await {
+                return Ok(outcome);
+            }
+        }
+    }
+
+    /// The final outcome of a round, or `None` after backing off for another
+    async fn after_round(
+        &self,
+        round: Poll,
+        signatures: &[Signature],
+        attempt: &mut u32,
+        deadline: Instant,
+    ) -> Option<SubmissionOutcome> {
+        let not_landed = |reason| SubmissionOutcome::NotLanded {
+            signatures: signatures.to_vec(),
+            reason,
+        };
+
+        match round {
+            Poll::Landed(outcome) => return Some(outcome),
+            Poll::TimedOut => return Some(not_landed(NotLandedReason::TimedOut)),
+            Poll::Resend if *attempt >= self.config.max_retries => {
+                return Some(not_landed(NotLandedReason::RetriesExhausted))
+            }
+            Poll::Resend => {}
+        }
+
+        let delay = backoff_delay(self.config.retry_delay, *attempt, rand::random());
+        *attempt += 1;
+        if Instant::now() + delay >= deadline {
+            return Some(not_landed(NotLandedReason::TimedOut));
+        }
+        tokio::time::sleep(delay).await;
+        None
+    }
+
+    /// `instructions` with the configured ComputeBudget instructions prepended
+    pub async fn with_compute_budget(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+    ) -> Result<Vec<Instruction>> {
+        compute_budget::with_compute_budget(
+            self.rpc_client,
+            instructions,
+            payer,
+            &self.config.priority_fee,
+            &self.config.compute_unit_limit,
+            self.config.commitment,
+        )
+        .await
+    }
+
+    /// Sign with a fresh blockhash, send, and poll until something happens
+    async fn send_round<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+        signatures: &mut Vec<Signature>,
+        deadline: Instant,
+    ) -> Result<Poll> {
+        let (blockhash, last_valid_block_height) = match self
+            .rpc_client
+            .get_latest_blockhash_with_commitment(self.config.commitment)
+            .await
+        {
+            Ok(latest) => latest,
+            Err(e) => return self.on_rpc_error(e, instructions, signatures).await,
+        };
+
+        let mut transaction = Transaction::new_with_payer(instructions, Some(payer));
+        transaction
+            .try_sign(signers, blockhash)
+            .map_err(|e| USDvClientError::SigningError(e.to_string()))?;
+
+        // Signing is deterministic: an unchanged blockhash gives the same
+        // signature, which is already being tracked
+        let signature = transaction.signatures[0];
+        if !signatures.contains(&signature) {
+            signatures.push(signature);
+        }
+
+        self.send_and_poll(
+            &transaction,
+            instructions,
+            signatures,
+            Some(last_valid_block_height),
+            deadline,
+        )
+        .await
+    }
+
+    /// Send a signed transaction and poll until something happens
+    ///
+    /// `signatures` holds every signature sent so far, including this one.
+    async fn send_and_poll(
+        &self,
+        transaction: &Transaction,
+        instructions: &[Instruction],
+        signatures: &[Signature],
+        last_valid_block_height: Option<u64>,
+        deadline: Instant,
+    ) -> Result<Poll> {
+        let send_config = RpcSendTransactionConfig {
+            skip_preflight: self.config.skip_preflight,
+            preflight_commitment: Some(self.config.commitment.commitment),
+            ..RpcSendTransactionConfig::default()
+        };
+        if let Err(e) = self
+            .rpc_client
+            .send_transaction_with_config(transaction, send_config)
+            .await
+        {
+            match preflight_error(&e) {
+                // A copy of this transaction already landed
+                Some((TransactionError::AlreadyProcessed, _)) => {}
+                // Stale blockhash, contention or a full block: re-sign and resend
+                Some((error, _)) if transaction_error_retryability(&error).is_retryable() => {
+                    if let Some(outcome) = self.landed(instructions, signatures).await {
+                        return Ok(Poll::Landed(outcome));
+                    }
+                    return Ok(Poll::Resend);
+                }
+                Some((error, logs)) => {
+                    // An earlier signature may have landed and caused this one to fail
+                    if let Some(outcome) = self.landed(instructions, signatures).await {
+                        return Ok(Poll::Landed(outcome));
+                    }
+                    return Ok(Poll::Landed(SubmissionOutcome::NotLanded {
+                        signatures: signatures.to_vec(),
+                        reason: NotLandedReason::Rejected {
+                            failed_program: failed_program(&error, instructions),
+                            error,
+                            logs,
+                        },
+                    }));
+                }
+                None => return self.on_rpc_error(e, instructions, signatures).await,
+            }
+        }
+
+        Ok(self
+            .poll(instructions, signatures, last_valid_block_height, deadline)
+            .await)
+    }
+
+    /// Resend after a transient RPC failure, give up on any other
+    ///
+    /// An earlier signature may have landed even though this call failed.
+    async fn on_rpc_error(
+        &self,
+        error: ClientError,
+        instructions: &[Instruction],
+        signatures: &[Signature],
+    ) -> Result<Poll> {
+        if let Some(outcome) = self.landed(instructions, signatures).await {
+            return Ok(Poll::Landed(outcome));
+        }
+        let error = USDvClientError::from(error);
+        if error.retryability().is_retryable() {
+            Ok(Poll::Resend)
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Poll `signatures` until one lands, the blockhash expires or the deadline passes
+    ///
+    /// `last_valid_block_height` is `None` for a durable nonce transaction.
+    async fn poll(
+        &self,
+        instructions: &[Instruction],
+        signatures: &[Signature],
+        last_valid_block_height: Option<u64>,
+        deadline: Instant,
+    ) -> Poll {
+        loop {
+            if let Some(outcome) = self.landed(instructions, signatures).await {
+                return Poll::Landed(outcome);
+            }
+
+            if Instant::now() >= deadline {
+                return Poll::TimedOut;
+            }
+
+            if let Some(last_valid_block_height) = last_valid_block_height {
+                let block_height = self
+                    .rpc_client
+                    .get_block_height_with_commitment(self.config.commitment)
+                    .await;
+                if block_height.is_ok_and(|height| height > last_valid_block_height) {
+                    // One last look: it may have landed in the final block
+                    return match self.landed(instructions, signatures).await {
+                        Some(outcome) => Poll::Landed(outcome),
+                        None => Poll::Resend,
+                    };
+                }
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            tokio::time::sleep(self.config.poll_interval.min(remaining)).await;
+        }
+    }
+
+    /// Outcome of the first signature that reached the commitment, if any
+    async fn landed(
+        &self,
+        instructions: &[Instruction],
+        signatures: &[Signature],
+    ) -> Option<SubmissionOutcome> {
+        let statuses = self
+            .rpc_client
+            .get_signature_statuses(signatures)
+            .await
+            .ok()?
+            .value;
+
+        let (signature, status) = signatures
+            .iter()
+            .zip(statuses)
+            .find_map(|(signature, status)| {
+                status
+                    .filter(|status| status.satisfies_commitment(self.config.commitment))
+                    .map(|status| (*signature, status))
+            })?;
+
+        Some(match status.err {
+            None => SubmissionOutcome::Confirmed {
+                signature,
+                slot: status.slot,
+            },
+            Some(error) => SubmissionOutcome::Failed {
+                signature,
+                slot: status.slot,
+                failed_program: failed_program(&error, instructions),
+                logs: self.transaction_logs(&signature).await,
+                error,
+            },
+        })
+    }
+
+    /// Log messages of a landed transaction, empty if unavailable
+    async fn transaction_logs(&self, signature: &Signature) -> Vec<String> {
+        // getTransaction does not accept processed commitment
+        let commitment = if self.config.commitment.is_finalized() {
+            CommitmentConfig::finalized()
+        } else {
+            CommitmentConfig::confirmed()
+        };
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(commitment),
+            max_supported_transaction_version: Some(0),
+        };
+
+        self.rpc_client
+            .get_transaction_with_config(signature, config)
+            .await
+            .ok()
+            .and_then(|transaction| transaction.transaction.meta)
+            .and_then(|meta| Option::from(meta.log_messages))
+            .unwrap_or_default()
+    }
+}
+
+/// Program of the instruction that failed with `error`, if any
+fn failed_program(error: &TransactionError, instructions: &[Instruction]) -> Option<Pubkey> {
+    match error {
+        TransactionError::InstructionError(index, _) => instructions
+            .get(usize::from(*index))
+            .map(|instruction| instruction.program_id),
+        _ => None,
+    }
+}
+
+/// Instructions of a compiled message, for locating the program of a failure
+fn decompile(message: &Message) -> Vec<Instruction> {
+    message
+        .instructions
+        .iter()
+        .map(|instruction| Instruction {
+            program_id: *instruction.program_id(&message.account_keys),
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|index| {
+                    let index = usize::from(*index);
+                    AccountMeta {
+                        pubkey: message.account_keys[index],
+                        is_signer: message.is_signer(index),
+                        is_writable: message.is_maybe_writable(index, None),
+                    }
+                })
+                .collect(),
+            data: instruction.data.clone(),
+        })
+        .collect()
+}
+
+/// Transaction error and logs from a failed preflight simulation
+fn preflight_error(error: &ClientError) -> Option<(TransactionError, Vec<String>)> {
+    match error.kind() {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+            ..
+        }) => Some((result.err.clone()?, result.logs.clone().unwrap_or_default())),
+        _ => None,
+    }
+}
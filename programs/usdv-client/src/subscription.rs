@@ -0,0 +1,365 @@
+//! Live program events over websockets
+//!
+//! [`subscribe_events`] subscribes to the logs of every transaction that
+//! mentions the USDv program, through `logsSubscribe` on the pubsub
+//! endpoint, and yields the decoded [`USDvEvent`]s as an [`EventStream`].
+//!
+//! The subscription survives dropped connections:
+//!
+//! - When the websocket closes, it reconnects with exponential backoff and
+//!   jitter, capped at `max_reconnect_delay`.
+//! - After reconnecting, it pages `getSignaturesForAddress` on the program
+//!   back to the last signature it delivered and replays the transactions
+//!   missed in between, oldest first, before resuming live delivery. A
+//!   transaction seen both in the replay and live is delivered once.
+//! - Until something is delivered, the replay goes back to the program's
+//!   newest signature when [`subscribe_events`] was called, so nothing is
+//!   lost to a disconnect before the first event either.
+//!
+//! Failed transactions are skipped: their events were rolled back. Events
+//! arrive at the configured commitment, confirmed by default; a confirmed
+//! transaction can still be dropped in a fork.
+
+use crate::events::{decode_events, USDvEvent};
+use crate::logs::parse_logs;
+use crate::receipts;
+use crate::submission::backoff_delay;
+use crate::Result;
+use futures::stream::{Stream, StreamExt};
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use usdv_results::USDvClientError;
+
+/// Default base delay before reconnecting
+pub const DEFAULT_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+/// Default number of notifications buffered for a slow consumer
+pub const DEFAULT_EVENT_BUFFER: usize = 1_024;
+
+/// Signatures requested per backfill page, the RPC maximum
+const BACKFILL_PAGE_SIZE: usize = 1_000;
+
+/// Event subscription settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriptionConfig {
+    /// Pubsub endpoint, e.g. `wss://api.devnet.solana.com`
+    pub websocket_url: String,
+    /// Commitment notifications and backfilled transactions must reach
+    pub commitment: CommitmentConfig,
+    /// Base delay for reconnect backoff
+    pub reconnect_delay: Duration,
+    /// Notifications buffered before the subscription waits for the consumer
+    pub buffer: usize,
+}
+
+impl SubscriptionConfig {
+    /// Defaults for `websocket_url`: confirmed commitment
+    pub fn new(websocket_url: impl Into<String>) -> Self {
+        Self {
+            websocket_url: websocket_url.into(),
+            commitment: CommitmentConfig::confirmed(),
+            reconnect_delay: DEFAULT_RECONNECT_DELAY,
+            buffer: DEFAULT_EVENT_BUFFER,
+        }
+    }
+}
+
+/// Pubsub endpoint matching an RPC URL
+///
+/// Follows the Solana CLI: `http` becomes `ws`, `https` becomes `wss`, and
+/// an explicit port is incremented, so a local validator's `8899` maps to
+/// `8900`.
+pub fn websocket_url(rpc_url: &str) -> String {
+    let (scheme, rest) = match rpc_url.split_once("://") {
+        Some(("https", rest)) => ("wss", rest),
+        Some((_, rest)) => ("ws", rest),
+        None => ("ws", rpc_url),
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, ""),
+    };
+    let authority = match authority.rsplit_once(':') {
+        Some((host, port)) => match port.parse::<u16>() {
+            Ok(port) => format!("{}:{}", host, port.saturating_add(1)),
+            Err(_) => authority.to_string(),
+        },
+        None => authority.to_string(),
+    };
+    format!("{}://{}{}", scheme, authority, path)
+}
+
+/// Where a notification came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventSource {
+    /// Pushed by the pubsub endpoint
+    Live,
+    /// Replayed after a reconnect to fill the gap
+    Backfill,
+}
+
+/// Events of one successful transaction that mentioned the program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventNotification {
+    /// Transaction signature
+    pub signature: Signature,
+    /// Slot of the notification or of the replayed transaction
+    pub slot: u64,
+    /// Events emitted by the program, in log order; may be empty
+    pub events: Vec<USDvEvent>,
+    /// Whether the transaction was delivered live or replayed
+    pub source: EventSource,
+}
+
+/// Stream of [`EventNotification`]s
+///
+/// Reconnection runs on a background task, which stops when the stream is
+/// dropped. The stream itself never ends while the task runs.
+pub struct EventStream {
+    receiver: mpsc::Receiver<EventNotification>,
+    task: JoinHandle<()>,
+}
+
+impl Stream for EventStream {
+    type Item = EventNotification;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Subscribe to the events of `program_id`
+///
+/// The first connection is made before returning, so an unreachable or
+/// malformed endpoint is reported here; later disconnects are retried
+/// indefinitely. `rpc_client` records where the stream starts and is used
+/// to backfill after a reconnect.
+pub async fn subscribe_events(
+    rpc_client: Arc<RpcClient>,
+    program_id: Pubkey,
+    config: SubscriptionConfig,
+) -> Result<EventStream> {
+    let pubsub = PubsubClient::new(&config.websocket_url)
+        .await
+        .map_err(USDvClientError::from)?;
+    let last_seen = newest_signature(&rpc_client, &program_id, config.commitment).await?;
+    let (sender, receiver) = mpsc::channel(config.buffer.max(1));
+
+    let subscription = Subscription {
+        rpc_client,
+        program_id,
+        config,
+        sender,
+        last_seen,
+    };
+    let task = tokio::spawn(subscription.run(pubsub));
+
+    Ok(EventStream { receiver, task })
+}
+
+/// The program's most recent transaction, `None` if it has none
+async fn newest_signature(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    commitment: CommitmentConfig,
+) -> Result<Option<Signature>> {
+    let config = GetConfirmedSignaturesForAddress2Config {
+        before: None,
+        until: None,
+        limit: Some(1),
+        commitment: Some(receipts::transaction_commitment(commitment)),
+    };
+    let page = rpc_client
+        .get_signatures_for_address_with_config(program_id, config)
+        .await
+        .map_err(USDvClientError::from)?;
+    match page.first() {
+        Some(status) => Signature::from_str(&status.signature)
+            .map(Some)
+            .map_err(|e| USDvClientError::SerializationError(e.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Why one connection ended
+enum Disconnect {
+    /// The websocket closed or the subscription could not be set up
+    Connection,
+    /// The [`EventStream`] was dropped
+    Consumer,
+}
+
+/// State carried across connections
+struct Subscription {
+    rpc_client: Arc<RpcClient>,
+    program_id: Pubkey,
+    config: SubscriptionConfig,
+    sender: mpsc::Sender<EventNotification>,
+    /// Last signature delivered, or the newest at subscribe time, where the
+    /// next backfill stops; `None` if the program had no transactions then
+    last_seen: Option<Signature>,
+}
+
+impl Subscription {
+    async fn run(mut self, mut pubsub: PubsubClient) {
+        loop {
+            if let Disconnect::Consumer = self.stream_connection(&pubsub).await {
+                return;
+            }
+            drop(pubsub);
+
+            pubsub = match self.reconnect().await {
+                Some(pubsub) => pubsub,
+                None => return,
+            };
+        }
+    }
+
+    /// Connect again with backoff, `None` once the consumer is gone
+    async fn reconnect(&self) -> Option<PubsubClient> {
+        let mut attempt = 0;
+        loop {
+            tokio::time::sleep(backoff_delay(self.config.reconnect_delay, attempt, rand::random())).await;
+            if self.sender.is_closed() {
+                return None;
+            }
+            if let Ok(pubsub) = PubsubClient::new(&self.config.websocket_url).await {
+                return Some(pubsub);
+            }
+            attempt = attempt.saturating_add(1);
+        }
+    }
+
+    /// Subscribe on `pubsub`, fill the gap since the last connection, then deliver until it closes
+    async fn stream_connection(&mut self, pubsub: &PubsubClient) -> Disconnect {
+        let filter = RpcTransactionLogsFilter::Mentions(vec![self.program_id.to_string()]);
+        let config = RpcTransactionLogsConfig {
+            commitment: Some(self.config.commitment),
+        };
+        let Ok((mut live, unsubscribe)) = pubsub.logs_subscribe(filter, config).await else {
+            return Disconnect::Connection;
+        };
+
+        // Live notifications are buffered from here on, so nothing falls
+        // between the backfill and the first live notification
+        let backfilled = match self.backfill().await {
+            Ok(Some(backfilled)) => backfilled,
+            Ok(None) => return Disconnect::Consumer,
+            Err(_) => return Disconnect::Connection,
+        };
+
+        while let Some(response) = live.next().await {
+            let logs = response.value;
+            if logs.err.is_some() {
+                continue;
+            }
+            let Ok(signature) = Signature::from_str(&logs.signature) else {
+                continue;
+            };
+            if backfilled.contains(&signature) {
+                continue;
+            }
+
+            let notification = EventNotification {
+                signature,
+                slot: response.context.slot,
+                events: decode_events(&parse_logs(&logs.logs), &self.program_id),
+                source: EventSource::Live,
+            };
+            if !self.deliver(notification).await {
+                return Disconnect::Consumer;
+            }
+        }
+
+        unsubscribe().await;
+        Disconnect::Connection
+    }
+
+    /// Replay transactions since `last_seen`, oldest first
+    ///
+    /// Returns the signatures replayed, or `None` if the consumer is gone.
+    /// On the first connection this replays whatever landed while
+    /// subscribing.
+    async fn backfill(&mut self) -> Result<Option<HashSet<Signature>>> {
+        let mut replayed = HashSet::new();
+        let commitment = receipts::transaction_commitment(self.config.commitment);
+        let mut missed = Vec::new();
+        let mut before = None;
+        loop {
+            let config = GetConfirmedSignaturesForAddress2Config {
+                before,
+                until: self.last_seen,
+                limit: Some(BACKFILL_PAGE_SIZE),
+                commitment: Some(commitment),
+            };
+            let page = self
+                .rpc_client
+                .get_signatures_for_address_with_config(&self.program_id, config)
+                .await
+                .map_err(USDvClientError::from)?;
+            let full_page = page.len() == BACKFILL_PAGE_SIZE;
+
+            for status in page {
+                let signature = Signature::from_str(&status.signature)
+                    .map_err(|e| USDvClientError::SerializationError(e.to_string()))?;
+                before = Some(signature);
+                if status.err.is_none() {
+                    missed.push(signature);
+                }
+            }
+            if !full_page {
+                break;
+            }
+        }
+
+        // Pages are newest first
+        for signature in missed.into_iter().rev() {
+            let info = receipts::fetch_transaction_info(
+                &self.rpc_client,
+                &signature,
+                &self.program_id,
+                commitment,
+            )
+            .await?;
+
+            let notification = EventNotification {
+                signature,
+                slot: info.slot.unwrap_or_default(),
+                events: info.events,
+                source: EventSource::Backfill,
+            };
+            if !self.deliver(notification).await {
+                return Ok(None);
+            }
+            replayed.insert(signature);
+        }
+        Ok(Some(replayed))
+    }
+
+    /// Hand `notification` to the consumer, `false` once it is gone
+    async fn deliver(&mut self, notification: EventNotification) -> bool {
+        let signature = notification.signature;
+        if self.sender.send(notification).await.is_err() {
+            return false;
+        }
+        self.last_seen = Some(signature);
+        true
+    }
+}
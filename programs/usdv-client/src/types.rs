@@ -1,14 +1,31 @@
 //! Type definitions for USDv client operations
 
+use crate::compute_budget::{ComputeUnitLimit, PriorityFeeStrategy};
+use crate::events::USDvEvent;
+use crate::simulation::TokenBalanceChange;
+use crate::Result;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
+use usdv_results::USDvClientError;
+use usdv_utils::math::CollateralRatio;
+
+/// Institutional minter information returned by the client
+pub use usdv_program::Minter as MinterInfo;
+
+/// Allowlist entry information returned by the client
+pub use usdv_program::AllowlistEntry as AllowlistEntryInfo;
+
+/// A single allowlist change, as sent to `update_allowlist`
+pub use usdv_program::AllowlistUpdate;
 
 /// Program state information returned by the client
+///
+/// Mirrors the program's zero-copy `ProgramState` layout field by field,
+/// which for a `repr(C)` struct without implicit padding is also its
+/// Borsh encoding.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct ProgramStateInfo {
-    /// Whether the program has been initialized
-    pub is_initialized: bool,
     /// Admin public key
     pub admin: Pubkey,
     /// USDC mint address
@@ -17,32 +34,71 @@ pub struct ProgramStateInfo {
     pub usdv_mint: Pubkey,
     /// Vault authority PDA
     pub vault_authority: Pubkey,
-    /// Vault authority bump seed
-    pub vault_bump: u8,
     /// Total USDv tokens in circulation
     pub total_usdv_supply: u64,
     /// Total USDC deposited in vault
     pub total_usdc_deposits: u64,
+    /// Total off-chain collateral attested by institutional minters
+    pub total_offchain_deposits: u64,
+    /// Unix timestamp at which wind-down was initiated (0 if never)
+    pub wind_down_started_at: i64,
+    /// Unix timestamp after which unclaimed USDC can be swept
+    pub wind_down_deadline: i64,
+    /// Whether the program has been initialized
+    pub is_initialized: bool,
+    /// Vault authority bump seed
+    pub vault_bump: u8,
+    /// Whether the program is winding down
+    pub is_wound_down: bool,
+    /// Whether only allowlisted wallets can mint and redeem
+    pub allowlist_enabled: bool,
+    /// Reserved padding
+    pub padding: [u8; 4],
 }
 
 impl ProgramStateInfo {
+    /// Decode the `ProgramState` account, skipping its 8-byte discriminator
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        let Some(state_data) = data.get(8..) else {
+            return Err(USDvClientError::SerializationError("Account data too short".to_string()));
+        };
+        borsh::from_slice(state_data).map_err(|e| USDvClientError::SerializationError(e.to_string()))
+    }
+
     /// Check if the 1:1 peg is maintained
     pub fn is_peg_maintained(&self) -> bool {
-        self.total_usdv_supply == self.total_usdc_deposits
+        self.total_usdc_deposits.checked_add(self.total_offchain_deposits)
+            == Some(self.total_usdv_supply)
     }
 
-    /// Get the collateralization ratio
-    pub fn collateralization_ratio(&self) -> Option<f64> {
-        if self.total_usdv_supply == 0 {
-            return Some(f64::INFINITY);
-        }
-        Some(self.total_usdc_deposits as f64 / self.total_usdv_supply as f64)
+    /// USDv supply backed by USDC held on-chain in the vault
+    pub fn onchain_backed_supply(&self) -> u64 {
+        self.total_usdv_supply.saturating_sub(self.total_offchain_deposits)
+    }
+
+    /// USDv supply backed by attested off-chain collateral
+    pub fn offchain_backed_supply(&self) -> u64 {
+        self.total_offchain_deposits
+    }
+
+    /// Collateralization ratio of total backing to supply, in basis points
+    pub fn collateralization_ratio(&self) -> CollateralRatio {
+        CollateralRatio::from_amounts(
+            self.total_usdc_deposits
+                .saturating_add(self.total_offchain_deposits),
+            self.total_usdv_supply,
+        )
     }
 
     /// Check if the program is healthy
     pub fn is_healthy(&self) -> bool {
         self.is_initialized && self.is_peg_maintained()
     }
+
+    /// Check if new USDv can be minted
+    pub fn is_minting_enabled(&self) -> bool {
+        self.is_initialized && !self.is_wound_down
+    }
 }
 
 /// Token balance information
@@ -127,18 +183,47 @@ impl UserAccountInfo {
 }
 
 /// Transaction status information
+///
+/// Filled from the landed transaction's metadata; see [`crate::receipts`].
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TransactionInfo {
     /// Transaction signature
     pub signature: String,
     /// Transaction status
     pub status: TransactionStatus,
-    /// Block height when confirmed
+    /// Slot the transaction landed in
+    pub slot: Option<u64>,
+    /// Block height when confirmed (not reported by `getTransaction`)
     pub block_height: Option<u64>,
     /// Confirmation time
     pub confirmation_time: Option<i64>,
     /// Transaction fee in lamports
     pub fee: Option<u64>,
+    /// Token accounts touched by the transaction, with their balances before and after
+    pub token_balances: Vec<TokenBalanceChange>,
+    /// Events emitted by the USDv program, in log order
+    pub events: Vec<USDvEvent>,
+}
+
+impl TransactionInfo {
+    /// Info for a transaction whose metadata could not be fetched
+    pub fn landed(signature: String, slot: u64, status: TransactionStatus) -> Self {
+        Self {
+            signature,
+            status,
+            slot: Some(slot),
+            block_height: None,
+            confirmation_time: None,
+            fee: None,
+            token_balances: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Balance change of `account`, if the transaction touched it
+    pub fn balance_change(&self, account: &Pubkey) -> Option<&TokenBalanceChange> {
+        self.token_balances.iter().find(|change| change.account == *account)
+    }
 }
 
 /// Transaction status enumeration
@@ -163,10 +248,10 @@ pub struct DepositResult {
     pub usdc_deposited: u64,
     /// Amount of USDv minted
     pub usdv_minted: u64,
-    /// New user USDC balance
-    pub new_usdc_balance: u64,
-    /// New user USDv balance
-    pub new_usdv_balance: u64,
+    /// New user USDC balance, `None` if the transaction metadata was unavailable
+    pub new_usdc_balance: Option<u64>,
+    /// New user USDv balance, `None` if the transaction metadata was unavailable
+    pub new_usdv_balance: Option<u64>,
 }
 
 /// Burn operation result
@@ -178,10 +263,10 @@ pub struct BurnResult {
     pub usdv_burned: u64,
     /// Amount of USDC withdrawn
     pub usdc_withdrawn: u64,
-    /// New user USDC balance
-    pub new_usdc_balance: u64,
-    /// New user USDv balance
-    pub new_usdv_balance: u64,
+    /// New user USDC balance, `None` if the transaction metadata was unavailable
+    pub new_usdc_balance: Option<u64>,
+    /// New user USDv balance, `None` if the transaction metadata was unavailable
+    pub new_usdv_balance: Option<u64>,
 }
 
 /// System health information
@@ -193,24 +278,35 @@ pub struct SystemHealth {
     pub is_healthy: bool,
     /// Total value locked in USD
     pub total_value_locked: f64,
-    /// Collateralization ratio
-    pub collateralization_ratio: f64,
+    /// USDv supply backed by USDC held on-chain
+    pub onchain_backed_supply: u64,
+    /// USDv supply backed by attested off-chain collateral
+    pub offchain_backed_supply: u64,
+    /// Collateralization ratio in basis points
+    pub collateralization_ratio: CollateralRatio,
     /// Last update timestamp
     pub last_updated: i64,
 }
 
 impl SystemHealth {
+    /// Collateralization below this (95%) is high risk
+    pub const HIGH_RISK_BELOW_BPS: u64 = 9_500;
+
     /// Create new system health info
     pub fn new(program_state: ProgramStateInfo) -> Self {
         let is_healthy = program_state.is_healthy();
         let total_value_locked = program_state.total_usdc_deposits as f64 / 1_000_000.0; // Convert to USDC
-        let collateralization_ratio = program_state.collateralization_ratio().unwrap_or(0.0);
+        let onchain_backed_supply = program_state.onchain_backed_supply();
+        let offchain_backed_supply = program_state.offchain_backed_supply();
+        let collateralization_ratio = program_state.collateralization_ratio();
         let last_updated = chrono::Utc::now().timestamp();
 
         Self {
             program_state,
             is_healthy,
             total_value_locked,
+            onchain_backed_supply,
+            offchain_backed_supply,
             collateralization_ratio,
             last_updated,
         }
@@ -218,16 +314,16 @@ impl SystemHealth {
 
     /// Check if system is at risk
     pub fn is_at_risk(&self) -> bool {
-        !self.is_healthy || self.collateralization_ratio < 1.0
+        !self.is_healthy || !self.collateralization_ratio.is_fully_collateralized()
     }
 
     /// Get risk level description
     pub fn risk_level(&self) -> &'static str {
         if !self.is_healthy {
             "CRITICAL"
-        } else if self.collateralization_ratio < 0.95 {
+        } else if self.collateralization_ratio.is_below(Self::HIGH_RISK_BELOW_BPS) {
             "HIGH"
-        } else if self.collateralization_ratio < 1.0 {
+        } else if !self.collateralization_ratio.is_fully_collateralized() {
             "MEDIUM"
         } else {
             "LOW"
@@ -248,6 +344,10 @@ pub struct ClientConfig {
     pub use_confirmed_commitment: bool,
     /// Whether to skip preflight checks
     pub skip_preflight: bool,
+    /// Compute unit price added to every transaction
+    pub priority_fee: PriorityFeeStrategy,
+    /// Compute unit limit set on every transaction
+    pub compute_unit_limit: ComputeUnitLimit,
 }
 
 impl Default for ClientConfig {
@@ -258,6 +358,8 @@ impl Default for ClientConfig {
             timeout_seconds: 30,
             use_confirmed_commitment: true,
             skip_preflight: false,
+            priority_fee: PriorityFeeStrategy::default(),
+            compute_unit_limit: ComputeUnitLimit::default(),
         }
     }
 }
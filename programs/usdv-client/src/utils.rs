@@ -0,0 +1,57 @@
+//! RPC helpers used by [`crate::USDvClient`]
+
+use crate::Result;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::transaction::Transaction;
+use usdv_results::USDvClientError;
+
+/// Cluster information reported by the RPC node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkInfo {
+    /// Solana core version of the node
+    pub solana_version: String,
+    /// Current slot
+    pub slot: u64,
+    /// Current epoch
+    pub epoch: u64,
+    /// Current block height
+    pub block_height: u64,
+}
+
+/// Fee in lamports the cluster would charge for `transaction`'s message
+pub async fn estimate_transaction_fee(
+    rpc_client: &RpcClient,
+    transaction: &Transaction,
+) -> Result<u64> {
+    rpc_client
+        .get_fee_for_message(&transaction.message)
+        .await
+        .map_err(USDvClientError::from)
+}
+
+/// Check that the RPC node is reachable and reports itself healthy
+pub async fn health_check_rpc(rpc_client: &RpcClient) -> Result<()> {
+    rpc_client
+        .get_health()
+        .await
+        .map_err(USDvClientError::from)
+}
+
+/// Fetch version, slot and epoch information from the RPC node
+pub async fn get_network_info(rpc_client: &RpcClient) -> Result<NetworkInfo> {
+    let version = rpc_client
+        .get_version()
+        .await
+        .map_err(USDvClientError::from)?;
+    let epoch_info = rpc_client
+        .get_epoch_info()
+        .await
+        .map_err(USDvClientError::from)?;
+
+    Ok(NetworkInfo {
+        solana_version: version.solana_core,
+        slot: epoch_info.absolute_slot,
+        epoch: epoch_info.epoch,
+        block_height: epoch_info.block_height,
+    })
+}
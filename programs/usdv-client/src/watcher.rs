@@ -0,0 +1,457 @@
+//! Live changes to the program state
+//!
+//! [`watch_program_state`] follows the `ProgramState` account and the vault's
+//! USDC account and yields one [`StateChange`] per field that changed: the
+//! admin, the mints, the supply and deposit counters, the wind-down and
+//! allowlist flags, and the vault balance. The first snapshot is only a
+//! baseline; it produces no changes.
+//!
+//! Updates arrive through `accountSubscribe` on the pubsub endpoint. When
+//! websockets are unavailable, either because no endpoint is configured or
+//! because it cannot be reached, the watcher polls both accounts with
+//! `getMultipleAccounts` instead, and tries the websocket again with
+//! exponential backoff. Changes made while disconnected are picked up by a
+//! poll right after each subscription, so none are lost, although several
+//! updates to one field in the gap collapse into one change.
+
+use crate::simulation::token_amount;
+use crate::submission::backoff_delay;
+use crate::subscription::{DEFAULT_EVENT_BUFFER, DEFAULT_RECONNECT_DELAY};
+use crate::types::ProgramStateInfo;
+use crate::Result;
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use usdv_results::USDvClientError;
+
+/// Default interval between polls while websockets are unavailable
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Watcher settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatcherConfig {
+    /// Pubsub endpoint, or `None` to only poll
+    pub websocket_url: Option<String>,
+    /// Commitment updates must reach
+    pub commitment: CommitmentConfig,
+    /// Base delay for reconnect backoff
+    pub reconnect_delay: Duration,
+    /// Interval between polls while websockets are unavailable
+    pub poll_interval: Duration,
+    /// Changes buffered before the watcher waits for the consumer
+    pub buffer: usize,
+}
+
+impl WatcherConfig {
+    /// Defaults for `websocket_url`: confirmed commitment
+    pub fn new(websocket_url: impl Into<String>) -> Self {
+        Self {
+            websocket_url: Some(websocket_url.into()),
+            ..Self::polling()
+        }
+    }
+
+    /// Defaults without websockets: poll every [`DEFAULT_POLL_INTERVAL`]
+    pub fn polling() -> Self {
+        Self {
+            websocket_url: None,
+            commitment: CommitmentConfig::confirmed(),
+            reconnect_delay: DEFAULT_RECONNECT_DELAY,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            buffer: DEFAULT_EVENT_BUFFER,
+        }
+    }
+}
+
+/// A watched field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateField {
+    Admin,
+    UsdcMint,
+    UsdvMint,
+    VaultAuthority,
+    TotalUsdvSupply,
+    TotalUsdcDeposits,
+    TotalOffchainDeposits,
+    WindDownStartedAt,
+    WindDownDeadline,
+    IsInitialized,
+    VaultBump,
+    IsWoundDown,
+    AllowlistEnabled,
+    /// USDC held by the vault account, 0 while it does not exist
+    VaultBalance,
+}
+
+/// Value of a [`StateField`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldValue {
+    Pubkey(Pubkey),
+    U64(u64),
+    I64(i64),
+    U8(u8),
+    Bool(bool),
+}
+
+/// How a change was observed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeSource {
+    /// Pushed by `accountSubscribe`
+    Subscription,
+    /// Found by polling
+    Polling,
+}
+
+/// One field that changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateChange {
+    pub field: StateField,
+    pub old: FieldValue,
+    pub new: FieldValue,
+    /// Slot at which the new value was observed
+    pub slot: u64,
+    pub source: ChangeSource,
+}
+
+/// Changes between two program states, in field order
+pub fn diff_program_state(
+    old: &ProgramStateInfo,
+    new: &ProgramStateInfo,
+    slot: u64,
+    source: ChangeSource,
+) -> Vec<StateChange> {
+    let fields = [
+        (StateField::Admin, FieldValue::Pubkey(old.admin), FieldValue::Pubkey(new.admin)),
+        (StateField::UsdcMint, FieldValue::Pubkey(old.usdc_mint), FieldValue::Pubkey(new.usdc_mint)),
+        (StateField::UsdvMint, FieldValue::Pubkey(old.usdv_mint), FieldValue::Pubkey(new.usdv_mint)),
+        (
+            StateField::VaultAuthority,
+            FieldValue::Pubkey(old.vault_authority),
+            FieldValue::Pubkey(new.vault_authority),
+        ),
+        (
+            StateField::TotalUsdvSupply,
+            FieldValue::U64(old.total_usdv_supply),
+            FieldValue::U64(new.total_usdv_supply),
+        ),
+        (
+            StateField::TotalUsdcDeposits,
+            FieldValue::U64(old.total_usdc_deposits),
+            FieldValue::U64(new.total_usdc_deposits),
+        ),
+        (
+            StateField::TotalOffchainDeposits,
+            FieldValue::U64(old.total_offchain_deposits),
+            FieldValue::U64(new.total_offchain_deposits),
+        ),
+        (
+            StateField::WindDownStartedAt,
+            FieldValue::I64(old.wind_down_started_at),
+            FieldValue::I64(new.wind_down_started_at),
+        ),
+        (
+            StateField::WindDownDeadline,
+            FieldValue::I64(old.wind_down_deadline),
+            FieldValue::I64(new.wind_down_deadline),
+        ),
+        (StateField::IsInitialized, FieldValue::Bool(old.is_initialized), FieldValue::Bool(new.is_initialized)),
+        (StateField::VaultBump, FieldValue::U8(old.vault_bump), FieldValue::U8(new.vault_bump)),
+        (StateField::IsWoundDown, FieldValue::Bool(old.is_wound_down), FieldValue::Bool(new.is_wound_down)),
+        (
+            StateField::AllowlistEnabled,
+            FieldValue::Bool(old.allowlist_enabled),
+            FieldValue::Bool(new.allowlist_enabled),
+        ),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| StateChange { field, old, new, slot, source })
+        .collect()
+}
+
+/// Stream of [`StateChange`]s
+///
+/// Watching runs on a background task, which stops when the stream is
+/// dropped. The stream itself never ends while the task runs.
+pub struct StateChangeStream {
+    receiver: mpsc::Receiver<StateChange>,
+    task: JoinHandle<()>,
+}
+
+impl Stream for StateChangeStream {
+    type Item = StateChange;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for StateChangeStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Watch `program_state` and the vault USDC account `vault`
+///
+/// The baseline snapshot is fetched before returning, so a missing or
+/// undecodable program state is reported here. An unreachable websocket
+/// is not an error: the watcher polls until it can connect.
+pub async fn watch_program_state(
+    rpc_client: Arc<RpcClient>,
+    program_state: Pubkey,
+    vault: Pubkey,
+    config: WatcherConfig,
+) -> Result<StateChangeStream> {
+    let (sender, receiver) = mpsc::channel(config.buffer.max(1));
+    let mut watcher = Watcher {
+        rpc_client,
+        program_state,
+        vault,
+        config,
+        sender,
+        state: None,
+        vault_balance: 0,
+        state_slot: 0,
+        vault_slot: 0,
+    };
+    let (state, vault_balance, slot) = watcher.fetch().await?;
+    let state = state.ok_or_else(|| {
+        USDvClientError::SerializationError(format!("Program state account {} not found", program_state))
+    })?;
+    watcher.state = Some(state);
+    watcher.vault_balance = vault_balance;
+    watcher.state_slot = slot;
+    watcher.vault_slot = slot;
+
+    let task = tokio::spawn(watcher.run());
+    Ok(StateChangeStream { receiver, task })
+}
+
+/// Why one connection ended
+enum Disconnect {
+    /// The websocket closed or the subscriptions could not be set up
+    Connection,
+    /// The [`StateChangeStream`] was dropped
+    Consumer,
+}
+
+/// An account update from either subscription
+enum Update {
+    ProgramState(Option<Account>),
+    Vault(Option<Account>),
+}
+
+/// Last observed values, carried across connections
+struct Watcher {
+    rpc_client: Arc<RpcClient>,
+    program_state: Pubkey,
+    vault: Pubkey,
+    config: WatcherConfig,
+    sender: mpsc::Sender<StateChange>,
+    state: Option<ProgramStateInfo>,
+    vault_balance: u64,
+    /// Slots of the last applied updates; older updates are dropped
+    state_slot: u64,
+    vault_slot: u64,
+}
+
+impl Watcher {
+    async fn run(mut self) {
+        let mut attempt = 0;
+        loop {
+            if let Some(url) = self.config.websocket_url.clone() {
+                if let Ok(pubsub) = PubsubClient::new(&url).await {
+                    attempt = 0;
+                    if let Disconnect::Consumer = self.stream_connection(&pubsub).await {
+                        return;
+                    }
+                }
+            }
+
+            // Websockets are unavailable: poll until the next attempt
+            let window = self
+                .config
+                .websocket_url
+                .as_ref()
+                .map(|_| backoff_delay(self.config.reconnect_delay, attempt, rand::random()));
+            if !self.poll_for(window).await {
+                return;
+            }
+            attempt = attempt.saturating_add(1);
+        }
+    }
+
+    /// Subscribe to both accounts on `pubsub`, catch up by polling once, then apply updates until it closes
+    async fn stream_connection(&mut self, pubsub: &PubsubClient) -> Disconnect {
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(self.config.commitment),
+            ..RpcAccountInfoConfig::default()
+        };
+        let Ok((state_updates, unsubscribe_state)) =
+            pubsub.account_subscribe(&self.program_state, Some(config.clone())).await
+        else {
+            return Disconnect::Connection;
+        };
+        let Ok((vault_updates, unsubscribe_vault)) = pubsub.account_subscribe(&self.vault, Some(config)).await else {
+            unsubscribe_state().await;
+            return Disconnect::Connection;
+        };
+
+        // Updates are buffered from here on, so a change made while
+        // disconnected is either in this poll or in the stream
+        match self.poll().await {
+            Ok(true) => {}
+            Ok(false) => return Disconnect::Consumer,
+            Err(_) => return Disconnect::Connection,
+        }
+
+        let mut updates = stream::select(
+            state_updates.map(|response| (response.context.slot, Update::ProgramState(response.value.decode()))),
+            vault_updates.map(|response| (response.context.slot, Update::Vault(response.value.decode()))),
+        );
+        while let Some((slot, update)) = updates.next().await {
+            let delivered = match update {
+                Update::ProgramState(account) => {
+                    let state = account.and_then(|account| ProgramStateInfo::from_account_data(&account.data).ok());
+                    self.apply_state(state, slot, ChangeSource::Subscription).await
+                }
+                Update::Vault(account) => {
+                    let balance = account.as_ref().and_then(token_amount).unwrap_or_default();
+                    self.apply_vault_balance(balance, slot, ChangeSource::Subscription).await
+                }
+            };
+            if !delivered {
+                return Disconnect::Consumer;
+            }
+        }
+
+        drop(updates);
+        unsubscribe_state().await;
+        unsubscribe_vault().await;
+        Disconnect::Connection
+    }
+
+    /// Poll now and every `poll_interval` until `window` elapses, forever if `None`
+    ///
+    /// Returns `false` once the consumer is gone. Failed polls are retried
+    /// at the next interval.
+    async fn poll_for(&mut self, window: Option<Duration>) -> bool {
+        let deadline = window.and_then(|window| Instant::now().checked_add(window));
+        loop {
+            if let Ok(false) = self.poll().await {
+                return false;
+            }
+            if self.sender.is_closed() {
+                return false;
+            }
+
+            let mut wait = self.config.poll_interval;
+            if let Some(deadline) = deadline {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return true;
+                }
+                wait = wait.min(remaining);
+            }
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Fetch both accounts and apply them, `Ok(false)` once the consumer is gone
+    async fn poll(&mut self) -> Result<bool> {
+        let (state, vault_balance, slot) = self.fetch().await?;
+        Ok(self.apply_state(state, slot, ChangeSource::Polling).await
+            && self.apply_vault_balance(vault_balance, slot, ChangeSource::Polling).await)
+    }
+
+    /// Program state, vault balance and the slot they were read at
+    async fn fetch(&self) -> Result<(Option<ProgramStateInfo>, u64, u64)> {
+        let response = self
+            .rpc_client
+            .get_multiple_accounts_with_commitment(&[self.program_state, self.vault], self.config.commitment)
+            .await
+            .map_err(USDvClientError::from)?;
+        let mut accounts = response.value.into_iter();
+
+        let state = match accounts.next().flatten() {
+            Some(account) => Some(ProgramStateInfo::from_account_data(&account.data)?),
+            None => None,
+        };
+        let vault_balance = accounts
+            .next()
+            .flatten()
+            .as_ref()
+            .and_then(token_amount)
+            .unwrap_or_default();
+        Ok((state, vault_balance, response.context.slot))
+    }
+
+    /// Deliver the changes from the last program state to `state`
+    ///
+    /// A closed or undecodable account leaves the last state in place.
+    async fn apply_state(&mut self, state: Option<ProgramStateInfo>, slot: u64, source: ChangeSource) -> bool {
+        let Some(state) = state else {
+            return true;
+        };
+        if slot < self.state_slot {
+            return true;
+        }
+        self.state_slot = slot;
+
+        let changes = match &self.state {
+            Some(old) => diff_program_state(old, &state, slot, source),
+            None => Vec::new(),
+        };
+        self.state = Some(state);
+        self.deliver(changes).await
+    }
+
+    /// Deliver the vault balance change, if any
+    async fn apply_vault_balance(&mut self, balance: u64, slot: u64, source: ChangeSource) -> bool {
+        if slot < self.vault_slot {
+            return true;
+        }
+        self.vault_slot = slot;
+
+        let old = std::mem::replace(&mut self.vault_balance, balance);
+        if old == balance {
+            return true;
+        }
+        self.deliver(vec![StateChange {
+            field: StateField::VaultBalance,
+            old: FieldValue::U64(old),
+            new: FieldValue::U64(balance),
+            slot,
+            source,
+        }])
+        .await
+    }
+
+    /// Hand `changes` to the consumer, `false` once it is gone
+    async fn deliver(&mut self, changes: Vec<StateChange>) -> bool {
+        for change in changes {
+            if self.sender.send(change).await.is_err() {
+                return false;
+            }
+        }
+        true
+    }
+}
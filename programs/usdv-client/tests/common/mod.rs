@@ -0,0 +1,44 @@
+//! Fixtures shared by the usdv_client integration tests
+
+#![allow(dead_code)]
+
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client::mock_sender::MocksMap;
+use solana_sdk::pubkey::Pubkey;
+use usdv_client::compute_budget::{ComputeUnitLimit, PriorityFeeStrategy};
+use usdv_client::types::ClientConfig;
+use usdv_client::{USDvClient, USDvConfig};
+
+/// USDC mint of [`test_config`]
+pub const USDC_MINT: Pubkey = Pubkey::new_from_array([10; 32]);
+
+/// USDv mint of [`test_config`]
+pub const USDV_MINT: Pubkey = Pubkey::new_from_array([11; 32]);
+
+/// A localnet deployment of the program with fixed mints
+pub fn test_config() -> USDvConfig {
+    USDvConfig::custom(
+        usdv_program::ID,
+        usdv_utils::derive_program_state_pda(&usdv_program::ID).0,
+        USDC_MINT,
+        USDV_MINT,
+        "localnet".to_string(),
+    )
+}
+
+/// Client for [`test_config`] over a mock RPC answering from `mocks`
+///
+/// Unmocked requests get the mock sender's defaults. Priority fees and
+/// compute unit simulation are off, so sending a transaction only needs a
+/// blockhash, the send and its confirmation.
+pub fn mock_client(mocks: MocksMap) -> USDvClient {
+    USDvClient::with_config(
+        RpcClient::new_mock_with_mocks_map("succeeds".to_string(), mocks),
+        test_config(),
+        ClientConfig {
+            priority_fee: PriorityFeeStrategy::None,
+            compute_unit_limit: ComputeUnitLimit::Default,
+            ..ClientConfig::default()
+        },
+    )
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use solana_sdk::pubkey::Pubkey;
+    use usdv_client::allowlist::{parse_allowlist_csv, AllowlistCsvError};
+    use usdv_client::types::AllowlistUpdate;
+
+    #[test]
+    fn test_parse_wallet_only() {
+        let wallet = Pubkey::new_unique();
+        let csv = format!("wallet\n{wallet}\n");
+
+        let updates = parse_allowlist_csv(csv.as_bytes()).unwrap();
+        assert_eq!(updates, vec![AllowlistUpdate::Add { wallet, expires_at: 0 }]);
+    }
+
+    #[test]
+    fn test_parse_expiry_and_action() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let c = Pubkey::new_unique();
+        let csv = format!(
+            "wallet,expires_at,action\n\
+             {a},1767225600,add\n\
+             {b},2026-01-01T00:00:00Z,\n\
+             {c},,REMOVE\n"
+        );
+
+        let updates = parse_allowlist_csv(csv.as_bytes()).unwrap();
+        assert_eq!(
+            updates,
+            vec![
+                AllowlistUpdate::Add { wallet: a, expires_at: 1_767_225_600 },
+                AllowlistUpdate::Add { wallet: b, expires_at: 1_767_225_600 },
+                AllowlistUpdate::Remove { wallet: c },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_rows() {
+        let wallet = Pubkey::new_unique();
+
+        let result = parse_allowlist_csv("wallet\nnot-a-key\n".as_bytes());
+        assert!(matches!(result, Err(AllowlistCsvError::InvalidWallet { line: 2, .. })));
+
+        let csv = format!("wallet,expires_at\n{wallet},tomorrow\n");
+        let result = parse_allowlist_csv(csv.as_bytes());
+        assert!(matches!(result, Err(AllowlistCsvError::InvalidExpiry { line: 2, .. })));
+
+        let csv = format!("wallet,action\n{wallet},suspend\n");
+        let result = parse_allowlist_csv(csv.as_bytes());
+        assert!(matches!(result, Err(AllowlistCsvError::InvalidAction { line: 2, .. })));
+    }
+}
@@ -0,0 +1,63 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::test_config;
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use solana_sdk::pubkey::Pubkey;
+    use std::sync::Arc;
+    use usdv_client::USDvClient;
+
+    /// Nothing listens here, so every RPC call fails fast
+    const UNREACHABLE_RPC: &str = "http://127.0.0.1:1";
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    fn create_client() -> USDvClient {
+        USDvClient::new(RpcClient::new(UNREACHABLE_RPC.to_string()), test_config())
+    }
+
+    #[test]
+    fn test_client_is_send_and_sync() {
+        assert_send_sync::<USDvClient>();
+        assert_send_sync::<Arc<USDvClient>>();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_client_shared_across_tasks() {
+        let client = Arc::new(create_client());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let client = Arc::clone(&client);
+                tokio::spawn(async move { client.get_program_state().await })
+            })
+            .collect();
+
+        for handle in handles {
+            assert!(handle.await.unwrap().is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rpc_errors_surface_on_current_thread_runtime() {
+        let client = create_client();
+        assert!(client.health_check().await.is_err());
+        assert!(client.get_usdc_balance(&Pubkey::new_unique()).await.is_err());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_blocking_facade() {
+        let client = usdv_client::blocking::USDvClient::new(
+            RpcClient::new(UNREACHABLE_RPC.to_string()),
+            test_config(),
+        )
+        .unwrap();
+
+        assert_eq!(client.config().cluster, "localnet");
+        assert!(client.get_program_state().is_err());
+        assert!(client.health_check().is_err());
+    }
+}
@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{
+        commitment_config::CommitmentConfig,
+        derivation_path::DerivationPath,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        signer::keypair::{
+            generate_seed_from_seed_phrase_and_passphrase, keypair_from_seed_and_derivation_path,
+            keypair_from_seed_phrase_and_passphrase, write_keypair_file,
+        },
+    };
+    use std::path::PathBuf;
+    use usdv_client::cli_config::{
+        keypair_from_seed_phrase, parse_signer_source, signer_from_path, signer_from_path_with_prompt,
+        CliConfig, SignerSource,
+    };
+    use usdv_client::{USDvClient, USDvConfig};
+    use usdv_results::USDvClientError;
+
+    const SEED_PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    const CONFIG: &str = r#"---
+json_rpc_url: "http://127.0.0.1:8899"
+websocket_url: ""
+keypair_path: /home/dev/.config/solana/id.json
+address_labels:
+  "11111111111111111111111111111111": System Program
+commitment: processed
+"#;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("usdv-{}-{}", Pubkey::new_unique(), name))
+    }
+
+    #[test]
+    fn test_cli_config_file() {
+        let config = CliConfig::from_yaml_str(CONFIG).unwrap();
+        assert_eq!(config.json_rpc_url, "http://127.0.0.1:8899");
+        assert_eq!(config.keypair_path, "/home/dev/.config/solana/id.json");
+        assert_eq!(config.commitment().unwrap(), CommitmentConfig::processed());
+        assert_eq!(config.websocket_url(), "ws://127.0.0.1:8900");
+
+        let explicit = CliConfig {
+            websocket_url: "wss://pubsub.example.com".to_string(),
+            ..config.clone()
+        };
+        assert_eq!(explicit.websocket_url(), "wss://pubsub.example.com");
+
+        // Missing fields take the CLI defaults
+        let partial = CliConfig::from_yaml_str("json_rpc_url: https://api.devnet.solana.com\n").unwrap();
+        assert_eq!(partial.commitment().unwrap(), CommitmentConfig::confirmed());
+        assert!(partial.keypair_path.ends_with(".config/solana/id.json"));
+
+        let invalid = CliConfig {
+            commitment: "max".to_string(),
+            ..config
+        };
+        assert!(matches!(invalid.commitment(), Err(USDvClientError::ConfigurationError(_))));
+    }
+
+    #[test]
+    fn test_client_from_cli_config() {
+        let config = CliConfig::from_yaml_str(CONFIG).unwrap();
+        let client = USDvClient::from_cli_config(&config, USDvConfig::localnet()).unwrap();
+        assert_eq!(client.rpc_client().url(), "http://127.0.0.1:8899");
+        assert_eq!(client.rpc_client().commitment(), CommitmentConfig::processed());
+    }
+
+    #[test]
+    fn test_parse_signer_source() {
+        assert_eq!(
+            parse_signer_source("/keys/admin.json").unwrap(),
+            SignerSource::File(PathBuf::from("/keys/admin.json"))
+        );
+        assert_eq!(
+            parse_signer_source("file:///keys/admin.json").unwrap(),
+            SignerSource::File(PathBuf::from("/keys/admin.json"))
+        );
+        assert_eq!(parse_signer_source("-").unwrap(), SignerSource::Stdin);
+        assert_eq!(parse_signer_source("stdin:").unwrap(), SignerSource::Stdin);
+        assert_eq!(
+            parse_signer_source("ASK").unwrap(),
+            SignerSource::Prompt { derivation_path: None, legacy: true }
+        );
+        assert_eq!(
+            parse_signer_source("prompt://").unwrap(),
+            SignerSource::Prompt { derivation_path: None, legacy: false }
+        );
+        assert_eq!(
+            parse_signer_source("prompt:?key=1/0").unwrap(),
+            SignerSource::Prompt {
+                derivation_path: Some(DerivationPath::new_bip44(Some(1), Some(0))),
+                legacy: false,
+            }
+        );
+        assert_eq!(
+            parse_signer_source("prompt://?full-path=m/44/501/2").unwrap(),
+            SignerSource::Prompt {
+                derivation_path: Some(DerivationPath::new_bip44(Some(2), None)),
+                legacy: false,
+            }
+        );
+
+        for unsupported in [
+            "usb://ledger",
+            "prompt:?account=1",
+            "prompt:?key=x",
+            "11111111111111111111111111111111",
+            "s3://bucket/key.json",
+        ] {
+            assert!(
+                matches!(parse_signer_source(unsupported), Err(USDvClientError::SigningError(_))),
+                "{unsupported}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_seed_phrase_derivation_matches_the_cli() {
+        // `ASK`: the first 32 bytes of the seed
+        let legacy = keypair_from_seed_phrase(SEED_PHRASE, "secret", None, true).unwrap();
+        let expected = keypair_from_seed_phrase_and_passphrase(SEED_PHRASE, "secret").unwrap();
+        assert_eq!(legacy.pubkey(), expected.pubkey());
+
+        // `prompt:`: m/44'/501' unless a path is given
+        let seed = generate_seed_from_seed_phrase_and_passphrase(SEED_PHRASE, "");
+        let derived = keypair_from_seed_phrase(SEED_PHRASE, "", None, false).unwrap();
+        let expected = keypair_from_seed_and_derivation_path(&seed, None).unwrap();
+        assert_eq!(derived.pubkey(), expected.pubkey());
+        assert_ne!(derived.pubkey(), legacy.pubkey());
+
+        let path = DerivationPath::new_bip44(Some(0), Some(0));
+        let account = keypair_from_seed_phrase(SEED_PHRASE, "", Some(path.clone()), false).unwrap();
+        let expected = keypair_from_seed_and_derivation_path(&seed, Some(path)).unwrap();
+        assert_eq!(account.pubkey(), expected.pubkey());
+
+        // Extra whitespace is ignored, a bad checksum is not
+        let spaced = SEED_PHRASE.replace(' ', "  ");
+        let respaced = keypair_from_seed_phrase(&format!(" {spaced}\n"), "", None, false).unwrap();
+        assert_eq!(respaced.pubkey(), derived.pubkey());
+        let typo = SEED_PHRASE.replace("about", "abandon");
+        assert!(matches!(
+            keypair_from_seed_phrase(&typo, "", None, false),
+            Err(USDvClientError::SigningError(_))
+        ));
+    }
+
+    #[test]
+    fn test_signer_from_prompt() {
+        let mut prompts = Vec::new();
+        let mut answers = vec![String::new(), SEED_PHRASE.to_string()];
+        let keypair = signer_from_path_with_prompt("prompt:?key=0/0", "admin", |prompt| {
+            prompts.push(prompt.to_string());
+            Ok(answers.pop().unwrap())
+        })
+        .unwrap();
+
+        let expected =
+            keypair_from_seed_phrase(SEED_PHRASE, "", Some(DerivationPath::new_bip44(Some(0), Some(0))), false)
+                .unwrap();
+        assert_eq!(keypair.pubkey(), expected.pubkey());
+        assert_eq!(prompts.len(), 2);
+        assert_eq!(prompts[0], "[admin] seed phrase: ");
+        assert!(prompts[1].starts_with("[admin] If this seed phrase has an associated passphrase"));
+    }
+
+    #[test]
+    fn test_signer_from_keypair_file() {
+        let keypair = Keypair::new();
+        let path = temp_path("id.json");
+        write_keypair_file(&keypair, &path).unwrap();
+
+        let from_path = signer_from_path(path.to_str().unwrap(), "keypair");
+        let from_uri = signer_from_path(&format!("file://{}", path.display()), "keypair");
+        let from_config = CliConfig {
+            keypair_path: path.display().to_string(),
+            ..CliConfig::default()
+        }
+        .signer();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(from_path.unwrap().pubkey(), keypair.pubkey());
+        assert_eq!(from_uri.unwrap().pubkey(), keypair.pubkey());
+        assert_eq!(from_config.unwrap().pubkey(), keypair.pubkey());
+
+        assert!(matches!(
+            signer_from_path(temp_path("missing.json").to_str().unwrap(), "keypair"),
+            Err(USDvClientError::SigningError(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_cli_config_file() {
+        let path = temp_path("config.yml");
+        std::fs::write(&path, CONFIG).unwrap();
+        let loaded = CliConfig::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), CliConfig::from_yaml_str(CONFIG).unwrap());
+
+        assert!(matches!(
+            CliConfig::load(temp_path("missing.yml")),
+            Err(USDvClientError::ConfigurationError(_))
+        ));
+    }
+}
@@ -1,60 +1,64 @@
-// placeholder
+mod common;
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use crate::USDvConfig;
 
-    fn create_test_client() -> USDvClient {
-        let rpc_client = RpcClient::new("http://localhost:8899".to_string());
-        let config = USDvConfig::localnet();
-        USDvClient::new(rpc_client, config)
+    use super::common::mock_client;
+    use serde_json::{json, Value};
+    use solana_client::rpc_request::RpcRequest;
+    use solana_rpc_client::mock_sender::MocksMap;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    fn token_balance(amount: u64) -> Value {
+        json!({
+            "context": { "slot": 1 },
+            "value": {
+                "amount": amount.to_string(),
+                "decimals": 6,
+                "uiAmount": amount as f64 / 1e6,
+                "uiAmountString": (amount as f64 / 1e6).to_string(),
+            },
+        })
     }
 
     #[test]
     fn test_client_creation() {
-        let client = create_test_client();
+        let client = mock_client(MocksMap::default());
         assert_eq!(client.config().cluster, "localnet");
     }
 
     #[tokio::test]
-    async fn test_balance_queries_dont_panic() {
-        let client = create_test_client();
+    async fn test_balance_queries() {
+        let mut mocks = MocksMap::default();
+        mocks.insert(RpcRequest::GetTokenAccountBalance, token_balance(2_000_000));
+        mocks.insert(RpcRequest::GetTokenAccountBalance, token_balance(5_000_000));
+        let client = mock_client(mocks);
         let user = Keypair::new();
-        
-        // These should return 0 or error, but not panic
-        let _usdv_result = client.get_usdv_balance(&user.pubkey()).await;
-        let _usdc_result = client.get_usdc_balance(&user.pubkey()).await;
-        
-        // Test should complete without panicking
-        assert!(true);
+
+        assert_eq!(client.get_usdv_balance(&user.pubkey()).await.unwrap(), 2_000_000);
+        assert_eq!(client.get_usdc_balance(&user.pubkey()).await.unwrap(), 5_000_000);
     }
 
     #[tokio::test]
     async fn test_can_deposit_validation() {
-        let client = create_test_client();
+        let client = mock_client(MocksMap::default());
         let user = Keypair::new();
-        
-        // Test with invalid amount (too small)
-        let result = client.can_deposit(&user.pubkey(), 1).await;
-        // Should handle gracefully (likely return false due to validation or balance)
-        assert!(result.is_ok());
+
+        // Below the minimum deposit, rejected before any balance lookup
+        assert!(!client.can_deposit(&user.pubkey(), 1).await.unwrap());
     }
 
     #[tokio::test]
     async fn test_user_account_info() {
-        let client = create_test_client();
+        let mut mocks = MocksMap::default();
+        mocks.insert(RpcRequest::GetTokenAccountBalance, token_balance(7_000_000));
+        mocks.insert(RpcRequest::GetTokenAccountBalance, token_balance(3_000_000));
+        let client = mock_client(mocks);
         let user = Keypair::new();
-        
-        // This should work even if accounts don't exist (should return 0 balances)
-        let result = client.get_user_account_info(&user.pubkey()).await;
-        if let Ok(info) = result {
-            assert_eq!(info.user, user.pubkey());
-            // Balances should be 0 for non-existent accounts
-            assert_eq!(info.usdc_balance.amount, 0);
-            assert_eq!(info.usdv_balance.amount, 0);
-        }
+
+        let info = client.get_user_account_info(&user.pubkey()).await.unwrap();
+        assert_eq!(info.user, user.pubkey());
+        assert_eq!(info.usdc_balance.amount, 7_000_000);
+        assert_eq!(info.usdv_balance.amount, 3_000_000);
     }
 }
-
-
@@ -0,0 +1,252 @@
+#[cfg(test)]
+mod tests {
+
+    use solana_client::pubsub_client::PubsubClientError;
+    use solana_sdk::{
+        instruction::InstructionError, pubkey::Pubkey, signature::Signature,
+        transaction::TransactionError,
+    };
+    use usdv_client::submission::{NotLandedReason, SubmissionOutcome};
+    use usdv_results::{
+        decode_custom_error, transaction_error_retryability, Retryability, USDvClientError,
+        USDvError,
+    };
+    use usdv_utils::ValidationError;
+
+    fn custom(index: u8, error: USDvError) -> TransactionError {
+        TransactionError::InstructionError(index, InstructionError::Custom(u32::from(error)))
+    }
+
+    fn logs() -> Vec<String> {
+        vec![
+            "Program log: AnchorError occurred.".to_string(),
+            "Program failed: custom program error".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_every_program_error_decodes_to_its_variant() {
+        for error in USDvError::ALL {
+            let (index, decoded) = decode_custom_error(&custom(2, error)).unwrap();
+            assert_eq!(index, 2);
+            assert_eq!(decoded as u32, error as u32);
+            assert_eq!(decoded.to_string(), error.to_string());
+        }
+    }
+
+    #[test]
+    fn test_non_program_errors_are_not_decoded() {
+        // Token program error codes are below Anchor's offset
+        let token_error = TransactionError::InstructionError(0, InstructionError::Custom(1));
+        assert!(decode_custom_error(&token_error).is_none());
+
+        let past_last = TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(6000 + USDvError::ALL.len() as u32),
+        );
+        assert!(decode_custom_error(&past_last).is_none());
+
+        let other = TransactionError::InstructionError(0, InstructionError::InvalidAccountData);
+        assert!(decode_custom_error(&other).is_none());
+        assert!(decode_custom_error(&TransactionError::BlockhashNotFound).is_none());
+    }
+
+    #[test]
+    fn test_validation_errors_convert() {
+        fn validate() -> Result<(), USDvClientError> {
+            usdv_utils::validate_deposit_amount(0)?;
+            Ok(())
+        }
+
+        let error = validate().unwrap_err();
+        assert!(matches!(
+            error,
+            USDvClientError::Validation(ValidationError::ZeroAmount)
+        ));
+        assert!(error.logs().is_empty());
+        assert!(error.program_error().is_none());
+    }
+
+    #[test]
+    fn test_landed_program_failure_is_decoded() {
+        let program_id = Pubkey::new_unique();
+        let signature = Signature::new_unique();
+        let outcome = SubmissionOutcome::Failed {
+            signature,
+            slot: 7,
+            error: custom(1, USDvError::NotAllowlisted),
+            failed_program: Some(program_id),
+            logs: logs(),
+        };
+
+        let error = outcome.into_result(&program_id).unwrap_err();
+        match &error {
+            USDvClientError::Program {
+                error: program_error,
+                instruction_index,
+                signature: failed_signature,
+                ..
+            } => {
+                assert!(matches!(program_error, USDvError::NotAllowlisted));
+                assert_eq!(*instruction_index, 1);
+                assert_eq!(*failed_signature, Some(signature));
+            }
+            other => panic!("expected Program, got {:?}", other),
+        }
+        assert!(matches!(error.program_error(), Some(USDvError::NotAllowlisted)));
+        assert_eq!(error.logs(), logs().as_slice());
+    }
+
+    #[test]
+    fn test_other_program_failure_is_not_decoded() {
+        // Another Anchor program can raise the same code
+        let signature = Signature::new_unique();
+        let outcome = SubmissionOutcome::Failed {
+            signature,
+            slot: 7,
+            error: custom(0, USDvError::NotAllowlisted),
+            failed_program: Some(Pubkey::new_unique()),
+            logs: logs(),
+        };
+
+        let error = outcome.into_result(&Pubkey::new_unique()).unwrap_err();
+        assert!(matches!(
+            error,
+            USDvClientError::TransactionFailed { signature: s, .. } if s == signature
+        ));
+        assert!(error.program_error().is_none());
+        assert_eq!(error.logs(), logs().as_slice());
+    }
+
+    #[test]
+    fn test_preflight_program_failure_is_decoded() {
+        let program_id = Pubkey::new_unique();
+        let outcome = SubmissionOutcome::NotLanded {
+            signatures: vec![Signature::new_unique()],
+            reason: NotLandedReason::Rejected {
+                error: custom(0, USDvError::SupplyLimitExceeded),
+                failed_program: Some(program_id),
+                logs: logs(),
+            },
+        };
+
+        let error = outcome.into_result(&program_id).unwrap_err();
+        assert!(matches!(
+            error,
+            USDvClientError::Program {
+                error: USDvError::SupplyLimitExceeded,
+                signature: None,
+                ..
+            }
+        ));
+        assert_eq!(error.logs(), logs().as_slice());
+    }
+
+    #[test]
+    fn test_preflight_rejection_keeps_logs() {
+        let signatures = vec![Signature::new_unique()];
+        let outcome = SubmissionOutcome::NotLanded {
+            signatures: signatures.clone(),
+            reason: NotLandedReason::Rejected {
+                error: TransactionError::InsufficientFundsForFee,
+                failed_program: None,
+                logs: logs(),
+            },
+        };
+
+        match outcome.into_result(&Pubkey::new_unique()).unwrap_err() {
+            USDvClientError::TransactionNotLanded {
+                signatures: tried,
+                reason,
+                error,
+                logs: failure_logs,
+            } => {
+                assert_eq!(tried, signatures);
+                assert!(reason.starts_with("rejected in preflight"));
+                assert_eq!(error, Some(TransactionError::InsufficientFundsForFee));
+                assert_eq!(failure_logs, logs());
+            }
+            other => panic!("expected TransactionNotLanded, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_timeout_has_no_logs() {
+        let outcome = SubmissionOutcome::NotLanded {
+            signatures: Vec::new(),
+            reason: NotLandedReason::TimedOut,
+        };
+
+        let error = outcome.into_result(&Pubkey::new_unique()).unwrap_err();
+        assert!(matches!(error, USDvClientError::TransactionNotLanded { .. }));
+        assert!(error.logs().is_empty());
+    }
+
+    #[test]
+    fn test_transaction_error_retryability() {
+        let cases = [
+            (TransactionError::BlockhashNotFound, Retryability::RetryableAfterRefresh),
+            (
+                TransactionError::InstructionError(0, InstructionError::ComputationalBudgetExceeded),
+                Retryability::RetryableAfterRefresh,
+            ),
+            (TransactionError::AccountInUse, Retryability::Retryable),
+            (TransactionError::WouldExceedMaxBlockCostLimit, Retryability::Retryable),
+            (TransactionError::InsufficientFundsForFee, Retryability::UserFixable),
+            (TransactionError::AccountNotFound, Retryability::UserFixable),
+            (TransactionError::AlreadyProcessed, Retryability::Fatal),
+            (TransactionError::SignatureFailure, Retryability::Fatal),
+            (custom(0, USDvError::NotAllowlisted), Retryability::Fatal),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(transaction_error_retryability(&error), expected, "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn test_client_error_retryability() {
+        let validation = USDvClientError::Validation(ValidationError::ZeroAmount);
+        assert_eq!(validation.retryability(), Retryability::UserFixable);
+
+        let signing = USDvClientError::SigningError("missing signer".to_string());
+        assert_eq!(signing.retryability(), Retryability::Fatal);
+
+        let configuration = USDvClientError::ConfigurationError("unknown profile".to_string());
+        assert_eq!(configuration.retryability(), Retryability::Fatal);
+
+        let program = USDvClientError::Program {
+            error: USDvError::InvalidAllowlistEntry,
+            instruction_index: 0,
+            signature: None,
+            logs: Vec::new(),
+        };
+        assert_eq!(program.retryability(), Retryability::RetryableAfterRefresh);
+
+        let failed = USDvClientError::TransactionFailed {
+            signature: Signature::new_unique(),
+            error: TransactionError::InsufficientFundsForFee,
+            logs: Vec::new(),
+        };
+        assert_eq!(failed.retryability(), Retryability::UserFixable);
+
+        let timed_out = SubmissionOutcome::NotLanded {
+            signatures: Vec::new(),
+            reason: NotLandedReason::TimedOut,
+        }
+        .into_result(&Pubkey::new_unique())
+        .unwrap_err();
+        assert_eq!(timed_out.retryability(), Retryability::RetryableAfterRefresh);
+
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+        let rpc = USDvClientError::from(solana_client::client_error::ClientError::from(io));
+        assert_eq!(rpc.retryability(), Retryability::Retryable);
+
+        let closed = USDvClientError::from(PubsubClientError::ConnectionClosed("eof".to_string()));
+        assert_eq!(closed.retryability(), Retryability::Retryable);
+        let refused = USDvClientError::from(PubsubClientError::SubscribeFailed {
+            reason: "Method not found (-32601)".to_string(),
+            message: String::new(),
+        });
+        assert_eq!(refused.retryability(), Retryability::Fatal);
+    }
+}
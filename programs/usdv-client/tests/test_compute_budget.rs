@@ -0,0 +1,247 @@
+#[cfg(test)]
+mod tests {
+
+    use serde_json::json;
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use solana_client::rpc_client::Mocks;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_compute_budget_interface::{self as compute_budget, ComputeBudgetInstruction};
+    use solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    };
+    use usdv_client::compute_budget::{
+        limit_with_headroom, percentile_fee, with_compute_budget, writable_accounts,
+        ComputeUnitLimit, PriorityFeeStrategy, MAX_COMPUTE_UNIT_LIMIT,
+    };
+
+    fn fee_mocks(fees: &[u64]) -> Mocks {
+        let fees: Vec<_> = fees
+            .iter()
+            .enumerate()
+            .map(|(slot, fee)| json!({ "slot": slot, "prioritizationFee": fee }))
+            .collect();
+        let mut mocks = Mocks::default();
+        mocks.insert(RpcRequest::GetRecentPrioritizationFees, json!(fees));
+        mocks
+    }
+
+    fn simulation_mock(mocks: &mut Mocks, units_consumed: u64) {
+        mocks.insert(
+            RpcRequest::SimulateTransaction,
+            json!({
+                "context": { "slot": 1 },
+                "value": { "err": null, "logs": [], "unitsConsumed": units_consumed },
+            }),
+        );
+    }
+
+    fn test_instruction() -> Instruction {
+        Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![AccountMeta::new(Pubkey::new_unique(), false)],
+        )
+    }
+
+    #[test]
+    fn test_percentile_fee() {
+        let fees = [50, 10, 40, 20, 30];
+        assert_eq!(percentile_fee(&fees, 0), 10);
+        assert_eq!(percentile_fee(&fees, 20), 10);
+        assert_eq!(percentile_fee(&fees, 50), 30);
+        assert_eq!(percentile_fee(&fees, 90), 50);
+        assert_eq!(percentile_fee(&fees, 100), 50);
+        assert_eq!(percentile_fee(&fees, 255), 50);
+        assert_eq!(percentile_fee(&[], 50), 0);
+    }
+
+    #[test]
+    fn test_limit_with_headroom() {
+        assert_eq!(limit_with_headroom(100_000, 0), 100_000);
+        assert_eq!(limit_with_headroom(100_000, 10), 110_000);
+        assert_eq!(limit_with_headroom(1_300_000, 50), MAX_COMPUTE_UNIT_LIMIT);
+        assert_eq!(limit_with_headroom(u64::MAX, u32::MAX), MAX_COMPUTE_UNIT_LIMIT);
+    }
+
+    #[test]
+    fn test_writable_accounts_are_deduplicated() {
+        let shared = Pubkey::new_unique();
+        let readonly = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let instructions = [
+            Instruction::new_with_bytes(
+                program,
+                &[],
+                vec![AccountMeta::new(shared, true), AccountMeta::new_readonly(readonly, false)],
+            ),
+            Instruction::new_with_bytes(program, &[], vec![AccountMeta::new(shared, false)]),
+        ];
+
+        assert_eq!(writable_accounts(&instructions), vec![shared]);
+    }
+
+    #[test]
+    fn test_default_strategy_is_capped_median() {
+        assert_eq!(
+            PriorityFeeStrategy::default(),
+            PriorityFeeStrategy::Capped {
+                strategy: Box::new(PriorityFeeStrategy::Percentile { percentile: 50 }),
+                max_micro_lamports: 1_000_000,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_strategies_resolve() {
+        let accounts = [Pubkey::new_unique()];
+
+        let rpc_client = RpcClient::new_mock("fails".to_string());
+        assert_eq!(
+            PriorityFeeStrategy::None.resolve(&rpc_client, &accounts).await.unwrap(),
+            0
+        );
+        assert_eq!(
+            PriorityFeeStrategy::Fixed { micro_lamports: 5_000 }
+                .resolve(&rpc_client, &accounts)
+                .await
+                .unwrap(),
+            5_000
+        );
+
+        let rpc_client =
+            RpcClient::new_mock_with_mocks("succeeds".to_string(), fee_mocks(&[100, 300, 200]));
+        assert_eq!(
+            PriorityFeeStrategy::Percentile { percentile: 50 }
+                .resolve(&rpc_client, &accounts)
+                .await
+                .unwrap(),
+            200
+        );
+    }
+
+    #[tokio::test]
+    async fn test_capped_strategy_uses_tightest_cap() {
+        let rpc_client =
+            RpcClient::new_mock_with_mocks("succeeds".to_string(), fee_mocks(&[9_000]));
+        let strategy = PriorityFeeStrategy::Capped {
+            strategy: Box::new(PriorityFeeStrategy::Capped {
+                strategy: Box::new(PriorityFeeStrategy::Percentile { percentile: 75 }),
+                max_micro_lamports: 5_000,
+            }),
+            max_micro_lamports: 7_000,
+        };
+
+        assert_eq!(strategy.resolve(&rpc_client, &[]).await.unwrap(), 5_000);
+    }
+
+    #[tokio::test]
+    async fn test_compute_budget_from_simulation() {
+        let mut mocks = fee_mocks(&[1_000]);
+        simulation_mock(&mut mocks, 100_000);
+        let rpc_client = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+        let instruction = test_instruction();
+
+        let instructions = with_compute_budget(
+            &rpc_client,
+            std::slice::from_ref(&instruction),
+            &Pubkey::new_unique(),
+            &PriorityFeeStrategy::Percentile { percentile: 50 },
+            &ComputeUnitLimit::Simulated { headroom_percent: 20 },
+            CommitmentConfig::confirmed(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            instructions,
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(120_000),
+                ComputeBudgetInstruction::set_compute_unit_price(1_000),
+                instruction,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compute_budget_without_simulated_usage() {
+        // The default mock simulation reports no units consumed
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+        let instruction = test_instruction();
+
+        let instructions = with_compute_budget(
+            &rpc_client,
+            std::slice::from_ref(&instruction),
+            &Pubkey::new_unique(),
+            &PriorityFeeStrategy::None,
+            &ComputeUnitLimit::Simulated { headroom_percent: 20 },
+            CommitmentConfig::confirmed(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(instructions, vec![instruction]);
+    }
+
+    #[tokio::test]
+    async fn test_fixed_compute_budget() {
+        let rpc_client = RpcClient::new_mock("fails".to_string());
+        let instruction = test_instruction();
+
+        let instructions = with_compute_budget(
+            &rpc_client,
+            std::slice::from_ref(&instruction),
+            &Pubkey::new_unique(),
+            &PriorityFeeStrategy::Fixed { micro_lamports: 42 },
+            &ComputeUnitLimit::Fixed { units: 80_000 },
+            CommitmentConfig::confirmed(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(instructions[0], ComputeBudgetInstruction::set_compute_unit_limit(80_000));
+        assert_eq!(instructions[1], ComputeBudgetInstruction::set_compute_unit_price(42));
+    }
+
+    #[tokio::test]
+    async fn test_existing_compute_budget_is_kept() {
+        let rpc_client = RpcClient::new_mock("fails".to_string());
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_price(7),
+            test_instruction(),
+        ];
+        assert!(compute_budget::check_id(&instructions[0].program_id));
+
+        let result = with_compute_budget(
+            &rpc_client,
+            &instructions,
+            &Pubkey::new_unique(),
+            &PriorityFeeStrategy::Fixed { micro_lamports: 42 },
+            &ComputeUnitLimit::Fixed { units: 80_000 },
+            CommitmentConfig::confirmed(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result, instructions);
+    }
+
+    #[tokio::test]
+    async fn test_fee_lookup_failure_is_an_error() {
+        let rpc_client = RpcClient::new_mock("fails".to_string());
+
+        let result = with_compute_budget(
+            &rpc_client,
+            &[test_instruction()],
+            &Pubkey::new_unique(),
+            &PriorityFeeStrategy::Percentile { percentile: 50 },
+            &ComputeUnitLimit::Default,
+            CommitmentConfig::confirmed(),
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -1,6 +1,44 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use anchor_lang::Discriminator;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde_json::{json, Value};
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_rpc_client::mock_sender::MocksMap;
+    use std::collections::HashMap;
+    use usdv_client::config::{Pubkey, USDvConfig};
+    use usdv_client::manifest::{DeploymentManifest, DeploymentProfile};
+    use usdv_client::types::ProgramStateInfo;
+    use usdv_client::{USDvClient, DEVNET_PROGRAM_ID, MAINNET_PROGRAM_ID};
+    use usdv_results::USDvClientError;
+    use usdv_utils::pda::derive_program_state_pda;
+
+    const MANIFEST: &str = r#"
+default_profile = "devnet"
+
+[profiles.devnet]
+cluster = "devnet"
+program_id = "USDvCoinProgram1111111111111111111111111111"
+usdc_mint = "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"
+usdv_mint = "11111111111111111111111111111112"
+
+[profiles.staging]
+cluster = "https://staging.example.com"
+program_id = "USDvCoinProgram1111111111111111111111111111"
+usdc_mint = "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"
+usdv_mint = "11111111111111111111111111111113"
+"#;
+
+    fn env(vars: &[(&str, String)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> =
+            vars.iter().map(|(name, value)| (name.to_string(), value.clone())).collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn is_configuration_error<T>(result: &Result<T, USDvClientError>) -> bool {
+        matches!(result, Err(USDvClientError::ConfigurationError(_)))
+    }
 
     #[test]
     fn test_config_creation() {
@@ -23,7 +61,7 @@ mod tests {
         let program_state = Pubkey::new_unique();
         let usdc_mint = Pubkey::new_unique();
         let usdv_mint = Pubkey::new_unique();
-        
+
         let config = USDvConfig::custom(
             program_id,
             program_state,
@@ -36,5 +74,178 @@ mod tests {
         assert_eq!(config.cluster, "custom-cluster");
         assert_eq!(config.rpc_url(), "custom-cluster");
     }
-}
 
+    #[test]
+    fn test_presets_match_the_program() {
+        assert_eq!(MAINNET_PROGRAM_ID, usdv_program::ID.to_string());
+        assert_eq!(DEVNET_PROGRAM_ID, usdv_program::ID.to_string());
+
+        for config in [USDvConfig::mainnet(), USDvConfig::devnet(), USDvConfig::localnet()] {
+            assert_eq!(config.program_id, usdv_program::ID);
+            assert_eq!(config.program_state, derive_program_state_pda(&usdv_program::ID).0);
+            // The USDv mint is only known once deployed
+            assert!(is_configuration_error(&config.validate()));
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let program_id = usdv_program::ID;
+        let valid = DeploymentProfile::new("devnet", program_id, Pubkey::new_unique(), Pubkey::new_unique())
+            .to_config();
+        assert!(valid.validate().is_ok());
+
+        let wrong_state = USDvConfig {
+            program_state: Pubkey::new_unique(),
+            ..valid.clone()
+        };
+        assert!(is_configuration_error(&wrong_state.validate()));
+
+        let same_mints = USDvConfig {
+            usdv_mint: valid.usdc_mint,
+            ..valid.clone()
+        };
+        assert!(is_configuration_error(&same_mints.validate()));
+    }
+
+    #[test]
+    fn test_manifest_profiles_and_overrides() {
+        let manifest = DeploymentManifest::from_toml_str(MANIFEST).unwrap();
+
+        // Default profile, with the program state derived
+        let devnet = manifest.config(None, env(&[])).unwrap();
+        assert_eq!(devnet.cluster, "devnet");
+        assert_eq!(devnet.program_id, usdv_program::ID);
+        assert_eq!(devnet.program_state, derive_program_state_pda(&usdv_program::ID).0);
+        assert_eq!(devnet.usdv_mint.to_string(), "11111111111111111111111111111112");
+
+        // The environment picks the profile over the argument
+        let staging = manifest
+            .config(Some("devnet"), env(&[("USDV_PROFILE", "staging".to_string())]))
+            .unwrap();
+        assert_eq!(staging.rpc_url(), "https://staging.example.com");
+
+        // Overriding the program re-derives its state
+        let program_id = Pubkey::new_unique();
+        let usdv_mint = Pubkey::new_unique();
+        let overridden = manifest
+            .config(
+                Some("devnet"),
+                env(&[
+                    ("USDV_CLUSTER", "http://127.0.0.1:8899".to_string()),
+                    ("USDV_PROGRAM_ID", program_id.to_string()),
+                    ("USDV_USDV_MINT", usdv_mint.to_string()),
+                ]),
+            )
+            .unwrap();
+        assert_eq!(overridden.rpc_url(), "http://127.0.0.1:8899");
+        assert_eq!(overridden.program_state, derive_program_state_pda(&program_id).0);
+        assert_eq!(overridden.usdv_mint, usdv_mint);
+
+        assert!(is_configuration_error(&manifest.config(Some("mainnet"), env(&[]))));
+        assert!(is_configuration_error(
+            &manifest.config(None, env(&[("USDV_USDC_MINT", "not-a-key".to_string())]))
+        ));
+        // An explicit program state must still be the program's PDA
+        assert!(is_configuration_error(
+            &manifest.config(None, env(&[("USDV_PROGRAM_STATE", Pubkey::new_unique().to_string())]))
+        ));
+    }
+
+    #[test]
+    fn test_manifest_rejects_malformed_keys() {
+        let manifest = MANIFEST.replace("11111111111111111111111111111112", "USDvMint");
+        assert!(is_configuration_error(&DeploymentManifest::from_toml_str(&manifest)));
+    }
+
+    #[test]
+    fn test_manifest_round_trip_through_file() {
+        let mut manifest = DeploymentManifest::default();
+        let profile = DeploymentProfile::new(
+            "localnet",
+            usdv_program::ID,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        manifest.set_profile("localnet", profile.clone());
+        manifest.default_profile = Some("localnet".to_string());
+
+        let toml = manifest.to_toml_string().unwrap();
+        assert!(toml.contains(&format!("usdv_mint = \"{}\"", profile.usdv_mint)));
+        assert!(!toml.contains("program_state"));
+
+        let path = std::env::temp_dir().join(format!("usdv-manifest-{}.toml", Pubkey::new_unique()));
+        manifest.save(&path).unwrap();
+        let loaded = DeploymentManifest::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), manifest);
+    }
+
+    fn ui_account(owner: &Pubkey, executable: bool, data: Vec<u8>) -> Value {
+        json!({
+            "lamports": 1_000_000,
+            "data": [STANDARD.encode(&data), "base64"],
+            "owner": owner.to_string(),
+            "executable": executable,
+            "rentEpoch": 0,
+            "space": data.len(),
+        })
+    }
+
+    fn deployment(program: Value, state: Value) -> Value {
+        json!({ "context": { "slot": 1 }, "value": [program, state] })
+    }
+
+    fn state_account(config: &USDvConfig) -> Value {
+        let state = ProgramStateInfo {
+            admin: Pubkey::new_unique(),
+            usdc_mint: config.usdc_mint,
+            usdv_mint: config.usdv_mint,
+            vault_authority: Pubkey::new_unique(),
+            total_usdv_supply: 0,
+            total_usdc_deposits: 0,
+            total_offchain_deposits: 0,
+            wind_down_started_at: 0,
+            wind_down_deadline: 0,
+            is_initialized: true,
+            vault_bump: 255,
+            is_wound_down: false,
+            allowlist_enabled: false,
+            padding: [0; 4],
+        };
+        let mut data = usdv_program::ProgramState::DISCRIMINATOR.to_vec();
+        data.extend(borsh::to_vec(&state).unwrap());
+        ui_account(&config.program_id, false, data)
+    }
+
+    #[tokio::test]
+    async fn test_verify_deployment() {
+        let config = DeploymentProfile::new("localnet", usdv_program::ID, Pubkey::new_unique(), Pubkey::new_unique())
+            .to_config();
+        let loader = Pubkey::new_unique();
+        let program = ui_account(&loader, true, vec![0; 36]);
+
+        let mut mocks = MocksMap::default();
+        mocks.insert(RpcRequest::GetMultipleAccounts, deployment(program.clone(), state_account(&config)));
+        // Deployed with another USDv mint
+        let other = USDvConfig {
+            usdv_mint: Pubkey::new_unique(),
+            ..config.clone()
+        };
+        mocks.insert(RpcRequest::GetMultipleAccounts, deployment(program.clone(), state_account(&other)));
+        // Not initialized
+        mocks.insert(RpcRequest::GetMultipleAccounts, deployment(program, Value::Null));
+        // Not deployed
+        mocks.insert(RpcRequest::GetMultipleAccounts, deployment(Value::Null, Value::Null));
+
+        let client = USDvClient::new(
+            RpcClient::new_mock_with_mocks_map("succeeds".to_string(), mocks),
+            config.clone(),
+        );
+        let state = client.verify_deployment().await.unwrap();
+        assert_eq!(state.usdv_mint, config.usdv_mint);
+        for _ in 0..3 {
+            assert!(is_configuration_error(&client.verify_deployment().await));
+        }
+    }
+}
@@ -2,7 +2,8 @@
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+    use usdv_client::{DEVNET_PROGRAM_ID, MAINNET_PROGRAM_ID, VERSION};
 
     #[test]
     fn test_version_exists() {
@@ -0,0 +1,458 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::{mock_client, test_config};
+    use anchor_lang::Event;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde_json::Value;
+    use solana_account_decoder_client_types::token::UiTokenAmount;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+    use solana_rpc_client::mock_sender::MocksMap;
+    use solana_sdk::{
+        hash::Hash,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+    use solana_transaction_status_client_types::{
+        option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+        EncodedTransaction, EncodedTransactionWithStatusMeta, TransactionBinaryEncoding,
+        UiTransactionStatusMeta, UiTransactionTokenBalance,
+    };
+    use usdv_client::events::USDvEvent;
+    use usdv_client::history::{HistoryAction, HistoryRange, UserHistory};
+    use usdv_client::{InstructionBuilder, USDvConfig};
+    use usdv_utils::pda::derive_associated_token_account;
+
+    struct Fixture {
+        user: Keypair,
+        config: USDvConfig,
+        builder: InstructionBuilder,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let config = test_config();
+            Self {
+                user: Keypair::new(),
+                builder: InstructionBuilder::new(config.program_id),
+                config,
+            }
+        }
+
+        fn user_usdc(&self) -> Pubkey {
+            derive_associated_token_account(&self.user.pubkey(), &self.config.usdc_mint)
+        }
+
+        fn user_usdv(&self) -> Pubkey {
+            derive_associated_token_account(&self.user.pubkey(), &self.config.usdv_mint)
+        }
+
+        fn deposit(&self, amount: u64) -> Transaction {
+            let user = self.user.pubkey();
+            let instruction = self.builder.deposit_and_mint(
+                &user,
+                &self.config.usdc_mint,
+                &self.config.usdv_mint,
+                false,
+                amount,
+            );
+            self.signed(Transaction::new_with_payer(&[instruction], Some(&user)))
+        }
+
+        fn burn(&self, amount: u64) -> Transaction {
+            let user = self.user.pubkey();
+            let instruction = self.builder.burn_and_withdraw(
+                &user,
+                &self.config.usdc_mint,
+                &self.config.usdv_mint,
+                false,
+                amount,
+            );
+            self.signed(Transaction::new_with_payer(&[instruction], Some(&user)))
+        }
+
+        fn signed(&self, mut transaction: Transaction) -> Transaction {
+            transaction.sign(&[&self.user], Hash::new_unique());
+            transaction
+        }
+
+        /// Post-transaction token balances of the user's USDC and USDv accounts
+        fn post_balances(&self, transaction: &Transaction, usdc: u64, usdv: u64) -> Vec<UiTransactionTokenBalance> {
+            let index = |account: Pubkey| {
+                transaction
+                    .message
+                    .account_keys
+                    .iter()
+                    .position(|key| *key == account)
+                    .unwrap() as u8
+            };
+            vec![
+                token_balance(index(self.user_usdc()), &self.config.usdc_mint, usdc),
+                token_balance(index(self.user_usdv()), &self.config.usdv_mint, usdv),
+            ]
+        }
+    }
+
+    fn token_balance(account_index: u8, mint: &Pubkey, amount: u64) -> UiTransactionTokenBalance {
+        UiTransactionTokenBalance {
+            account_index,
+            mint: mint.to_string(),
+            ui_token_amount: UiTokenAmount {
+                ui_amount: Some(amount as f64 / 1e6),
+                decimals: 6,
+                amount: amount.to_string(),
+                ui_amount_string: (amount as f64 / 1e6).to_string(),
+            },
+            owner: OptionSerializer::None,
+            program_id: OptionSerializer::None,
+        }
+    }
+
+    fn landed(
+        transaction: &Transaction,
+        slot: u64,
+        block_time: i64,
+        post_token_balances: Option<Vec<UiTransactionTokenBalance>>,
+        logs: Vec<String>,
+    ) -> Value {
+        let encoded = STANDARD.encode(bincode::serialize(transaction).unwrap());
+        let accounts = transaction.message.account_keys.len();
+        serde_json::to_value(EncodedConfirmedTransactionWithStatusMeta {
+            slot,
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: EncodedTransaction::Binary(encoded, TransactionBinaryEncoding::Base64),
+                meta: Some(UiTransactionStatusMeta {
+                    err: None,
+                    status: Ok(()),
+                    fee: 5_000,
+                    pre_balances: vec![0; accounts],
+                    post_balances: vec![0; accounts],
+                    inner_instructions: OptionSerializer::None,
+                    log_messages: OptionSerializer::Some(logs),
+                    pre_token_balances: OptionSerializer::Some(Vec::new()),
+                    post_token_balances: post_token_balances.into(),
+                    rewards: OptionSerializer::None,
+                    loaded_addresses: OptionSerializer::Skip,
+                    return_data: OptionSerializer::Skip,
+                    compute_units_consumed: OptionSerializer::Skip,
+                    cost_units: OptionSerializer::Skip,
+                }),
+                version: None,
+            },
+            block_time: Some(block_time),
+        })
+        .unwrap()
+    }
+
+    fn status(transaction: &Transaction, slot: u64, block_time: i64) -> RpcConfirmedTransactionStatusWithSignature {
+        RpcConfirmedTransactionStatusWithSignature {
+            signature: transaction.signatures[0].to_string(),
+            slot,
+            err: None,
+            memo: None,
+            block_time: Some(block_time),
+            confirmation_status: None,
+        }
+    }
+
+    fn deposited_logs(user: &Pubkey, amount: u64, total_usdv_supply: u64) -> Vec<String> {
+        let event = usdv_program::events::Deposited {
+            user: *user,
+            amount,
+            total_usdv_supply,
+        };
+        vec![
+            format!("Program {} invoke [1]", usdv_program::ID),
+            format!("Program data: {}", STANDARD.encode(event.data())),
+            format!("Program {} success", usdv_program::ID),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_history_pages_both_accounts_in_slot_order() {
+        let fixture = Fixture::new();
+        let deposit = fixture.deposit(3_000_000);
+        let burn = fixture.burn(1_000_000);
+        let failed = fixture.deposit(5_000_000);
+
+        let mut mocks = MocksMap::default();
+        // USDC account: newest first, including a failed deposit
+        let failed_status = RpcConfirmedTransactionStatusWithSignature {
+            err: Some(TransactionError::InsufficientFundsForFee),
+            ..status(&failed, 15, 150)
+        };
+        mocks.insert(
+            RpcRequest::GetSignaturesForAddress,
+            serde_json::to_value(vec![status(&burn, 20, 200), failed_status, status(&deposit, 10, 100)])
+                .unwrap(),
+        );
+        // USDv account: the same successful transactions
+        mocks.insert(
+            RpcRequest::GetSignaturesForAddress,
+            serde_json::to_value(vec![status(&burn, 20, 200), status(&deposit, 10, 100)]).unwrap(),
+        );
+        mocks.insert(
+            RpcRequest::GetTransaction,
+            landed(
+                &deposit,
+                10,
+                100,
+                Some(fixture.post_balances(&deposit, 7_000_000, 3_000_000)),
+                deposited_logs(&fixture.user.pubkey(), 3_000_000, 3_000_000),
+            ),
+        );
+        // No token balances reported for the burn
+        mocks.insert(RpcRequest::GetTransaction, landed(&burn, 20, 200, None, Vec::new()));
+
+        let history = mock_client(mocks)
+            .get_user_history(&fixture.user.pubkey(), HistoryRange::all())
+            .await
+            .unwrap();
+
+        assert_eq!(history.user, fixture.user.pubkey().to_string());
+        assert_eq!(history.records.len(), 2);
+
+        let first = &history.records[0];
+        assert_eq!(first.signature, deposit.signatures[0].to_string());
+        assert_eq!(first.action, HistoryAction::Deposit);
+        assert_eq!((first.amount, first.usdc_change, first.usdv_change), (3_000_000, -3_000_000, 3_000_000));
+        assert_eq!((first.usdc_balance, first.usdv_balance), (Some(7_000_000), Some(3_000_000)));
+        assert!(matches!(&first.event, Some(USDvEvent::Deposited(event)) if event.amount == 3_000_000));
+
+        let second = &history.records[1];
+        assert_eq!(second.action, HistoryAction::Burn);
+        assert_eq!((second.slot, second.block_time), (20, Some(200)));
+        // Carried forward from the deposit
+        assert_eq!((second.usdc_balance, second.usdv_balance), (Some(8_000_000), Some(2_000_000)));
+        assert_eq!(second.event, None);
+
+        assert_eq!(history.net_usdv_change(), 2_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_history_keeps_order_within_a_slot() {
+        let fixture = Fixture::new();
+        let first = fixture.deposit(1_000_000);
+        let second = fixture.deposit(2_000_000);
+
+        let mut mocks = MocksMap::default();
+        // Both accounts list both deposits, newest first
+        for _ in 0..2 {
+            mocks.insert(
+                RpcRequest::GetSignaturesForAddress,
+                serde_json::to_value(vec![status(&second, 10, 100), status(&first, 10, 100)]).unwrap(),
+            );
+        }
+        mocks.insert(
+            RpcRequest::GetTransaction,
+            landed(&first, 10, 100, Some(fixture.post_balances(&first, 9_000_000, 1_000_000)), Vec::new()),
+        );
+        mocks.insert(
+            RpcRequest::GetTransaction,
+            landed(&second, 10, 100, Some(fixture.post_balances(&second, 7_000_000, 3_000_000)), Vec::new()),
+        );
+
+        let history = mock_client(mocks)
+            .get_user_history(&fixture.user.pubkey(), HistoryRange::all())
+            .await
+            .unwrap();
+
+        let signatures: Vec<&str> = history.records.iter().map(|record| record.signature.as_str()).collect();
+        assert_eq!(
+            signatures,
+            vec![first.signatures[0].to_string(), second.signatures[0].to_string()]
+        );
+        assert_eq!(history.records[1].usdv_balance, Some(3_000_000));
+    }
+
+    #[tokio::test]
+    async fn test_history_merges_accounts_within_a_slot() {
+        let fixture = Fixture::new();
+        let minter = Keypair::new();
+        let mut mint = Transaction::new_with_payer(
+            &[fixture.builder.mint_allowance(
+                &minter.pubkey(),
+                &fixture.config.usdv_mint,
+                &fixture.user_usdv(),
+                500_000,
+            )],
+            Some(&minter.pubkey()),
+        );
+        mint.sign(&[&minter], Hash::new_unique());
+        let deposit = fixture.deposit(1_000_000);
+
+        let mut mocks = MocksMap::default();
+        // The off-chain mint only touches the USDv account and landed just
+        // before the deposit in the same slot
+        mocks.insert(
+            RpcRequest::GetSignaturesForAddress,
+            serde_json::to_value(vec![status(&deposit, 10, 100)]).unwrap(),
+        );
+        mocks.insert(
+            RpcRequest::GetSignaturesForAddress,
+            serde_json::to_value(vec![status(&deposit, 10, 100), status(&mint, 10, 100)]).unwrap(),
+        );
+        mocks.insert(RpcRequest::GetTransaction, landed(&mint, 10, 100, None, Vec::new()));
+        mocks.insert(
+            RpcRequest::GetTransaction,
+            landed(&deposit, 10, 100, Some(fixture.post_balances(&deposit, 9_000_000, 1_500_000)), Vec::new()),
+        );
+
+        let history = mock_client(mocks)
+            .get_user_history(&fixture.user.pubkey(), HistoryRange::all())
+            .await
+            .unwrap();
+
+        let records: Vec<(String, HistoryAction)> = history
+            .records
+            .iter()
+            .map(|record| (record.signature.clone(), record.action))
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                (mint.signatures[0].to_string(), HistoryAction::OffchainMint),
+                (deposit.signatures[0].to_string(), HistoryAction::Deposit),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_matches_events_to_the_user() {
+        let fixture = Fixture::new();
+        let other = Keypair::new();
+        let other_deposit = fixture.builder.deposit_and_mint(
+            &other.pubkey(),
+            &fixture.config.usdc_mint,
+            &fixture.config.usdv_mint,
+            false,
+            4_000_000,
+        );
+        let user_deposit = fixture.builder.deposit_and_mint(
+            &fixture.user.pubkey(),
+            &fixture.config.usdc_mint,
+            &fixture.config.usdv_mint,
+            false,
+            1_000_000,
+        );
+        let mut deposits = Transaction::new_with_payer(&[other_deposit, user_deposit], Some(&fixture.user.pubkey()));
+        deposits.sign(&[&fixture.user, &other], Hash::new_unique());
+
+        let mut logs = deposited_logs(&other.pubkey(), 4_000_000, 4_000_000);
+        logs.extend(deposited_logs(&fixture.user.pubkey(), 1_000_000, 5_000_000));
+
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetSignaturesForAddress,
+            serde_json::to_value(vec![status(&deposits, 10, 100)]).unwrap(),
+        );
+        mocks.insert(
+            RpcRequest::GetSignaturesForAddress,
+            serde_json::to_value(vec![status(&deposits, 10, 100)]).unwrap(),
+        );
+        mocks.insert(
+            RpcRequest::GetTransaction,
+            landed(&deposits, 10, 100, Some(fixture.post_balances(&deposits, 9_000_000, 1_000_000)), logs),
+        );
+
+        let history = mock_client(mocks)
+            .get_user_history(&fixture.user.pubkey(), HistoryRange::all())
+            .await
+            .unwrap();
+
+        assert_eq!(history.records.len(), 1);
+        assert_eq!(history.records[0].amount, 1_000_000);
+        assert!(matches!(
+            &history.records[0].event,
+            Some(USDvEvent::Deposited(event)) if event.user == fixture.user.pubkey() && event.amount == 1_000_000
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_history_range_stops_paging_at_start() {
+        let fixture = Fixture::new();
+        let deposit = fixture.deposit(3_000_000);
+        let burn = fixture.burn(1_000_000);
+
+        let mut mocks = MocksMap::default();
+        for _ in 0..2 {
+            mocks.insert(
+                RpcRequest::GetSignaturesForAddress,
+                serde_json::to_value(vec![status(&burn, 20, 200), status(&deposit, 10, 100)]).unwrap(),
+            );
+        }
+        mocks.insert(
+            RpcRequest::GetTransaction,
+            landed(&burn, 20, 200, Some(fixture.post_balances(&burn, 8_000_000, 2_000_000)), Vec::new()),
+        );
+
+        let history = mock_client(mocks)
+            .get_user_history(&fixture.user.pubkey(), HistoryRange::since(150))
+            .await
+            .unwrap();
+
+        assert_eq!(history.records.len(), 1);
+        assert_eq!(history.records[0].action, HistoryAction::Burn);
+        assert_eq!(history.records[0].signature, burn.signatures[0].to_string());
+    }
+
+    #[test]
+    fn test_range_bounds() {
+        let range = HistoryRange::between(100, 200);
+        assert!(range.contains(Some(100)));
+        assert!(range.contains(Some(199)));
+        assert!(!range.contains(Some(200)));
+        assert!(!range.contains(Some(99)));
+        assert!(!range.contains(None));
+        assert!(HistoryRange::all().contains(None));
+    }
+
+    #[tokio::test]
+    async fn test_statement_exports() {
+        let fixture = Fixture::new();
+        let deposit = fixture.deposit(3_000_000);
+
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetSignaturesForAddress,
+            serde_json::to_value(vec![status(&deposit, 10, 100)]).unwrap(),
+        );
+        mocks.insert(
+            RpcRequest::GetTransaction,
+            landed(
+                &deposit,
+                10,
+                100,
+                Some(fixture.post_balances(&deposit, 7_000_000, 3_000_000)),
+                deposited_logs(&fixture.user.pubkey(), 3_000_000, 3_000_000),
+            ),
+        );
+        mocks.insert(RpcRequest::GetSignaturesForAddress, serde_json::to_value(Vec::<Value>::new()).unwrap());
+
+        let history = mock_client(mocks)
+            .get_user_history(&fixture.user.pubkey(), HistoryRange::all())
+            .await
+            .unwrap();
+
+        let csv = history.to_csv().unwrap();
+        let lines: Vec<String> = csv.lines().map(str::to_string).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "signature,slot,block_time,action,amount,usdc_change,usdv_change,usdc_balance,usdv_balance"
+                    .to_string(),
+                format!(
+                    "{},10,100,deposit,3000000,-3000000,3000000,7000000,3000000",
+                    deposit.signatures[0]
+                ),
+            ]
+        );
+
+        let json = history.to_json().unwrap();
+        assert_eq!(serde_json::from_str::<UserHistory>(&json).unwrap(), history);
+    }
+}
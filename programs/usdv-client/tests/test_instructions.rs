@@ -0,0 +1,506 @@
+//! `InstructionBuilder` tests
+//!
+//! The LiteSVM tests load the compiled program from
+//! `target/deploy/usdv_program.so`, so they only run with the `test-sbf`
+//! feature: build the program with `anchor build`, then
+//! `cargo test --features test-sbf`.
+
+// LiteSVM's failed-transaction metadata is large; fine for test helpers
+#![allow(clippy::result_large_err)]
+
+#[cfg(test)]
+mod tests {
+
+    use anchor_lang::solana_program::hash::hash;
+    use anchor_lang::AccountDeserialize;
+    use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account;
+    use anchor_spl::token::spl_token;
+    use litesvm::LiteSVM;
+    use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+    use solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        program_option::COption,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+    use usdv_client::types::AllowlistUpdate;
+    use usdv_client::InstructionBuilder;
+    use usdv_program::ProgramState;
+    use usdv_utils::constants::{ASSOCIATED_TOKEN_PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID};
+    use usdv_utils::pda::derive_associated_token_account;
+
+    const PROGRAM_SO: &str = "../../target/deploy/usdv_program.so";
+    const ONE_TOKEN: u64 = 1_000_000;
+
+    type TxResult = Result<TransactionMetadata, FailedTransactionMetadata>;
+
+    /// Anchor discriminator computed independently of the generated code
+    fn discriminator(name: &str) -> [u8; 8] {
+        hash(format!("global:{name}").as_bytes()).to_bytes()[..8]
+            .try_into()
+            .unwrap()
+    }
+
+    fn assert_instruction(instruction: &Instruction, name: &str, args: &[u8]) {
+        assert_eq!(instruction.program_id, usdv_program::ID);
+        assert_eq!(instruction.data[..8], discriminator(name), "{name} discriminator");
+        assert_eq!(instruction.data[8..], *args, "{name} args");
+    }
+
+    #[test]
+    fn test_discriminators_and_args() {
+        let builder = InstructionBuilder::new(usdv_program::ID);
+        let admin = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let usdc_mint = Pubkey::new_unique();
+        let usdv_mint = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        assert_instruction(
+            &builder.initialize(&admin, &usdv_mint, &usdc_mint),
+            "initialize",
+            usdc_mint.as_ref(),
+        );
+        assert_instruction(
+            &builder.deposit_and_mint(&user, &usdc_mint, &usdv_mint, false, 42),
+            "deposit_and_mint",
+            &42u64.to_le_bytes(),
+        );
+        assert_instruction(
+            &builder.burn_and_withdraw(&user, &usdc_mint, &usdv_mint, false, 42),
+            "burn_and_withdraw",
+            &42u64.to_le_bytes(),
+        );
+        assert_instruction(
+            &builder.update_program_state(&admin, None),
+            "update_program_state",
+            &[0],
+        );
+        assert_instruction(
+            &builder.update_program_state(&admin, Some(other)),
+            "update_program_state",
+            &[&[1][..], other.as_ref()].concat(),
+        );
+        assert_instruction(
+            &builder.initiate_wind_down(&admin, -7),
+            "initiate_wind_down",
+            &(-7i64).to_le_bytes(),
+        );
+        assert_instruction(
+            &builder.sweep_unclaimed(&admin, &usdc_mint, &other),
+            "sweep_unclaimed",
+            &[],
+        );
+        assert_instruction(
+            &builder.recover_tokens(&admin, &other, &other),
+            "recover_tokens",
+            &[],
+        );
+        assert_instruction(
+            &builder.configure_minter(&admin, &other, 5),
+            "configure_minter",
+            &5u64.to_le_bytes(),
+        );
+        assert_instruction(
+            &builder.mint_allowance(&other, &usdv_mint, &user, 6),
+            "mint_allowance",
+            &6u64.to_le_bytes(),
+        );
+        assert_instruction(
+            &builder.burn_for_offchain_redemption(&other, &usdv_mint, 7),
+            "burn_for_offchain_redemption",
+            &7u64.to_le_bytes(),
+        );
+        assert_instruction(
+            &builder.set_allowlist_mode(&admin, true),
+            "set_allowlist_mode",
+            &[1],
+        );
+
+        // Vec length, then variant index and fields of each update
+        let updates = [
+            AllowlistUpdate::Add { wallet: user, expires_at: 9 },
+            AllowlistUpdate::Remove { wallet: other },
+        ];
+        let expected = [
+            &2u32.to_le_bytes()[..],
+            &[0],
+            user.as_ref(),
+            &9i64.to_le_bytes(),
+            &[1],
+            other.as_ref(),
+        ]
+        .concat();
+        assert_instruction(
+            &builder.update_allowlist(&admin, &updates),
+            "update_allowlist",
+            &expected,
+        );
+    }
+
+    #[test]
+    fn test_deposit_and_mint_account_metas() {
+        let builder = InstructionBuilder::new(usdv_program::ID);
+        let user = Pubkey::new_unique();
+        let usdc_mint = Pubkey::new_unique();
+        let usdv_mint = Pubkey::new_unique();
+
+        let expected = |allowlist_entry: AccountMeta| {
+            vec![
+                AccountMeta::new(*builder.program_state(), false),
+                AccountMeta::new_readonly(usdc_mint, false),
+                AccountMeta::new(usdv_mint, false),
+                AccountMeta::new(derive_associated_token_account(&user, &usdc_mint), false),
+                AccountMeta::new(derive_associated_token_account(&user, &usdv_mint), false),
+                AccountMeta::new(builder.vault_usdc_account(&usdc_mint), false),
+                AccountMeta::new_readonly(*builder.vault_authority(), false),
+                AccountMeta::new(user, true),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                allowlist_entry,
+            ]
+        };
+
+        let instruction = builder.deposit_and_mint(&user, &usdc_mint, &usdv_mint, false, 1);
+        assert_eq!(
+            instruction.accounts,
+            expected(AccountMeta::new_readonly(usdv_program::ID, false))
+        );
+
+        let instruction = builder.deposit_and_mint(&user, &usdc_mint, &usdv_mint, true, 1);
+        assert_eq!(
+            instruction.accounts,
+            expected(AccountMeta::new_readonly(builder.allowlist_entry(&user), false))
+        );
+    }
+
+    #[test]
+    fn test_burn_and_withdraw_account_metas() {
+        let builder = InstructionBuilder::new(usdv_program::ID);
+        let user = Pubkey::new_unique();
+        let usdc_mint = Pubkey::new_unique();
+        let usdv_mint = Pubkey::new_unique();
+
+        let instruction = builder.burn_and_withdraw(&user, &usdc_mint, &usdv_mint, false, 1);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new(*builder.program_state(), false),
+                AccountMeta::new(usdv_mint, false),
+                AccountMeta::new(derive_associated_token_account(&user, &usdv_mint), false),
+                AccountMeta::new(derive_associated_token_account(&user, &usdc_mint), false),
+                AccountMeta::new(builder.vault_usdc_account(&usdc_mint), false),
+                AccountMeta::new_readonly(*builder.vault_authority(), false),
+                AccountMeta::new(user, true),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(usdv_program::ID, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_admin_account_metas() {
+        let builder = InstructionBuilder::new(usdv_program::ID);
+        let admin = Pubkey::new_unique();
+        let usdv_mint = Pubkey::new_unique();
+
+        assert_eq!(
+            builder.initialize(&admin, &usdv_mint, &Pubkey::new_unique()).accounts,
+            vec![
+                AccountMeta::new(*builder.program_state(), false),
+                AccountMeta::new(usdv_mint, true),
+                AccountMeta::new_readonly(*builder.vault_authority(), false),
+                AccountMeta::new(admin, true),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ]
+        );
+
+        assert_eq!(
+            builder.set_allowlist_mode(&admin, true).accounts,
+            vec![
+                AccountMeta::new(*builder.program_state(), false),
+                AccountMeta::new_readonly(admin, true),
+            ]
+        );
+
+        let wallet = Pubkey::new_unique();
+        assert_eq!(
+            builder
+                .update_allowlist(&admin, &[AllowlistUpdate::Remove { wallet }])
+                .accounts,
+            vec![
+                AccountMeta::new_readonly(*builder.program_state(), false),
+                AccountMeta::new(admin, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new(builder.allowlist_entry(&wallet), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_program_id() {
+        let program_id = Pubkey::new_unique();
+        let builder = InstructionBuilder::new(program_id);
+        let user = Pubkey::new_unique();
+
+        let instruction =
+            builder.burn_and_withdraw(&user, &Pubkey::new_unique(), &Pubkey::new_unique(), false, 1);
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(
+            instruction.accounts.last(),
+            Some(&AccountMeta::new_readonly(program_id, false))
+        );
+        assert!(instruction.accounts.iter().all(|meta| meta.pubkey != usdv_program::ID));
+    }
+
+    /// An initialized deployment driven only through `InstructionBuilder`
+    struct Env {
+        svm: LiteSVM,
+        builder: InstructionBuilder,
+        admin: Keypair,
+        usdc_mint: Pubkey,
+        usdv_mint: Pubkey,
+    }
+
+    impl Env {
+        fn new() -> Self {
+            let mut svm = LiteSVM::new();
+            svm.add_program_from_file(usdv_program::ID, PROGRAM_SO)
+                .expect("build the program with `anchor build` before running tests");
+
+            let admin = Keypair::new();
+            svm.airdrop(&admin.pubkey(), 100_000_000_000).unwrap();
+            let usdc_mint = create_mint(&mut svm, &admin.pubkey());
+
+            let builder = InstructionBuilder::new(usdv_program::ID);
+            let usdv_mint = Keypair::new();
+            let ix = builder.initialize(&admin.pubkey(), &usdv_mint.pubkey(), &usdc_mint);
+            send(&mut svm, ix, &admin, &[&usdv_mint]).expect("initialize failed");
+
+            Self {
+                svm,
+                builder,
+                admin,
+                usdc_mint,
+                usdv_mint: usdv_mint.pubkey(),
+            }
+        }
+
+        /// Funded user holding `usdc_amount` USDC in their ATA
+        fn create_user(&mut self, usdc_amount: u64) -> Keypair {
+            let user = Keypair::new();
+            self.svm.airdrop(&user.pubkey(), 10_000_000_000).unwrap();
+            let ix = create_associated_token_account(
+                &user.pubkey(),
+                &user.pubkey(),
+                &self.usdc_mint,
+                &spl_token::ID,
+            );
+            send(&mut self.svm, ix, &user, &[]).unwrap();
+
+            let usdc_mint = self.usdc_mint;
+            let account = derive_associated_token_account(&user.pubkey(), &usdc_mint);
+            self.mint_tokens(&usdc_mint, &account, usdc_amount);
+            user
+        }
+
+        fn mint_tokens(&mut self, mint: &Pubkey, account: &Pubkey, amount: u64) {
+            let ix = spl_token::instruction::mint_to(
+                &spl_token::ID,
+                mint,
+                account,
+                &self.admin.pubkey(),
+                &[],
+                amount,
+            )
+            .unwrap();
+            let admin = self.admin.insecure_clone();
+            send(&mut self.svm, ix, &admin, &[]).unwrap();
+        }
+
+        fn admin_send(&mut self, ix: Instruction) -> TxResult {
+            let admin = self.admin.insecure_clone();
+            send(&mut self.svm, ix, &admin, &[])
+        }
+
+        fn balance(&self, account: &Pubkey) -> u64 {
+            self.svm
+                .get_account(account)
+                .map(|a| spl_token::state::Account::unpack(&a.data).unwrap().amount)
+                .unwrap_or(0)
+        }
+
+        fn usdv_balance(&self, owner: &Pubkey) -> u64 {
+            self.balance(&derive_associated_token_account(owner, &self.usdv_mint))
+        }
+
+        fn program_state(&self) -> ProgramState {
+            let account = self.svm.get_account(self.builder.program_state()).unwrap();
+            ProgramState::try_deserialize(&mut account.data.as_slice()).unwrap()
+        }
+    }
+
+    fn create_mint(svm: &mut LiteSVM, authority: &Pubkey) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        let state = spl_token::state::Mint {
+            mint_authority: COption::Some(*authority),
+            supply: 0,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint::pack(state, &mut data).unwrap();
+        set_token_program_account(svm, mint, data);
+        mint
+    }
+
+    fn create_token_account(svm: &mut LiteSVM, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+        let account = Pubkey::new_unique();
+        let state = spl_token::state::Account {
+            mint: *mint,
+            owner: *owner,
+            amount: 0,
+            delegate: COption::None,
+            state: spl_token::state::AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        };
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account::pack(state, &mut data).unwrap();
+        set_token_program_account(svm, account, data);
+        account
+    }
+
+    fn set_token_program_account(svm: &mut LiteSVM, address: Pubkey, data: Vec<u8>) {
+        let account = Account {
+            lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: spl_token::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        svm.set_account(address, account).unwrap();
+    }
+
+    fn send(svm: &mut LiteSVM, ix: Instruction, payer: &Keypair, signers: &[&Keypair]) -> TxResult {
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &all_signers,
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        svm.expire_blockhash();
+        result
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "test-sbf"), ignore = "needs the compiled program; run with `--features test-sbf`")]
+    fn test_deposit_and_burn_execute() {
+        let mut env = Env::new();
+        let user = env.create_user(100 * ONE_TOKEN);
+
+        let ix = env.builder.deposit_and_mint(
+            &user.pubkey(),
+            &env.usdc_mint,
+            &env.usdv_mint,
+            false,
+            60 * ONE_TOKEN,
+        );
+        send(&mut env.svm, ix, &user, &[]).unwrap();
+        assert_eq!(env.usdv_balance(&user.pubkey()), 60 * ONE_TOKEN);
+
+        let ix = env.builder.burn_and_withdraw(
+            &user.pubkey(),
+            &env.usdc_mint,
+            &env.usdv_mint,
+            false,
+            20 * ONE_TOKEN,
+        );
+        send(&mut env.svm, ix, &user, &[]).unwrap();
+        assert_eq!(env.usdv_balance(&user.pubkey()), 40 * ONE_TOKEN);
+        assert_eq!(env.program_state().total_usdc_deposits, 40 * ONE_TOKEN);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "test-sbf"), ignore = "needs the compiled program; run with `--features test-sbf`")]
+    fn test_admin_instructions_execute() {
+        let mut env = Env::new();
+        let admin = env.admin.pubkey();
+
+        let ix = env.builder.update_program_state(&admin, None);
+        env.admin_send(ix).unwrap();
+
+        // Allowlisted deposit needs the entry passed as the optional account
+        let user = env.create_user(10 * ONE_TOKEN);
+        env.admin_send(env.builder.set_allowlist_mode(&admin, true)).unwrap();
+        let updates = [AllowlistUpdate::Add { wallet: user.pubkey(), expires_at: 0 }];
+        env.admin_send(env.builder.update_allowlist(&admin, &updates)).unwrap();
+        assert!(env.program_state().is_allowlist_enabled());
+
+        let ix = env.builder.deposit_and_mint(
+            &user.pubkey(),
+            &env.usdc_mint,
+            &env.usdv_mint,
+            true,
+            10 * ONE_TOKEN,
+        );
+        send(&mut env.svm, ix, &user, &[]).unwrap();
+        assert_eq!(env.usdv_balance(&user.pubkey()), 10 * ONE_TOKEN);
+
+        // Stray tokens sent to the vault authority can be recovered
+        let stray_mint = create_mint(&mut env.svm, &admin);
+        let source = create_token_account(&mut env.svm, &stray_mint, env.builder.vault_authority());
+        env.mint_tokens(&stray_mint, &source, 5 * ONE_TOKEN);
+        let destination = create_token_account(&mut env.svm, &stray_mint, &admin);
+        env.admin_send(env.builder.recover_tokens(&admin, &source, &destination)).unwrap();
+        assert_eq!(env.balance(&destination), 5 * ONE_TOKEN);
+
+        let ix = env.builder.initiate_wind_down(&admin, ProgramState::MIN_WIND_DOWN_CLAIM_PERIOD);
+        env.admin_send(ix).unwrap();
+        assert!(env.program_state().is_wound_down());
+
+        // The sweep is built correctly but rejected before the deadline
+        let recovery = create_token_account(&mut env.svm, &env.usdc_mint, &admin);
+        let ix = env.builder.sweep_unclaimed(&admin, &env.usdc_mint, &recovery);
+        assert!(env.admin_send(ix).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "test-sbf"), ignore = "needs the compiled program; run with `--features test-sbf`")]
+    fn test_minter_instructions_execute() {
+        let mut env = Env::new();
+        let admin = env.admin.pubkey();
+        let minter = env.create_user(0);
+
+        let ix = env.builder.configure_minter(&admin, &minter.pubkey(), 50 * ONE_TOKEN);
+        env.admin_send(ix).unwrap();
+
+        let ix = create_associated_token_account(
+            &minter.pubkey(),
+            &minter.pubkey(),
+            &env.usdv_mint,
+            &spl_token::ID,
+        );
+        send(&mut env.svm, ix, &minter, &[]).unwrap();
+        let destination = derive_associated_token_account(&minter.pubkey(), &env.usdv_mint);
+
+        let ix = env.builder.mint_allowance(&minter.pubkey(), &env.usdv_mint, &destination, 30 * ONE_TOKEN);
+        send(&mut env.svm, ix, &minter, &[]).unwrap();
+
+        let ix = env.builder.burn_for_offchain_redemption(&minter.pubkey(), &env.usdv_mint, 10 * ONE_TOKEN);
+        send(&mut env.svm, ix, &minter, &[]).unwrap();
+
+        assert_eq!(env.usdv_balance(&minter.pubkey()), 20 * ONE_TOKEN);
+        assert_eq!(env.program_state().total_offchain_deposits, 20 * ONE_TOKEN);
+    }
+}
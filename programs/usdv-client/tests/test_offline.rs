@@ -0,0 +1,215 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::mock_client;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde_json::json;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_rpc_client::mock_sender::MocksMap;
+    use solana_nonce::{
+        state::{Data, DurableNonce, State},
+        versions::Versions,
+    };
+    use solana_sdk::{
+        account::Account,
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+    };
+    use solana_system_interface::program as system_program;
+    use usdv_client::offline::{
+        add_signature, decode_transaction_base64, deserialize_transaction,
+        encode_transaction_base64, missing_signers, nonce_transaction, serialize_transaction,
+        sign_offline, NonceInfo,
+    };
+    use usdv_results::USDvClientError;
+
+    fn nonce_data(authority: &Pubkey, blockhash: &Hash) -> Vec<u8> {
+        let data = Data::new(*authority, DurableNonce::from_blockhash(blockhash), 5000);
+        bincode::serialize(&Versions::new(State::Initialized(data))).unwrap()
+    }
+
+    fn admin_instruction(admin: &Pubkey) -> Instruction {
+        Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1, 2, 3],
+            vec![AccountMeta::new_readonly(*admin, true)],
+        )
+    }
+
+    fn test_nonce(authority: &Pubkey) -> NonceInfo {
+        NonceInfo {
+            account: Pubkey::new_unique(),
+            authority: *authority,
+            blockhash: Hash::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_nonce_from_account() {
+        let address = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+        let account = Account {
+            lamports: 1_447_680,
+            data: nonce_data(&authority, &blockhash),
+            owner: system_program::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        let nonce = NonceInfo::from_account(&address, &account).unwrap();
+        assert_eq!(nonce.account, address);
+        assert_eq!(nonce.authority, authority);
+        assert_eq!(nonce.blockhash, *DurableNonce::from_blockhash(&blockhash).as_hash());
+
+        let wrong_owner = Account {
+            owner: Pubkey::new_unique(),
+            ..account
+        };
+        assert!(matches!(
+            NonceInfo::from_account(&address, &wrong_owner),
+            Err(USDvClientError::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_nonce_transaction_advances_nonce_first() {
+        let fee_payer = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let nonce = test_nonce(&admin);
+
+        let transaction = nonce_transaction(&[admin_instruction(&admin)], &fee_payer, &nonce);
+        let message = &transaction.message;
+
+        assert_eq!(message.recent_blockhash, nonce.blockhash);
+        assert_eq!(message.account_keys[0], fee_payer);
+        assert_eq!(message.instructions.len(), 2);
+        assert_eq!(
+            *message.instructions[0].program_id(&message.account_keys),
+            system_program::ID
+        );
+        assert!(solana_sdk::transaction::uses_durable_nonce(&transaction).is_some());
+        assert_eq!(missing_signers(&transaction), vec![fee_payer, admin]);
+    }
+
+    #[test]
+    fn test_signatures_collected_across_exports() {
+        let fee_payer = Keypair::new();
+        let admin = Keypair::new();
+        let nonce = test_nonce(&admin.pubkey());
+        let transaction =
+            nonce_transaction(&[admin_instruction(&admin.pubkey())], &fee_payer.pubkey(), &nonce);
+
+        // Cold machine: import, sign with the admin key, export
+        let mut cold = decode_transaction_base64(&encode_transaction_base64(&transaction).unwrap())
+            .unwrap();
+        sign_offline(&mut cold, &[&admin]).unwrap();
+        assert_eq!(missing_signers(&cold), vec![fee_payer.pubkey()]);
+        let exported = serialize_transaction(&cold).unwrap();
+
+        // Online machine: import and add the fee payer's signature
+        let mut online = deserialize_transaction(&exported).unwrap();
+        sign_offline(&mut online, &[&fee_payer]).unwrap();
+
+        assert!(missing_signers(&online).is_empty());
+        assert!(online.is_signed());
+        assert_eq!(online.message.recent_blockhash, nonce.blockhash);
+        online.verify().unwrap();
+    }
+
+    #[test]
+    fn test_add_signature_made_elsewhere() {
+        let fee_payer = Keypair::new();
+        let admin = Keypair::new();
+        let nonce = test_nonce(&admin.pubkey());
+        let mut transaction =
+            nonce_transaction(&[admin_instruction(&admin.pubkey())], &fee_payer.pubkey(), &nonce);
+        let message = transaction.message_data();
+
+        let wrong = Keypair::new().sign_message(&message);
+        assert!(matches!(
+            add_signature(&mut transaction, &admin.pubkey(), wrong),
+            Err(USDvClientError::SigningError(_))
+        ));
+
+        let stranger = Keypair::new();
+        assert!(matches!(
+            add_signature(&mut transaction, &stranger.pubkey(), stranger.sign_message(&message)),
+            Err(USDvClientError::SigningError(_))
+        ));
+
+        add_signature(&mut transaction, &admin.pubkey(), admin.sign_message(&message)).unwrap();
+        assert_eq!(missing_signers(&transaction), vec![fee_payer.pubkey()]);
+    }
+
+    #[test]
+    fn test_malformed_exports_are_rejected() {
+        assert!(matches!(
+            decode_transaction_base64("not base64!"),
+            Err(USDvClientError::SerializationError(_))
+        ));
+        assert!(matches!(
+            deserialize_transaction(&[1, 2, 3]),
+            Err(USDvClientError::SerializationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_offline_transaction_reads_nonce() {
+        let nonce_account = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            json!({
+                "context": { "slot": 1 },
+                "value": {
+                    "lamports": 1_447_680,
+                    "data": [STANDARD.encode(nonce_data(&authority, &blockhash)), "base64"],
+                    "owner": system_program::ID.to_string(),
+                    "executable": false,
+                    "rentEpoch": 0,
+                    "space": 80,
+                },
+            }),
+        );
+        let client = mock_client(mocks);
+
+        let fee_payer = Pubkey::new_unique();
+        let transaction = client
+            .prepare_offline_transaction(&[admin_instruction(&authority)], &fee_payer, &nonce_account)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            transaction.message.recent_blockhash,
+            *DurableNonce::from_blockhash(&blockhash).as_hash()
+        );
+        assert_eq!(missing_signers(&transaction), vec![fee_payer, authority]);
+    }
+
+    #[tokio::test]
+    async fn test_submit_signed() {
+        let client = mock_client(MocksMap::default());
+        let fee_payer = Keypair::new();
+        let admin = Keypair::new();
+        let nonce = test_nonce(&admin.pubkey());
+        let mut transaction =
+            nonce_transaction(&[admin_instruction(&admin.pubkey())], &fee_payer.pubkey(), &nonce);
+
+        assert!(matches!(
+            client.submit_signed(&transaction).await,
+            Err(USDvClientError::SigningError(_))
+        ));
+
+        sign_offline(&mut transaction, &[&fee_payer, &admin]).unwrap();
+        let signature = client.submit_signed(&transaction).await.unwrap();
+        assert_eq!(signature, transaction.signatures[0]);
+        assert_ne!(signature, Signature::default());
+    }
+}
@@ -0,0 +1,363 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::{mock_client, test_config};
+    use anchor_lang::Event;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use solana_account_decoder_client_types::token::UiTokenAmount;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_rpc_client::mock_sender::MocksMap;
+    use solana_sdk::{
+        commitment_config::CommitmentConfig,
+        message::MessageHeader,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+        transaction::TransactionError,
+    };
+    use solana_transaction_status_client_types::{
+        option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+        EncodedTransaction, EncodedTransactionWithStatusMeta, UiMessage, UiRawMessage,
+        UiTransaction, UiTransactionStatusMeta, UiTransactionTokenBalance,
+    };
+    use usdv_client::events::{self, decode_events, USDvEvent};
+    use usdv_client::logs::parse_logs;
+    use usdv_client::receipts::transaction_info;
+    use usdv_client::types::TransactionStatus;
+    use usdv_utils::pda::derive_associated_token_account;
+
+    fn event_log<E: Event>(event: &E) -> String {
+        format!("Program data: {}", STANDARD.encode(event.data()))
+    }
+
+    fn program_logs(lines: &[String]) -> Vec<String> {
+        let program_id = usdv_program::ID.to_string();
+        let mut logs = vec![format!("Program {} invoke [1]", program_id)];
+        logs.extend_from_slice(lines);
+        logs.push(format!("Program {} success", program_id));
+        logs
+    }
+
+    fn token_balance(account_index: u8, mint: &Pubkey, amount: u64) -> UiTransactionTokenBalance {
+        UiTransactionTokenBalance {
+            account_index,
+            mint: mint.to_string(),
+            ui_token_amount: UiTokenAmount {
+                ui_amount: Some(amount as f64 / 1e6),
+                decimals: 6,
+                amount: amount.to_string(),
+                ui_amount_string: (amount as f64 / 1e6).to_string(),
+            },
+            owner: OptionSerializer::None,
+            program_id: OptionSerializer::None,
+        }
+    }
+
+    /// A landed transaction touching `accounts`, with token balances given by account index
+    fn landed_transaction(
+        accounts: &[Pubkey],
+        pre: Vec<UiTransactionTokenBalance>,
+        post: Vec<UiTransactionTokenBalance>,
+        logs: Vec<String>,
+        err: Option<TransactionError>,
+    ) -> EncodedConfirmedTransactionWithStatusMeta {
+        EncodedConfirmedTransactionWithStatusMeta {
+            slot: 42,
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: EncodedTransaction::Json(UiTransaction {
+                    signatures: vec![Signature::default().to_string()],
+                    message: UiMessage::Raw(UiRawMessage {
+                        header: MessageHeader {
+                            num_required_signatures: 1,
+                            num_readonly_signed_accounts: 0,
+                            num_readonly_unsigned_accounts: 0,
+                        },
+                        account_keys: accounts.iter().map(Pubkey::to_string).collect(),
+                        recent_blockhash: Pubkey::default().to_string(),
+                        instructions: Vec::new(),
+                        address_table_lookups: None,
+                    }),
+                }),
+                meta: Some(UiTransactionStatusMeta {
+                    status: match &err {
+                        Some(err) => Err(err.clone()),
+                        None => Ok(()),
+                    },
+                    err,
+                    fee: 5_000,
+                    pre_balances: vec![0; accounts.len()],
+                    post_balances: vec![0; accounts.len()],
+                    inner_instructions: OptionSerializer::None,
+                    log_messages: OptionSerializer::Some(logs),
+                    pre_token_balances: OptionSerializer::Some(pre),
+                    post_token_balances: OptionSerializer::Some(post),
+                    rewards: OptionSerializer::None,
+                    loaded_addresses: OptionSerializer::Skip,
+                    return_data: OptionSerializer::Skip,
+                    compute_units_consumed: OptionSerializer::Skip,
+                    cost_units: OptionSerializer::Skip,
+                }),
+                version: None,
+            },
+            block_time: Some(1_700_000_000),
+        }
+    }
+
+    #[test]
+    fn test_every_program_event_decodes() {
+        let user = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let minter = Pubkey::new_unique();
+        let logs = program_logs(&[
+            event_log(&usdv_program::events::Deposited {
+                user,
+                amount: 1,
+                total_usdv_supply: 2,
+            }),
+            event_log(&usdv_program::events::Burned {
+                user,
+                amount: 3,
+                total_usdv_supply: 4,
+            }),
+            event_log(&usdv_program::events::WindDownInitiated {
+                admin,
+                started_at: 5,
+                deadline: 6,
+                outstanding_usdv_supply: 7,
+            }),
+            event_log(&usdv_program::events::UnclaimedCollateralSwept {
+                admin,
+                recovery_account: user,
+                amount: 8,
+                outstanding_usdv_supply: 9,
+                timestamp: 10,
+            }),
+            event_log(&usdv_program::events::TokensRecovered {
+                admin,
+                mint: minter,
+                source: user,
+                destination: admin,
+                amount: 11,
+                timestamp: 12,
+            }),
+            event_log(&usdv_program::events::MinterConfigured {
+                admin,
+                minter,
+                allowance: 13,
+            }),
+            event_log(&usdv_program::events::OffchainMint {
+                minter,
+                destination: user,
+                amount: 14,
+                remaining_allowance: 15,
+            }),
+            event_log(&usdv_program::events::OffchainRedemption { minter, amount: 16 }),
+            event_log(&usdv_program::events::AllowlistModeChanged {
+                admin,
+                enabled: true,
+            }),
+            event_log(&usdv_program::events::AllowlistUpdated {
+                admin,
+                added: 17,
+                removed: 18,
+            }),
+        ]);
+
+        let decoded = decode_events(&parse_logs(&logs), &usdv_program::ID);
+        assert_eq!(
+            decoded,
+            vec![
+                USDvEvent::Deposited(events::Deposited {
+                    user,
+                    amount: 1,
+                    total_usdv_supply: 2,
+                }),
+                USDvEvent::Burned(events::Burned {
+                    user,
+                    amount: 3,
+                    total_usdv_supply: 4,
+                }),
+                USDvEvent::WindDownInitiated(events::WindDownInitiated {
+                    admin,
+                    started_at: 5,
+                    deadline: 6,
+                    outstanding_usdv_supply: 7,
+                }),
+                USDvEvent::UnclaimedCollateralSwept(events::UnclaimedCollateralSwept {
+                    admin,
+                    recovery_account: user,
+                    amount: 8,
+                    outstanding_usdv_supply: 9,
+                    timestamp: 10,
+                }),
+                USDvEvent::TokensRecovered(events::TokensRecovered {
+                    admin,
+                    mint: minter,
+                    source: user,
+                    destination: admin,
+                    amount: 11,
+                    timestamp: 12,
+                }),
+                USDvEvent::MinterConfigured(events::MinterConfigured {
+                    admin,
+                    minter,
+                    allowance: 13,
+                }),
+                USDvEvent::OffchainMint(events::OffchainMint {
+                    minter,
+                    destination: user,
+                    amount: 14,
+                    remaining_allowance: 15,
+                }),
+                USDvEvent::OffchainRedemption(events::OffchainRedemption { minter, amount: 16 }),
+                USDvEvent::AllowlistModeChanged(events::AllowlistModeChanged {
+                    admin,
+                    enabled: true,
+                }),
+                USDvEvent::AllowlistUpdated(events::AllowlistUpdated {
+                    admin,
+                    added: 17,
+                    removed: 18,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_events_from_other_programs_are_ignored() {
+        let forged = event_log(&usdv_program::events::Deposited {
+            user: Pubkey::new_unique(),
+            amount: 1_000_000,
+            total_usdv_supply: 1_000_000,
+        });
+        let impostor = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {} invoke [1]", impostor),
+            forged,
+            "Program data: bm90IGFuIGV2ZW50".to_string(),
+            format!("Program {} success", impostor),
+        ];
+
+        assert!(decode_events(&parse_logs(&logs), &usdv_program::ID).is_empty());
+        assert_eq!(USDvEvent::decode(&[1, 2, 3]), None);
+    }
+
+    #[test]
+    fn test_transaction_info_from_metadata() {
+        let user_usdc = Pubkey::new_unique();
+        let user_usdv = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let usdc_mint = Pubkey::new_unique();
+        let usdv_mint = Pubkey::new_unique();
+        let accounts = [Pubkey::new_unique(), user_usdc, user_usdv, vault];
+        let signature = Signature::from([7; 64]);
+
+        let transaction = landed_transaction(
+            &accounts,
+            vec![token_balance(1, &usdc_mint, 500), token_balance(3, &usdc_mint, 1_000)],
+            vec![
+                token_balance(1, &usdc_mint, 400),
+                token_balance(2, &usdv_mint, 100),
+                token_balance(3, &usdc_mint, 1_100),
+            ],
+            program_logs(&["Program log: Deposited".to_string()]),
+            None,
+        );
+
+        let info = transaction_info(
+            &signature,
+            &transaction,
+            &usdv_program::ID,
+            CommitmentConfig::confirmed(),
+        );
+        assert_eq!(info.signature, signature.to_string());
+        assert_eq!(info.status, TransactionStatus::Confirmed);
+        assert_eq!(info.slot, Some(42));
+        assert_eq!(info.confirmation_time, Some(1_700_000_000));
+        assert_eq!(info.fee, Some(5_000));
+        assert!(info.events.is_empty());
+        assert_eq!(info.token_balances.len(), 3);
+
+        let usdc = info.balance_change(&user_usdc).unwrap();
+        assert_eq!((usdc.pre, usdc.post, usdc.delta()), (Some(500), Some(400), -100));
+        let usdv = info.balance_change(&user_usdv).unwrap();
+        assert_eq!((usdv.pre, usdv.post, usdv.delta()), (None, Some(100), 100));
+        assert_eq!(info.balance_change(&vault).unwrap().delta(), 100);
+
+        let failed = landed_transaction(
+            &accounts,
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Some(TransactionError::InsufficientFundsForFee),
+        );
+        let info = transaction_info(
+            &signature,
+            &failed,
+            &usdv_program::ID,
+            CommitmentConfig::finalized(),
+        );
+        assert!(matches!(info.status, TransactionStatus::Failed(_)));
+        assert!(info.token_balances.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deposit_and_mint_returns_result() {
+        let user = Keypair::new();
+        let mut mocks = MocksMap::default();
+        let config = test_config();
+        let user_usdc = derive_associated_token_account(&user.pubkey(), &config.usdc_mint);
+        let user_usdv = derive_associated_token_account(&user.pubkey(), &config.usdv_mint);
+
+        let transaction = landed_transaction(
+            &[user.pubkey(), user_usdc, user_usdv],
+            vec![token_balance(1, &config.usdc_mint, 3_000_000)],
+            vec![
+                token_balance(1, &config.usdc_mint, 1_000_000),
+                token_balance(2, &config.usdv_mint, 2_000_000),
+            ],
+            program_logs(&[event_log(&usdv_program::events::Deposited {
+                user: user.pubkey(),
+                amount: 2_000_000,
+                total_usdv_supply: 9_000_000,
+            })]),
+            None,
+        );
+        mocks.insert(
+            RpcRequest::GetTransaction,
+            serde_json::to_value(&transaction).unwrap(),
+        );
+        let client = mock_client(mocks);
+
+        let result = client.deposit_and_mint(&user, 2_000_000).await.unwrap();
+        assert_eq!(result.usdc_deposited, 2_000_000);
+        assert_eq!(result.usdv_minted, 2_000_000);
+        assert_eq!(result.new_usdc_balance, Some(1_000_000));
+        assert_eq!(result.new_usdv_balance, Some(2_000_000));
+        assert_eq!(result.transaction.fee, Some(5_000));
+        assert_eq!(result.transaction.slot, Some(42));
+        assert!(matches!(
+            result.transaction.events.as_slice(),
+            [USDvEvent::Deposited(events::Deposited {
+                total_usdv_supply: 9_000_000,
+                ..
+            })]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_deposit_without_metadata_still_succeeds() {
+        // The default mock transaction carries no token balances or logs
+        let client = mock_client(MocksMap::default());
+        let user = Keypair::new();
+
+        let result = client.deposit_and_mint(&user, 2_000_000).await.unwrap();
+        assert_eq!(result.usdc_deposited, 2_000_000);
+        assert_eq!(result.usdv_minted, 2_000_000);
+        assert_eq!(result.new_usdc_balance, None);
+        assert_eq!(result.new_usdv_balance, None);
+        assert_eq!(result.transaction.status, TransactionStatus::Confirmed);
+        assert!(result.transaction.slot.is_some());
+    }
+}
@@ -0,0 +1,157 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::mock_client;
+    use solana_rpc_client::mock_sender::MocksMap;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signature},
+        signer::{presigner::Presigner, Signer, SignerError},
+    };
+    use std::sync::{Arc, Mutex};
+    use usdv_client::signer::{RemoteSigner, RemoteSignerAdapter};
+    use usdv_results::USDvClientError;
+
+    /// Stands in for an HTTP signing service, recording every message it signs
+    struct MockRemoteSigner {
+        keypair: Keypair,
+        signed: Mutex<Vec<Vec<u8>>>,
+    }
+
+    impl MockRemoteSigner {
+        fn new() -> Self {
+            Self {
+                keypair: Keypair::new(),
+                signed: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl RemoteSigner for MockRemoteSigner {
+        fn pubkey(&self) -> Result<Pubkey, SignerError> {
+            Ok(self.keypair.pubkey())
+        }
+
+        fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+            self.signed.lock().unwrap().push(message.to_vec());
+            Ok(self.keypair.sign_message(message))
+        }
+    }
+
+    /// Claims one key but signs with another
+    struct MisconfiguredRemoteSigner;
+
+    impl RemoteSigner for MisconfiguredRemoteSigner {
+        fn pubkey(&self) -> Result<Pubkey, SignerError> {
+            Ok(Pubkey::new_unique())
+        }
+
+        fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+            Ok(Keypair::new().sign_message(message))
+        }
+    }
+
+    /// Service that cannot be reached
+    struct UnreachableRemoteSigner;
+
+    impl RemoteSigner for UnreachableRemoteSigner {
+        fn pubkey(&self) -> Result<Pubkey, SignerError> {
+            Err(SignerError::Connection("connection refused".to_string()))
+        }
+
+        fn sign_message(&self, _message: &[u8]) -> Result<Signature, SignerError> {
+            Err(SignerError::Connection("connection refused".to_string()))
+        }
+    }
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[test]
+    fn test_remote_signer_adapter_signs() {
+        let adapter = RemoteSignerAdapter::new(MockRemoteSigner::new()).unwrap();
+        let message = b"message";
+
+        let signature = adapter.try_sign_message(message).unwrap();
+        assert!(signature.verify(adapter.pubkey().as_ref(), message));
+        assert_eq!(adapter.remote().signed.lock().unwrap().len(), 1);
+        assert!(!adapter.is_interactive());
+    }
+
+    #[test]
+    fn test_remote_signer_adapter_rejects_wrong_key() {
+        let adapter = RemoteSignerAdapter::new(MisconfiguredRemoteSigner).unwrap();
+        assert!(matches!(
+            adapter.try_sign_message(b"message"),
+            Err(SignerError::Custom(_))
+        ));
+    }
+
+    #[test]
+    fn test_remote_signer_adapter_surfaces_connection_errors() {
+        assert!(matches!(
+            RemoteSignerAdapter::new(UnreachableRemoteSigner),
+            Err(SignerError::Connection(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_separate_fee_payer_pays() {
+        let fee_payer = Arc::new(RemoteSignerAdapter::new(MockRemoteSigner::new()).unwrap());
+        let fee_payer_pubkey = fee_payer.pubkey();
+        let client = mock_client(MocksMap::default()).with_fee_payer(fee_payer.clone());
+        let admin = Keypair::new();
+        let admin: &dyn Signer = &admin;
+
+        let signature = client.update_program_state(admin, None).await.unwrap();
+
+        // The mock RPC returns the first signature, which belongs to the fee payer
+        let signed = fee_payer.remote().signed.lock().unwrap();
+        assert_eq!(signed.len(), 1);
+        assert!(signature.verify(fee_payer_pubkey.as_ref(), &signed[0]));
+        // Both the fee payer and the admin signed
+        assert_eq!(signed[0][0], 2);
+    }
+
+    #[tokio::test]
+    async fn test_authority_pays_without_fee_payer() {
+        let client = mock_client(MocksMap::default());
+        let admin = Keypair::new();
+
+        let signature = client.update_program_state(&admin, None).await.unwrap();
+        assert_ne!(signature, Signature::default());
+    }
+
+    #[tokio::test]
+    async fn test_fee_payer_that_is_also_the_authority_signs_once() {
+        let fee_payer = Arc::new(RemoteSignerAdapter::new(MockRemoteSigner::new()).unwrap());
+        let client = mock_client(MocksMap::default()).with_fee_payer(fee_payer.clone());
+
+        client.update_program_state(fee_payer.as_ref(), None).await.unwrap();
+
+        let signed = fee_payer.remote().signed.lock().unwrap();
+        assert_eq!(signed.len(), 1);
+        assert_eq!(signed[0][0], 1);
+    }
+
+    #[tokio::test]
+    async fn test_presigner_without_matching_signature_fails_to_sign() {
+        let client = mock_client(MocksMap::default());
+        let admin = Keypair::new();
+        let presigner = Presigner::new(&admin.pubkey(), &Signature::default());
+
+        let error = client.update_program_state(&presigner, None).await.unwrap_err();
+        assert!(matches!(error, USDvClientError::SigningError(_)));
+    }
+
+    #[test]
+    fn test_futures_with_sync_signers_are_send() {
+        let client = mock_client(MocksMap::default());
+        let keypair = Keypair::new();
+        let remote = RemoteSignerAdapter::new(MockRemoteSigner::new()).unwrap();
+
+        assert_send(&client.deposit_and_mint(&keypair, 1));
+        assert_send(&client.update_program_state(&remote, None));
+    }
+}
@@ -0,0 +1,223 @@
+#[cfg(test)]
+mod tests {
+
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde_json::json;
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use solana_client::rpc_client::Mocks;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+        transaction::TransactionError,
+    };
+    use usdv_client::logs::{parse_logs, LogEntry};
+    use usdv_client::simulation::{decode_program_error, simulate};
+    use usdv_results::USDvError;
+    use usdv_utils::constants::TOKEN_PROGRAM_ID;
+
+    const PROGRAM_LOGS: [&str; 9] = [
+        "Program 11111111111111111111111111111111 invoke [1]",
+        "Program log: Instruction: DepositAndMint",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+        "Program log: Instruction: Transfer",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA consumed 4645 of 180000 compute units",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+        "Program data: AQID BAUG",
+        "Program 11111111111111111111111111111111 failed: custom program error: 0x1773",
+        "Log truncated",
+    ];
+
+    fn logs() -> Vec<String> {
+        PROGRAM_LOGS.iter().map(|line| line.to_string()).collect()
+    }
+
+    /// JSON for an SPL token account holding `amount`
+    fn token_account(amount: u64) -> serde_json::Value {
+        let mut data = vec![0u8; 165];
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        json!({
+            "lamports": 2_039_280,
+            "data": [STANDARD.encode(data), "base64"],
+            "owner": TOKEN_PROGRAM_ID.to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+            "space": 165,
+        })
+    }
+
+    #[test]
+    fn test_parse_logs_tracks_invocation_stack() {
+        let system = solana_sdk::system_program::ID;
+        let token = TOKEN_PROGRAM_ID;
+        let entries = parse_logs(&logs());
+
+        assert_eq!(entries[0], LogEntry::Invoke { program_id: system, depth: 1 });
+        assert_eq!(
+            entries[1],
+            LogEntry::Log {
+                program_id: Some(system),
+                message: "Instruction: DepositAndMint".to_string(),
+            }
+        );
+        assert_eq!(entries[3].program_id(), Some(&token));
+        assert_eq!(
+            entries[4],
+            LogEntry::ComputeUnits { program_id: token, consumed: 4645, limit: 180_000 }
+        );
+        assert_eq!(entries[5], LogEntry::Success { program_id: token });
+        assert_eq!(
+            entries[6],
+            LogEntry::Data {
+                program_id: Some(system),
+                data: vec!["AQID".to_string(), "BAUG".to_string()],
+            }
+        );
+        assert_eq!(
+            entries[7],
+            LogEntry::Failure {
+                program_id: system,
+                error: "custom program error: 0x1773".to_string(),
+            }
+        );
+        assert_eq!(entries[8], LogEntry::Other("Log truncated".to_string()));
+    }
+
+    #[test]
+    fn test_error_codes_round_trip() {
+        for (index, error) in USDvError::ALL.iter().enumerate() {
+            assert_eq!(*error as usize, index);
+            let code = u32::from(*error);
+            assert_eq!(code, 6000 + index as u32);
+            assert_eq!(USDvError::from_code(code).map(u32::from), Some(code));
+        }
+        assert!(USDvError::from_code(5999).is_none());
+        assert!(USDvError::from_code(6000 + USDvError::ALL.len() as u32).is_none());
+    }
+
+    #[test]
+    fn test_decode_program_error() {
+        let program_id = Pubkey::new_unique();
+        let instructions = [
+            Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]),
+            Instruction::new_with_bytes(program_id, &[], vec![]),
+        ];
+        let code = u32::from(USDvError::SupplyLimitExceeded);
+
+        let error = TransactionError::InstructionError(1, InstructionError::Custom(code));
+        assert!(matches!(
+            decode_program_error(&error, &instructions, &program_id),
+            Some(USDvError::SupplyLimitExceeded)
+        ));
+
+        // Another program's custom error
+        let error = TransactionError::InstructionError(0, InstructionError::Custom(code));
+        assert!(decode_program_error(&error, &instructions, &program_id).is_none());
+
+        // A token program error surfacing through the USDv instruction
+        let error = TransactionError::InstructionError(1, InstructionError::Custom(1));
+        assert!(decode_program_error(&error, &instructions, &program_id).is_none());
+
+        assert!(decode_program_error(&TransactionError::AccountInUse, &instructions, &program_id)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_reports_balances_and_error() {
+        let program_id = Pubkey::new_unique();
+        let user_usdc = Pubkey::new_unique();
+        let vault_usdc = Pubkey::new_unique();
+        let user_usdv = Pubkey::new_unique();
+        let readonly = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bytes(
+            program_id,
+            &[],
+            vec![
+                AccountMeta::new(user_usdc, false),
+                AccountMeta::new(vault_usdc, false),
+                AccountMeta::new(user_usdv, false),
+                AccountMeta::new_readonly(readonly, false),
+            ],
+        );
+        let code = u32::from(USDvError::InsufficientUSDCBalance);
+
+        let mut mocks = Mocks::default();
+        mocks.insert(
+            RpcRequest::GetMultipleAccounts,
+            json!({
+                "context": { "slot": 1 },
+                "value": [token_account(500), token_account(1_000), null],
+            }),
+        );
+        mocks.insert(
+            RpcRequest::SimulateTransaction,
+            json!({
+                "context": { "slot": 1 },
+                "value": {
+                    "err": { "InstructionError": [0, { "Custom": code }] },
+                    "logs": logs(),
+                    "accounts": [token_account(400), token_account(1_100), token_account(100)],
+                    "unitsConsumed": 21_000,
+                },
+            }),
+        );
+        let rpc_client = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+
+        let result = simulate(
+            &rpc_client,
+            &program_id,
+            &[instruction],
+            &Pubkey::new_unique(),
+            CommitmentConfig::confirmed(),
+        )
+        .await
+        .unwrap();
+
+        assert!(!result.succeeded());
+        assert!(matches!(result.program_error, Some(USDvError::InsufficientUSDCBalance)));
+        assert_eq!(result.units_consumed, Some(21_000));
+        assert_eq!(result.logs.len(), PROGRAM_LOGS.len());
+        assert_eq!(result.parsed_logs.len(), PROGRAM_LOGS.len());
+
+        assert_eq!(result.token_balances.len(), 3);
+        let usdc = result.balance_change(&user_usdc).unwrap();
+        assert_eq!((usdc.pre, usdc.post, usdc.delta()), (Some(500), Some(400), -100));
+        let vault = result.balance_change(&vault_usdc).unwrap();
+        assert_eq!((vault.pre, vault.post, vault.delta()), (Some(1_000), Some(1_100), 100));
+        let usdv = result.balance_change(&user_usdv).unwrap();
+        assert_eq!((usdv.pre, usdv.post, usdv.delta()), (None, Some(100), 100));
+        assert!(result.balance_change(&readonly).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_success_without_accounts() {
+        // The default mock simulation succeeds with no logs or accounts
+        let mut mocks = Mocks::default();
+        mocks.insert(
+            RpcRequest::GetMultipleAccounts,
+            json!({ "context": { "slot": 1 }, "value": [null] }),
+        );
+        let rpc_client = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![AccountMeta::new(Pubkey::new_unique(), false)],
+        );
+
+        let result = simulate(
+            &rpc_client,
+            &instruction.program_id,
+            std::slice::from_ref(&instruction),
+            &Pubkey::new_unique(),
+            CommitmentConfig::confirmed(),
+        )
+        .await
+        .unwrap();
+
+        assert!(result.succeeded());
+        assert!(result.program_error.is_none());
+        assert!(result.logs.is_empty());
+        assert!(result.token_balances.is_empty());
+    }
+}
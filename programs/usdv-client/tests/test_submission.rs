@@ -0,0 +1,235 @@
+#[cfg(test)]
+mod tests {
+
+    use serde_json::json;
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use solana_client::rpc_client::Mocks;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_sdk::{
+        commitment_config::CommitmentConfig,
+        hash::Hash,
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    };
+    use std::time::Duration;
+    use usdv_client::compute_budget::{ComputeUnitLimit, PriorityFeeStrategy};
+    use usdv_client::submission::{
+        backoff_delay, NotLandedReason, SubmissionConfig, SubmissionEngine, SubmissionOutcome,
+        DEFAULT_POLL_INTERVAL, MAX_BACKOFF,
+    };
+    use usdv_client::types::ClientConfig;
+    use usdv_results::{Retryability, USDvClientError};
+
+    /// Above the mock's default `last_valid_block_height` of 1234
+    const EXPIRED_BLOCK_HEIGHT: u64 = 2000;
+
+    fn test_config(max_retries: u32) -> SubmissionConfig {
+        SubmissionConfig {
+            max_retries,
+            retry_delay: Duration::from_millis(1),
+            timeout: Duration::from_millis(200),
+            skip_preflight: true,
+            commitment: CommitmentConfig::confirmed(),
+            poll_interval: Duration::from_millis(10),
+            priority_fee: PriorityFeeStrategy::None,
+            compute_unit_limit: ComputeUnitLimit::Default,
+        }
+    }
+
+    fn test_instruction() -> Instruction {
+        Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![])
+    }
+
+    async fn submit(rpc_client: &RpcClient, config: SubmissionConfig) -> SubmissionOutcome {
+        let payer = Keypair::new();
+        SubmissionEngine::new(rpc_client, config)
+            .submit(&[test_instruction()], &payer.pubkey(), &[&payer])
+            .await
+            .unwrap()
+    }
+
+    fn latest_blockhash(blockhash: Hash, last_valid_block_height: u64) -> serde_json::Value {
+        json!({
+            "context": { "slot": 1 },
+            "value": {
+                "blockhash": blockhash.to_string(),
+                "lastValidBlockHeight": last_valid_block_height,
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_landed_and_succeeded() {
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+
+        match submit(&rpc_client, test_config(3)).await {
+            SubmissionOutcome::Confirmed { slot, .. } => assert_eq!(slot, 1),
+            other => panic!("expected Confirmed, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_landed_and_failed() {
+        let rpc_client = RpcClient::new_mock("instruction_error".to_string());
+
+        let instruction = test_instruction();
+        let payer = Keypair::new();
+        let outcome = SubmissionEngine::new(&rpc_client, test_config(3))
+            .submit(std::slice::from_ref(&instruction), &payer.pubkey(), &[&payer])
+            .await
+            .unwrap();
+        match &outcome {
+            SubmissionOutcome::Failed {
+                error,
+                slot,
+                failed_program,
+                ..
+            } => {
+                assert_eq!(*slot, 1);
+                assert_eq!(
+                    *error,
+                    TransactionError::InstructionError(0, InstructionError::UninitializedAccount)
+                );
+                assert_eq!(*failed_program, Some(instruction.program_id));
+            }
+            other => panic!("expected Failed, got {:?}", other),
+        }
+        assert!(matches!(
+            outcome.into_result(&instruction.program_id),
+            Err(USDvClientError::TransactionFailed { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_never_landed_times_out() {
+        let rpc_client = RpcClient::new_mock("sig_not_found".to_string());
+
+        let outcome = submit(&rpc_client, test_config(3)).await;
+        match &outcome {
+            SubmissionOutcome::NotLanded { signatures, reason } => {
+                assert_eq!(signatures.len(), 1);
+                assert_eq!(*reason, NotLandedReason::TimedOut);
+            }
+            other => panic!("expected NotLanded, got {:?}", other),
+        }
+        assert!(matches!(
+            outcome.into_result(&Pubkey::new_unique()),
+            Err(USDvClientError::TransactionNotLanded { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_expired_blockhash_exhausts_retries() {
+        let mut mocks = Mocks::default();
+        mocks.insert(RpcRequest::GetBlockHeight, json!(EXPIRED_BLOCK_HEIGHT));
+        let rpc_client = RpcClient::new_mock_with_mocks("sig_not_found".to_string(), mocks);
+
+        match submit(&rpc_client, test_config(0)).await {
+            SubmissionOutcome::NotLanded { reason, .. } => {
+                assert_eq!(reason, NotLandedReason::RetriesExhausted)
+            }
+            other => panic!("expected NotLanded, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expired_blockhash_is_re_signed() {
+        let mut mocks = Mocks::default();
+        mocks.insert(
+            RpcRequest::GetLatestBlockhash,
+            latest_blockhash(Hash::new_unique(), 1000),
+        );
+        mocks.insert(RpcRequest::GetBlockHeight, json!(EXPIRED_BLOCK_HEIGHT));
+        let rpc_client = RpcClient::new_mock_with_mocks("sig_not_found".to_string(), mocks);
+
+        match submit(&rpc_client, test_config(1)).await {
+            SubmissionOutcome::NotLanded { signatures, .. } => {
+                assert_eq!(signatures.len(), 2);
+                assert_ne!(signatures[0], signatures[1]);
+            }
+            other => panic!("expected NotLanded, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resend_with_same_blockhash_is_deduplicated() {
+        let mut mocks = Mocks::default();
+        mocks.insert(RpcRequest::GetBlockHeight, json!(EXPIRED_BLOCK_HEIGHT));
+        let rpc_client = RpcClient::new_mock_with_mocks("sig_not_found".to_string(), mocks);
+
+        match submit(&rpc_client, test_config(1)).await {
+            SubmissionOutcome::NotLanded { signatures, .. } => assert_eq!(signatures.len(), 1),
+            other => panic!("expected NotLanded, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fatal_rpc_error_is_returned() {
+        // Every call returns null, which cannot be parsed and will not change
+        let rpc_client = RpcClient::new_mock("fails".to_string());
+        let payer = Keypair::new();
+
+        let error = SubmissionEngine::new(&rpc_client, test_config(3))
+            .submit(&[test_instruction()], &payer.pubkey(), &[&payer])
+            .await
+            .unwrap_err();
+        assert!(matches!(error, USDvClientError::SolanaClientError(_)));
+        assert_eq!(error.retryability(), Retryability::Fatal);
+    }
+
+    #[tokio::test]
+    async fn test_signing_error_is_returned() {
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+        let payer = Keypair::new();
+        let missing_signer = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![solana_sdk::instruction::AccountMeta::new_readonly(missing_signer, true)],
+        );
+
+        let result = SubmissionEngine::new(&rpc_client, test_config(0))
+            .submit(&[instruction], &payer.pubkey(), &[&payer])
+            .await;
+        assert!(matches!(result, Err(USDvClientError::SigningError(_))));
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        let base = Duration::from_millis(100);
+
+        // Equal jitter: between half and all of the exponential delay
+        assert_eq!(backoff_delay(base, 0, 0.0), Duration::from_millis(50));
+        assert_eq!(backoff_delay(base, 0, 1.0), Duration::from_millis(100));
+        assert_eq!(backoff_delay(base, 3, 0.0), Duration::from_millis(400));
+        assert_eq!(backoff_delay(base, 3, 1.0), Duration::from_millis(800));
+
+        // Capped, including for huge attempt counts
+        assert_eq!(backoff_delay(base, 20, 1.0), MAX_BACKOFF);
+        assert_eq!(backoff_delay(base, u32::MAX, 1.0), MAX_BACKOFF);
+
+        // Out-of-range jitter is clamped
+        assert_eq!(backoff_delay(base, 0, 7.0), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_submission_config_from_client_config() {
+        let config = SubmissionConfig::from(&ClientConfig::default());
+        assert_eq!(config.max_retries, 3);
+        assert_eq!(config.retry_delay, Duration::from_secs(1));
+        assert_eq!(config.timeout, Duration::from_secs(30));
+        assert!(!config.skip_preflight);
+        assert_eq!(config.commitment, CommitmentConfig::confirmed());
+        assert_eq!(config.poll_interval, DEFAULT_POLL_INTERVAL);
+        assert_eq!(config.priority_fee, PriorityFeeStrategy::default());
+        assert_eq!(config.compute_unit_limit, ComputeUnitLimit::default());
+
+        let config = SubmissionConfig::from(&ClientConfig {
+            use_confirmed_commitment: false,
+            ..ClientConfig::default()
+        });
+        assert_eq!(config.commitment, CommitmentConfig::processed());
+    }
+}
@@ -0,0 +1,282 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::mock_client;
+    use anchor_lang::Event;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use futures::{SinkExt, StreamExt};
+    use serde_json::{json, Value};
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+    use solana_rpc_client::mock_sender::MocksMap;
+    use solana_sdk::{pubkey::Pubkey, signature::Signature};
+    use solana_transaction_status_client_types::{
+        option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+        EncodedTransaction, EncodedTransactionWithStatusMeta, TransactionBinaryEncoding,
+        UiTransactionStatusMeta,
+    };
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::Message;
+    use usdv_client::events::USDvEvent;
+    use usdv_client::subscription::{websocket_url, EventSource, SubscriptionConfig};
+    use usdv_client::{USDvClient, USDvConfig};
+
+    fn event_logs<E: Event>(event: &E) -> Vec<String> {
+        vec![
+            format!("Program {} invoke [1]", usdv_program::ID),
+            format!("Program data: {}", STANDARD.encode(event.data())),
+            format!("Program {} success", usdv_program::ID),
+        ]
+    }
+
+    fn deposited(amount: u64) -> Vec<String> {
+        event_logs(&usdv_program::events::Deposited {
+            user: Pubkey::new_unique(),
+            amount,
+            total_usdv_supply: amount,
+        })
+    }
+
+    fn notification(signature: &Signature, slot: u64, err: Value, logs: Vec<String>) -> Message {
+        Message::Text(
+            json!({
+                "jsonrpc": "2.0",
+                "method": "logsNotification",
+                "params": {
+                    "result": {
+                        "context": { "slot": slot },
+                        "value": { "signature": signature.to_string(), "err": err, "logs": logs },
+                    },
+                    "subscription": 7,
+                },
+            })
+            .to_string(),
+        )
+    }
+
+    /// One pubsub connection: acknowledge `logsSubscribe`, push `messages`, then close
+    async fn serve_connection(listener: &TcpListener, messages: Vec<Message>) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+        let request = loop {
+            if let Message::Text(text) = ws.next().await.unwrap().unwrap() {
+                break serde_json::from_str::<Value>(&text).unwrap();
+            }
+        };
+        assert_eq!(request["method"], "logsSubscribe");
+        assert_eq!(request["params"][0]["mentions"][0], usdv_program::ID.to_string());
+        ws.send(Message::Text(
+            json!({ "jsonrpc": "2.0", "result": 7, "id": request["id"] }).to_string(),
+        ))
+        .await
+        .unwrap();
+
+        for message in messages {
+            ws.send(message).await.unwrap();
+        }
+        ws.close(None).await.ok();
+    }
+
+    /// A `getSignaturesForAddress` page, newest first
+    fn statuses(signatures: &[(Signature, u64)]) -> Value {
+        let page: Vec<RpcConfirmedTransactionStatusWithSignature> = signatures
+            .iter()
+            .map(|(signature, slot)| RpcConfirmedTransactionStatusWithSignature {
+                signature: signature.to_string(),
+                slot: *slot,
+                err: None,
+                memo: None,
+                block_time: None,
+                confirmation_status: None,
+            })
+            .collect();
+        serde_json::to_value(page).unwrap()
+    }
+
+    fn landed(slot: u64, logs: Vec<String>) -> Value {
+        serde_json::to_value(EncodedConfirmedTransactionWithStatusMeta {
+            slot,
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: EncodedTransaction::Binary(String::new(), TransactionBinaryEncoding::Base64),
+                meta: Some(UiTransactionStatusMeta {
+                    err: None,
+                    status: Ok(()),
+                    fee: 5_000,
+                    pre_balances: Vec::new(),
+                    post_balances: Vec::new(),
+                    inner_instructions: OptionSerializer::None,
+                    log_messages: OptionSerializer::Some(logs),
+                    pre_token_balances: OptionSerializer::None,
+                    post_token_balances: OptionSerializer::None,
+                    rewards: OptionSerializer::None,
+                    loaded_addresses: OptionSerializer::Skip,
+                    return_data: OptionSerializer::Skip,
+                    compute_units_consumed: OptionSerializer::Skip,
+                    cost_units: OptionSerializer::Skip,
+                }),
+                version: None,
+            },
+            block_time: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_websocket_url() {
+        assert_eq!(websocket_url("http://127.0.0.1:8899"), "ws://127.0.0.1:8900");
+        assert_eq!(websocket_url("https://api.devnet.solana.com"), "wss://api.devnet.solana.com");
+        assert_eq!(
+            websocket_url("https://rpc.example.com:443/token"),
+            "wss://rpc.example.com:444/token"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_events_stream_reconnects_and_backfills() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let first = Signature::from([1; 64]);
+        let failed = Signature::from([2; 64]);
+        let missed = Signature::from([3; 64]);
+        let after = Signature::from([4; 64]);
+
+        // Newest signature at subscribe time, nothing landed while
+        // subscribing, then the transaction missed while down
+        let mut mocks = MocksMap::default();
+        mocks.insert(RpcRequest::GetSignaturesForAddress, statuses(&[(Signature::from([9; 64]), 9)]));
+        mocks.insert(RpcRequest::GetSignaturesForAddress, statuses(&[]));
+        mocks.insert(RpcRequest::GetSignaturesForAddress, statuses(&[(missed, 11)]));
+        mocks.insert(
+            RpcRequest::GetTransaction,
+            landed(
+                11,
+                event_logs(&usdv_program::events::Burned {
+                    user: Pubkey::new_unique(),
+                    amount: 2,
+                    total_usdv_supply: 0,
+                }),
+            ),
+        );
+
+        let server = tokio::spawn(async move {
+            serve_connection(
+                &listener,
+                vec![
+                    notification(&first, 10, Value::Null, deposited(1)),
+                    notification(&failed, 10, json!({ "InstructionError": [0, { "Custom": 6000 }] }), deposited(9)),
+                ],
+            )
+            .await;
+            // The missed transaction also arrives live after the reconnect
+            serve_connection(
+                &listener,
+                vec![
+                    notification(&missed, 11, Value::Null, Vec::new()),
+                    notification(&after, 12, Value::Null, deposited(3)),
+                ],
+            )
+            .await;
+        });
+
+        let mut config = SubscriptionConfig::new(format!("ws://{}", address));
+        config.reconnect_delay = Duration::from_millis(10);
+        let mut stream = mock_client(mocks).subscribe_events_with_config(config).await.unwrap();
+
+        let mut received = Vec::new();
+        for _ in 0..3 {
+            let notification = tokio::time::timeout(Duration::from_secs(10), stream.next())
+                .await
+                .unwrap()
+                .unwrap();
+            received.push(notification);
+        }
+        server.await.unwrap();
+
+        let summary: Vec<(Signature, u64, EventSource)> = received
+            .iter()
+            .map(|notification| (notification.signature, notification.slot, notification.source))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (first, 10, EventSource::Live),
+                (missed, 11, EventSource::Backfill),
+                (after, 12, EventSource::Live),
+            ]
+        );
+        assert!(matches!(received[0].events.as_slice(), [USDvEvent::Deposited(event)] if event.amount == 1));
+        assert!(matches!(received[1].events.as_slice(), [USDvEvent::Burned(event)] if event.amount == 2));
+        assert!(matches!(received[2].events.as_slice(), [USDvEvent::Deposited(event)] if event.amount == 3));
+    }
+
+    #[tokio::test]
+    async fn test_backfill_before_the_first_event() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let missed = Signature::from([3; 64]);
+        let after = Signature::from([4; 64]);
+
+        // The first connection drops before delivering anything; the replay
+        // still reaches back to the newest signature at subscribe time
+        let mut mocks = MocksMap::default();
+        mocks.insert(RpcRequest::GetSignaturesForAddress, statuses(&[(Signature::from([9; 64]), 9)]));
+        mocks.insert(RpcRequest::GetSignaturesForAddress, statuses(&[]));
+        mocks.insert(RpcRequest::GetSignaturesForAddress, statuses(&[(missed, 11)]));
+        mocks.insert(RpcRequest::GetTransaction, landed(11, deposited(2)));
+
+        let server = tokio::spawn(async move {
+            serve_connection(&listener, Vec::new()).await;
+            serve_connection(&listener, vec![notification(&after, 12, Value::Null, deposited(3))]).await;
+        });
+
+        let mut config = SubscriptionConfig::new(format!("ws://{}", address));
+        config.reconnect_delay = Duration::from_millis(10);
+        let mut stream = mock_client(mocks).subscribe_events_with_config(config).await.unwrap();
+
+        let mut received = Vec::new();
+        for _ in 0..2 {
+            let notification = tokio::time::timeout(Duration::from_secs(10), stream.next())
+                .await
+                .unwrap()
+                .unwrap();
+            received.push((notification.signature, notification.source));
+        }
+        server.await.unwrap();
+
+        assert_eq!(received, vec![(missed, EventSource::Backfill), (after, EventSource::Live)]);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint_is_reported() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let result = mock_client(MocksMap::default())
+            .subscribe_events_with_config(SubscriptionConfig::new(format!("ws://{}", address)))
+            .await;
+        assert!(matches!(result, Err(usdv_results::USDvClientError::PubsubError(_))));
+    }
+
+    /// Needs `solana-test-validator` running with its default ports:
+    /// `cargo test -p usdv_client --test test_subscription -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_subscribe_against_local_validator() {
+        let client = USDvClient::new(
+            RpcClient::new("http://127.0.0.1:8899".to_string()),
+            USDvConfig::localnet(),
+        );
+        let mut stream = client.subscribe_events().await.unwrap();
+
+        // Nothing touches the program, so the stream stays open and quiet
+        assert!(tokio::time::timeout(Duration::from_secs(2), stream.next()).await.is_err());
+    }
+}
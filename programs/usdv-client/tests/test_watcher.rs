@@ -0,0 +1,244 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::mock_client;
+    use anchor_lang::Discriminator;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use futures::{SinkExt, StreamExt};
+    use serde_json::{json, Value};
+    use solana_client::rpc_request::RpcRequest;
+    use solana_rpc_client::mock_sender::MocksMap;
+    use solana_sdk::pubkey::Pubkey;
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::Message;
+    use usdv_client::types::ProgramStateInfo;
+    use usdv_client::watcher::{
+        diff_program_state, ChangeSource, FieldValue, StateChange, StateChangeStream, StateField,
+        WatcherConfig,
+    };
+    use usdv_utils::constants::TOKEN_PROGRAM_ID;
+
+    fn program_state() -> ProgramStateInfo {
+        ProgramStateInfo {
+            admin: Pubkey::new_from_array([1; 32]),
+            usdc_mint: Pubkey::new_from_array([2; 32]),
+            usdv_mint: Pubkey::new_from_array([3; 32]),
+            vault_authority: Pubkey::new_from_array([4; 32]),
+            total_usdv_supply: 100,
+            total_usdc_deposits: 100,
+            total_offchain_deposits: 0,
+            wind_down_started_at: 0,
+            wind_down_deadline: 0,
+            is_initialized: true,
+            vault_bump: 255,
+            is_wound_down: false,
+            allowlist_enabled: false,
+            padding: [0; 4],
+        }
+    }
+
+    fn ui_account(owner: &Pubkey, data: Vec<u8>) -> Value {
+        json!({
+            "lamports": 1_000_000,
+            "data": [STANDARD.encode(&data), "base64"],
+            "owner": owner.to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+            "space": data.len(),
+        })
+    }
+
+    fn state_account(state: &ProgramStateInfo) -> Value {
+        let mut data = usdv_program::ProgramState::DISCRIMINATOR.to_vec();
+        data.extend(borsh::to_vec(state).unwrap());
+        ui_account(&usdv_program::ID, data)
+    }
+
+    fn vault_account(amount: u64) -> Value {
+        let mut data = vec![0; 165];
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        ui_account(&TOKEN_PROGRAM_ID, data)
+    }
+
+    fn accounts(slot: u64, state: &ProgramStateInfo, vault_balance: u64) -> Value {
+        json!({
+            "context": { "slot": slot },
+            "value": [state_account(state), vault_account(vault_balance)],
+        })
+    }
+
+    fn account_notification(subscription: u64, slot: u64, account: Value) -> Message {
+        Message::Text(
+            json!({
+                "jsonrpc": "2.0",
+                "method": "accountNotification",
+                "params": {
+                    "result": { "context": { "slot": slot }, "value": account },
+                    "subscription": subscription,
+                },
+            })
+            .to_string(),
+        )
+    }
+
+    async fn next_changes(stream: &mut StateChangeStream, count: usize) -> Vec<StateChange> {
+        let mut changes = Vec::new();
+        for _ in 0..count {
+            let change = tokio::time::timeout(Duration::from_secs(10), stream.next())
+                .await
+                .unwrap()
+                .unwrap();
+            changes.push(change);
+        }
+        changes
+    }
+
+    #[test]
+    fn test_diff_program_state() {
+        let old = program_state();
+        let new = ProgramStateInfo {
+            admin: Pubkey::new_from_array([9; 32]),
+            total_usdv_supply: 150,
+            wind_down_started_at: 1_700_000_000,
+            is_wound_down: true,
+            padding: [1; 4],
+            ..old.clone()
+        };
+
+        let changes = diff_program_state(&old, &new, 42, ChangeSource::Polling);
+        let fields: Vec<(StateField, FieldValue, FieldValue)> =
+            changes.iter().map(|change| (change.field, change.old, change.new)).collect();
+        assert_eq!(
+            fields,
+            vec![
+                (StateField::Admin, FieldValue::Pubkey(old.admin), FieldValue::Pubkey(new.admin)),
+                (StateField::TotalUsdvSupply, FieldValue::U64(100), FieldValue::U64(150)),
+                (StateField::WindDownStartedAt, FieldValue::I64(0), FieldValue::I64(1_700_000_000)),
+                (StateField::IsWoundDown, FieldValue::Bool(false), FieldValue::Bool(true)),
+            ]
+        );
+        assert!(changes.iter().all(|change| change.slot == 42));
+        assert!(diff_program_state(&old, &old, 42, ChangeSource::Polling).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_polls_when_websocket_is_unreachable() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let changed = ProgramStateInfo {
+            admin: Pubkey::new_from_array([9; 32]),
+            is_wound_down: true,
+            ..program_state()
+        };
+        let mut mocks = MocksMap::default();
+        mocks.insert(RpcRequest::GetMultipleAccounts, accounts(5, &program_state(), 100));
+        mocks.insert(RpcRequest::GetMultipleAccounts, accounts(6, &changed, 250));
+
+        let mut config = WatcherConfig::new(format!("ws://{}", address));
+        config.reconnect_delay = Duration::from_millis(10);
+        config.poll_interval = Duration::from_millis(10);
+        let mut stream = mock_client(mocks).watch_program_state_with_config(config).await.unwrap();
+
+        let changes = next_changes(&mut stream, 3).await;
+        let summary: Vec<(StateField, u64, ChangeSource)> = changes
+            .iter()
+            .map(|change| (change.field, change.slot, change.source))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (StateField::Admin, 6, ChangeSource::Polling),
+                (StateField::IsWoundDown, 6, ChangeSource::Polling),
+                (StateField::VaultBalance, 6, ChangeSource::Polling),
+            ]
+        );
+        assert_eq!((changes[2].old, changes[2].new), (FieldValue::U64(100), FieldValue::U64(250)));
+    }
+
+    #[tokio::test]
+    async fn test_account_subscriptions_produce_diffs() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        // Baseline, then the catch-up poll after subscribing finds nothing new
+        let mut mocks = MocksMap::default();
+        mocks.insert(RpcRequest::GetMultipleAccounts, accounts(5, &program_state(), 100));
+        mocks.insert(RpcRequest::GetMultipleAccounts, accounts(5, &program_state(), 100));
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+            for subscription in [1, 2] {
+                let request = loop {
+                    if let Message::Text(text) = ws.next().await.unwrap().unwrap() {
+                        break serde_json::from_str::<Value>(&text).unwrap();
+                    }
+                };
+                assert_eq!(request["method"], "accountSubscribe");
+                assert_eq!(request["params"][1]["encoding"], "base64");
+                ws.send(Message::Text(
+                    json!({ "jsonrpc": "2.0", "result": subscription, "id": request["id"] }).to_string(),
+                ))
+                .await
+                .unwrap();
+            }
+
+            let changed = ProgramStateInfo {
+                allowlist_enabled: true,
+                ..program_state()
+            };
+            for message in [
+                // Older than the baseline, dropped
+                account_notification(2, 3, vault_account(999)),
+                account_notification(1, 8, state_account(&changed)),
+                account_notification(2, 9, vault_account(300)),
+            ] {
+                ws.send(message).await.unwrap();
+            }
+            // Hold the connection until the watcher goes away
+            while let Some(Ok(_)) = ws.next().await {}
+        });
+
+        let config = WatcherConfig::new(format!("ws://{}", address));
+        let mut stream = mock_client(mocks).watch_program_state_with_config(config).await.unwrap();
+
+        let changes = next_changes(&mut stream, 2).await;
+        assert_eq!(
+            changes,
+            vec![
+                StateChange {
+                    field: StateField::AllowlistEnabled,
+                    old: FieldValue::Bool(false),
+                    new: FieldValue::Bool(true),
+                    slot: 8,
+                    source: ChangeSource::Subscription,
+                },
+                StateChange {
+                    field: StateField::VaultBalance,
+                    old: FieldValue::U64(100),
+                    new: FieldValue::U64(300),
+                    slot: 9,
+                    source: ChangeSource::Subscription,
+                },
+            ]
+        );
+
+        drop(stream);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_missing_program_state_is_reported() {
+        // The mock reports every account as missing
+        let result = mock_client(MocksMap::default())
+            .watch_program_state_with_config(WatcherConfig::polling())
+            .await;
+        assert!(matches!(result, Err(usdv_results::USDvClientError::SerializationError(_))));
+    }
+}
@@ -0,0 +1,143 @@
+//! Events emitted by the USDv stablecoin program
+
+use anchor_lang::prelude::*;
+
+/// Emitted when the admin puts the program into wind-down
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindDownInitiated {
+    /// Admin that triggered the wind-down
+    pub admin: Pubkey,
+    /// Unix timestamp at which wind-down started
+    pub started_at: i64,
+    /// Unix timestamp after which unclaimed USDc can be swept
+    pub deadline: i64,
+    /// USDv still in circulation when wind-down started
+    pub outstanding_usdv_supply: u64,
+}
+
+/// Emitted when unclaimed USDc is moved out of the vault after wind-down
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnclaimedCollateralSwept {
+    /// Admin that performed the sweep
+    pub admin: Pubkey,
+    /// Token account that received the unclaimed USDc
+    pub recovery_account: Pubkey,
+    /// Amount of USDc swept
+    pub amount: u64,
+    /// USDv still in circulation at sweep time
+    pub outstanding_usdv_supply: u64,
+    /// Unix timestamp of the sweep
+    pub timestamp: i64,
+}
+
+/// Emitted when non-collateral tokens are recovered from the vault authority
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokensRecovered {
+    /// Admin that performed the recovery
+    pub admin: Pubkey,
+    /// Mint of the recovered tokens
+    pub mint: Pubkey,
+    /// Token account owned by the vault authority the tokens came from
+    pub source: Pubkey,
+    /// Token account that received the tokens
+    pub destination: Pubkey,
+    /// Amount of tokens recovered
+    pub amount: u64,
+    /// Unix timestamp of the recovery
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets a minter's allowance
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub struct MinterConfigured {
+    /// Admin that configured the minter
+    pub admin: Pubkey,
+    /// Minter authority
+    pub minter: Pubkey,
+    /// New remaining allowance
+    pub allowance: u64,
+}
+
+/// Emitted when a minter mints USDv against off-chain collateral
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub struct OffchainMint {
+    /// Minter authority
+    pub minter: Pubkey,
+    /// Token account that received the USDv
+    pub destination: Pubkey,
+    /// Amount of USDv minted
+    pub amount: u64,
+    /// Allowance left after the mint
+    pub remaining_allowance: u64,
+}
+
+/// Emitted when a minter burns USDv for an off-chain redemption
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub struct OffchainRedemption {
+    /// Minter authority
+    pub minter: Pubkey,
+    /// Amount of USDv burned
+    pub amount: u64,
+}
+
+/// Emitted when the admin toggles allowlist-gated minting and redemption
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub struct AllowlistModeChanged {
+    /// Admin that changed the mode
+    pub admin: Pubkey,
+    /// Whether the allowlist is now enforced
+    pub enabled: bool,
+}
+
+/// Emitted once per `update_allowlist` batch
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub struct AllowlistUpdated {
+    /// Admin that applied the batch
+    pub admin: Pubkey,
+    /// Number of wallets added or whose expiry changed
+    pub added: u32,
+    /// Number of wallets removed
+    pub removed: u32,
+}
+
+/// Emitted when a user deposits USDc and mints USDv
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deposited {
+    /// User that deposited
+    pub user: Pubkey,
+    /// Amount of USDc deposited, equal to the USDv minted
+    pub amount: u64,
+    /// USDv in circulation after the deposit
+    pub total_usdv_supply: u64,
+}
+
+/// Emitted when a user burns USDv and withdraws USDc
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub struct Burned {
+    /// User that burned
+    pub user: Pubkey,
+    /// Amount of USDv burned, equal to the USDc withdrawn
+    pub amount: u64,
+    /// USDv in circulation after the burn
+    pub total_usdv_supply: u64,
+}
@@ -1,33 +1,38 @@
 //! Instruction implementations for USDv stablecoin program
 
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount};
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{get_associated_token_address, AssociatedToken},
     token::{self, Mint, Token, TokenAccount, Transfer, MintTo, Burn},
 };
 
 use usdv_results::USDvError;
-use crate::ProgramState;
+use usdv_utils::{
+    validate_burn_operation, validate_deposit_operation, validate_mint_supply_limit,
+    ValidationError,
+};
+use crate::events::{
+    AllowlistModeChanged, AllowlistUpdated, Burned, Deposited, MinterConfigured, OffchainMint,
+    OffchainRedemption, TokensRecovered, UnclaimedCollateralSwept, WindDownInitiated,
+};
+use crate::{AllowlistEntry, AllowlistUpdate, Minter, ProgramState};
 
 /// Initialize the USDv stablecoin program
 pub fn initialize(
     ctx: Context<Initialize>,
     usdc_mint_key: Pubkey,
 ) -> Result<()> {
-    let program_state = &mut ctx.accounts.program_state;
-    
-    // Ensure program hasn't been initialized yet
-    require!(!program_state.is_initialized, USDvError::AlreadyInitialized);
+    // `init` guarantees a fresh zeroed account, so counters, wind-down and
+    // allowlist fields already start at zero
+    let mut program_state = ctx.accounts.program_state.load_init()?;
     
-    // Initialize program state
-    program_state.is_initialized = true;
+    program_state.initialized = 1;
     program_state.admin = ctx.accounts.admin.key();
     program_state.usdc_mint = usdc_mint_key;
     program_state.usdv_mint = ctx.accounts.usdv_mint.key();
     program_state.vault_authority = ctx.accounts.vault_authority.key();
     program_state.vault_bump = ctx.bumps.vault_authority;
-    program_state.total_usdv_supply = 0;
-    program_state.total_usdc_deposits = 0;
     
     msg!("USDv Stablecoin program initialized successfully");
     msg!("USDc Mint: {}", usdc_mint_key);
@@ -37,26 +42,44 @@ pub fn initialize(
 }
 
 /// Deposit USDC and mint USDv tokens (1:1 ratio)
+///
+/// Returns the amount of USDv minted.
 pub fn deposit_and_mint(
     ctx: Context<DepositAndMint>,
     amount: u64,
-) -> Result<()> {
-    let program_state = &mut ctx.accounts.program_state;
-    
-    // Ensure program is initialized
-    require!(program_state.is_initialized, USDvError::NotInitialized);
-    
-    // Verify USDc mint matches
-    require!(
-        ctx.accounts.usdc_mint.key() == program_state.usdc_mint,
+) -> Result<u64> {
+    // Loaded once; the account constraints only check what needs no state
+    let mut program_state = ctx.accounts.program_state.load_mut()?;
+    require!(program_state.is_initialized(), USDvError::NotInitialized);
+    require_keys_eq!(
+        ctx.accounts.usdc_mint.key(),
+        program_state.usdc_mint,
         USDvError::InvalidUSDCMint
     );
+    require_usdv_accounts(
+        &program_state,
+        &ctx.accounts.usdv_mint.key(),
+        &ctx.accounts.vault_authority.key(),
+    )?;
     
-    // Verify user has sufficient USDc balance
-    require!(
-        ctx.accounts.user_usdc_account.amount >= amount,
-        USDvError::InsufficientUSDCBalance
-    );
+    // Minting is permanently disabled once wind-down has started
+    require!(!program_state.is_wound_down(), USDvError::WindDownActive);
+    
+    // In allowlist mode only verified wallets can mint
+    if program_state.is_allowlist_enabled() {
+        require_allowlisted(
+            ctx.accounts.allowlist_entry.as_deref(),
+            &ctx.accounts.user.key(),
+        )?;
+    }
+    
+    // Same amount, balance and supply rules the client checks before sending
+    validate_deposit_operation(
+        amount,
+        ctx.accounts.user_usdc_account.amount,
+        program_state.total_usdv_supply,
+    )
+    .map_err(|e| validation_error(e, USDvError::InsufficientUSDCBalance))?;
     
     // Transfer USDC from user to vault
     let transfer_ctx = CpiContext::new(
@@ -95,26 +118,64 @@ pub fn deposit_and_mint(
         .checked_add(amount)
         .ok_or(USDvError::ArithmeticOverflow)?;
     
+    emit!(Deposited {
+        user: ctx.accounts.user.key(),
+        amount,
+        total_usdv_supply: program_state.total_usdv_supply,
+    });
+    
     msg!("Successfully deposited {} USDC and minted {} USDv", amount, amount);
     
-    Ok(())
+    Ok(amount)
 }
 
 /// Burn USDv tokens and withdraw USDC (1:1 ratio)
+///
+/// Redemptions stay open during wind-down so holders can always exit.
+/// Returns the amount of USDC paid out.
 pub fn burn_and_withdraw(
     ctx: Context<BurnAndWithdraw>,
     amount: u64,
-) -> Result<()> {
-    let program_state = &mut ctx.accounts.program_state;
+) -> Result<u64> {
+    // Loaded once; the account constraints only check what needs no state
+    let mut program_state = ctx.accounts.program_state.load_mut()?;
+    require!(program_state.is_initialized(), USDvError::NotInitialized);
+    require_usdv_accounts(
+        &program_state,
+        &ctx.accounts.usdv_mint.key(),
+        &ctx.accounts.vault_authority.key(),
+    )?;
+    require_usdc_vault(&program_state, &ctx.accounts.vault_usdc_account)?;
+    require_keys_eq!(
+        ctx.accounts.user_usdc_account.mint,
+        program_state.usdc_mint,
+        USDvError::InvalidUSDCMint
+    );
     
-    // Ensure program is initialized
-    require!(program_state.is_initialized, USDvError::NotInitialized);
+    // In allowlist mode only verified wallets can redeem, except during
+    // wind-down when every holder must be able to exit
+    if program_state.is_allowlist_enabled() && !program_state.is_wound_down() {
+        require_allowlisted(
+            ctx.accounts.allowlist_entry.as_deref(),
+            &ctx.accounts.user.key(),
+        )?;
+    }
     
-    // Verify user has sufficient USDv balance
-    require!(
-        ctx.accounts.user_usdv_account.amount >= amount,
-        USDvError::InsufficientUSDvBalance
-    );
+    // Same amount and balance rules the client checks before sending
+    validate_burn_operation(
+        amount,
+        ctx.accounts.user_usdv_account.amount,
+        ctx.accounts.vault_usdc_account.amount,
+    )
+    .map_err(|e| validation_error(e, USDvError::InsufficientUSDvBalance))?;
+    
+    // Off-chain minted USDv is fungible with deposit-backed USDv, so cap USDC
+    // payouts at the on-chain backed supply and leave off-chain collateral
+    // to be redeemed through minters
+    let redeemable = program_state
+        .onchain_backed_supply()
+        .min(program_state.total_usdc_deposits);
+    require!(amount <= redeemable, USDvError::InsufficientOnchainDeposits);
     
     // Burn USDv tokens from user account
     let burn_ctx = CpiContext::new(
@@ -153,29 +214,580 @@ pub fn burn_and_withdraw(
         .checked_sub(amount)
         .ok_or(USDvError::ArithmeticOverflow)?;
     
+    emit!(Burned {
+        user: ctx.accounts.user.key(),
+        amount,
+        total_usdv_supply: program_state.total_usdv_supply,
+    });
+    
     msg!("Successfully burned {} USDv and withdrew {} USDC", amount, amount);
     
+    Ok(amount)
+}
+
+/// Check the USDv mint and vault authority accounts against `program_state`
+fn require_usdv_accounts(
+    program_state: &ProgramState,
+    usdv_mint: &Pubkey,
+    vault_authority: &Pubkey,
+) -> Result<()> {
+    require_keys_eq!(*usdv_mint, program_state.usdv_mint, USDvError::InvalidMintAddress);
+    require_keys_eq!(
+        *vault_authority,
+        program_state.vault_authority,
+        USDvError::InvalidVaultAuthority
+    );
     Ok(())
 }
 
+/// Check that `vault` is the vault authority's USDC associated token account
+///
+/// Checked on the state the handler already loaded, rather than in the
+/// account constraints where each check would load `program_state` again.
+fn require_usdc_vault(program_state: &ProgramState, vault: &Account<TokenAccount>) -> Result<()> {
+    let expected = get_associated_token_address(&program_state.vault_authority, &program_state.usdc_mint);
+    require_keys_eq!(vault.key(), expected, USDvError::InvalidVaultAuthority);
+    require_keys_eq!(vault.mint, program_state.usdc_mint, USDvError::InvalidUSDCMint);
+    Ok(())
+}
+
+/// Map a shared validation failure to the program's error code
+///
+/// `insufficient_balance` is the error for the user's own balance, which
+/// depends on the token being spent.
+fn validation_error(err: ValidationError, insufficient_balance: USDvError) -> Error {
+    msg!("Validation failed: {}", err);
+    match err {
+        ValidationError::InsufficientBalance { .. } => error!(insufficient_balance),
+        ValidationError::InsufficientVaultBalance { .. } => {
+            error!(USDvError::InsufficientVaultBalance)
+        }
+        ValidationError::SupplyLimitExceeded { .. } => error!(USDvError::SupplyLimitExceeded),
+        ValidationError::InvalidPublicKey(_) => error!(USDvError::InvalidPublicKey),
+        ValidationError::InvalidDecimals(_) => error!(USDvError::InvalidMintAddress),
+        ValidationError::InvalidAmount { .. }
+        | ValidationError::AmountTooSmall { .. }
+        | ValidationError::AmountTooLarge { .. }
+        | ValidationError::ZeroAmount => error!(USDvError::InvalidAmount),
+    }
+}
+
 /// Update program parameters (admin only)
+///
+/// Still allowed during wind-down: rotating the admin key gives the
+/// admin no power over user funds.
 pub fn update_program_state(
     ctx: Context<UpdateProgramState>,
     new_admin: Option<Pubkey>,
 ) -> Result<()> {
-    let program_state = &mut ctx.accounts.program_state;
+    // Only the current admin can update, checked by the account constraints
+    let mut program_state = ctx.accounts.program_state.load_mut()?;
+    
+    if let Some(new_admin_key) = new_admin {
+        program_state.admin = new_admin_key;
+        msg!("Admin updated to: {}", new_admin_key);
+    }
+    
+    Ok(())
+}
+
+/// Put the program into wind-down (admin only, one-way)
+///
+/// Minting is permanently disabled, redemptions stay open, and once
+/// `claim_period_seconds` have elapsed the admin can sweep whatever USDc
+/// holders did not claim.
+pub fn initiate_wind_down(
+    ctx: Context<InitiateWindDown>,
+    claim_period_seconds: i64,
+) -> Result<()> {
+    let mut program_state = ctx.accounts.program_state.load_mut()?;
+    
+    // Wind-down can only be entered once
+    require!(!program_state.is_wound_down(), USDvError::WindDownActive);
     
-    // Only current admin can update
+    // Holders must get a reasonable window to redeem
     require!(
-        ctx.accounts.admin.key() == program_state.admin,
+        claim_period_seconds >= ProgramState::MIN_WIND_DOWN_CLAIM_PERIOD,
+        USDvError::InvalidClaimPeriod
+    );
+    
+    let now = Clock::get()?.unix_timestamp;
+    let deadline = now
+        .checked_add(claim_period_seconds)
+        .ok_or(USDvError::ArithmeticOverflow)?;
+    
+    program_state.wound_down = 1;
+    program_state.wind_down_started_at = now;
+    program_state.wind_down_deadline = deadline;
+    
+    emit!(WindDownInitiated {
+        admin: ctx.accounts.admin.key(),
+        started_at: now,
+        deadline,
+        outstanding_usdv_supply: program_state.total_usdv_supply,
+    });
+    
+    msg!("Wind-down initiated, unclaimed USDc can be swept after {}", deadline);
+    
+    Ok(())
+}
+
+/// Sweep unclaimed USDc to a recovery account after the wind-down deadline (admin only)
+pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+    let mut program_state = ctx.accounts.program_state.load_mut()?;
+    require_keys_eq!(
+        ctx.accounts.admin.key(),
+        program_state.admin,
         USDvError::Unauthorized
     );
+    require_keys_eq!(
+        ctx.accounts.vault_authority.key(),
+        program_state.vault_authority,
+        USDvError::InvalidVaultAuthority
+    );
+    require_usdc_vault(&program_state, &ctx.accounts.vault_usdc_account)?;
+    require_keys_eq!(
+        ctx.accounts.recovery_usdc_account.mint,
+        program_state.usdc_mint,
+        USDvError::InvalidUSDCMint
+    );
     
-    if let Some(new_admin_key) = new_admin {
-        program_state.admin = new_admin_key;
-        msg!("Admin updated to: {}", new_admin_key);
+    require!(program_state.is_wound_down(), USDvError::WindDownNotActive);
+    
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        program_state.is_claim_period_over(now),
+        USDvError::ClaimPeriodNotElapsed
+    );
+    
+    let amount = ctx.accounts.vault_usdc_account.amount;
+    
+    if amount > 0 {
+        let vault_authority_bump = program_state.vault_bump;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            ProgramState::VAULT_AUTHORITY_SEED,
+            &[vault_authority_bump],
+        ]];
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_usdc_account.to_account_info(),
+                to: ctx.accounts.recovery_usdc_account.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, amount)?;
+    }
+    
+    // The vault may hold more than was deposited (direct transfers), so saturate
+    program_state.total_usdc_deposits = program_state.total_usdc_deposits
+        .saturating_sub(amount);
+    
+    emit!(UnclaimedCollateralSwept {
+        admin: ctx.accounts.admin.key(),
+        recovery_account: ctx.accounts.recovery_usdc_account.key(),
+        amount,
+        outstanding_usdv_supply: program_state.total_usdv_supply,
+        timestamp: now,
+    });
+    
+    msg!("Swept {} unclaimed USDC after wind-down", amount);
+    
+    Ok(())
+}
+
+/// Recover non-collateral tokens sent to the vault authority by mistake (admin only)
+///
+/// Transfers the full balance of a token account owned by the vault
+/// authority. Accounts holding accepted collateral, and in particular the
+/// USDc vault ATA, are always rejected. Allowed during wind-down since the
+/// recovered tokens never back USDv.
+pub fn recover_tokens(ctx: Context<RecoverTokens>) -> Result<()> {
+    let program_state = ctx.accounts.program_state.load()?;
+    let source = &ctx.accounts.source_token_account;
+    
+    // Never touch the collateral vault, whatever its mint claims to be
+    let vault_usdc_account = get_associated_token_address(
+        &program_state.vault_authority,
+        &program_state.usdc_mint,
+    );
+    require_keys_neq!(
+        source.key(),
+        vault_usdc_account,
+        USDvError::CollateralNotRecoverable
+    );
+    
+    // Only non-collateral mints can be recovered
+    require_keys_neq!(
+        source.mint,
+        program_state.usdc_mint,
+        USDvError::CollateralNotRecoverable
+    );
+    
+    let amount = source.amount;
+    require!(amount > 0, USDvError::InvalidAmount);
+    
+    let vault_authority_bump = program_state.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        ProgramState::VAULT_AUTHORITY_SEED,
+        &[vault_authority_bump],
+    ]];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: source.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+    
+    emit!(TokensRecovered {
+        admin: ctx.accounts.admin.key(),
+        mint: source.mint,
+        source: source.key(),
+        destination: ctx.accounts.destination_token_account.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Recovered {} tokens of mint {}", amount, source.mint);
+    
+    Ok(())
+}
+
+/// Register a minter or set its remaining allowance (admin only)
+///
+/// During wind-down the allowance can only be revoked (set to zero).
+pub fn configure_minter(
+    ctx: Context<ConfigureMinter>,
+    allowance: u64,
+) -> Result<()> {
+    let program_state = ctx.accounts.program_state.load()?;
+    
+    require!(
+        !program_state.is_wound_down() || allowance == 0,
+        USDvError::WindDownActive
+    );
+    
+    let minter = &mut ctx.accounts.minter;
+    minter.authority = ctx.accounts.minter_authority.key();
+    minter.allowance = allowance;
+    minter.bump = ctx.bumps.minter;
+    
+    emit!(MinterConfigured {
+        admin: ctx.accounts.admin.key(),
+        minter: minter.authority,
+        allowance,
+    });
+    
+    msg!("Minter {} allowance set to {}", minter.authority, allowance);
+    
+    Ok(())
+}
+
+/// Mint USDv against off-chain collateral, debiting the minter's allowance
+pub fn mint_allowance(
+    ctx: Context<MintAllowance>,
+    amount: u64,
+) -> Result<()> {
+    let mut program_state = ctx.accounts.program_state.load_mut()?;
+    require!(program_state.is_initialized(), USDvError::NotInitialized);
+    require_usdv_accounts(
+        &program_state,
+        &ctx.accounts.usdv_mint.key(),
+        &ctx.accounts.vault_authority.key(),
+    )?;
+    let minter = &mut ctx.accounts.minter;
+    
+    require!(!program_state.is_wound_down(), USDvError::WindDownActive);
+    require!(amount > 0, USDvError::InvalidAmount);
+    require!(
+        amount <= minter.allowance,
+        USDvError::MinterAllowanceExceeded
+    );
+    
+    // Same supply cap as on-chain deposits
+    validate_mint_supply_limit(amount, program_state.total_usdv_supply)
+        .map_err(|e| validation_error(e, USDvError::MinterAllowanceExceeded))?;
+    
+    let vault_authority_bump = program_state.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        ProgramState::VAULT_AUTHORITY_SEED,
+        &[vault_authority_bump],
+    ]];
+    
+    let mint_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.usdv_mint.to_account_info(),
+            to: ctx.accounts.destination_usdv_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::mint_to(mint_ctx, amount)?;
+    
+    minter.allowance = minter.allowance
+        .checked_sub(amount)
+        .ok_or(USDvError::ArithmeticOverflow)?;
+    minter.total_minted = minter.total_minted
+        .checked_add(amount)
+        .ok_or(USDvError::ArithmeticOverflow)?;
+    
+    // Off-chain backed mints are tracked apart from on-chain USDc deposits
+    program_state.total_usdv_supply = program_state.total_usdv_supply
+        .checked_add(amount)
+        .ok_or(USDvError::ArithmeticOverflow)?;
+    program_state.total_offchain_deposits = program_state.total_offchain_deposits
+        .checked_add(amount)
+        .ok_or(USDvError::ArithmeticOverflow)?;
+    
+    emit!(OffchainMint {
+        minter: minter.authority,
+        destination: ctx.accounts.destination_usdv_account.key(),
+        amount,
+        remaining_allowance: minter.allowance,
+    });
+    
+    msg!("Minter {} minted {} USDv against off-chain collateral", minter.authority, amount);
+    
+    Ok(())
+}
+
+/// Burn a minter's USDv so the collateral can be paid out off-chain
+///
+/// Allowed during wind-down, like every other redemption.
+pub fn burn_for_offchain_redemption(
+    ctx: Context<BurnForOffchainRedemption>,
+    amount: u64,
+) -> Result<()> {
+    let mut program_state = ctx.accounts.program_state.load_mut()?;
+    require!(program_state.is_initialized(), USDvError::NotInitialized);
+    require_keys_eq!(
+        ctx.accounts.usdv_mint.key(),
+        program_state.usdv_mint,
+        USDvError::InvalidMintAddress
+    );
+    let minter = &mut ctx.accounts.minter;
+    
+    require!(amount > 0, USDvError::InvalidAmount);
+    require!(
+        ctx.accounts.minter_usdv_account.amount >= amount,
+        USDvError::InsufficientUSDvBalance
+    );
+    require!(
+        amount <= program_state.total_offchain_deposits,
+        USDvError::InsufficientOffchainDeposits
+    );
+    
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.usdv_mint.to_account_info(),
+            from: ctx.accounts.minter_usdv_account.to_account_info(),
+            authority: ctx.accounts.minter_authority.to_account_info(),
+        },
+    );
+    token::burn(burn_ctx, amount)?;
+    
+    minter.total_burned = minter.total_burned
+        .checked_add(amount)
+        .ok_or(USDvError::ArithmeticOverflow)?;
+    program_state.total_usdv_supply = program_state.total_usdv_supply
+        .checked_sub(amount)
+        .ok_or(USDvError::ArithmeticOverflow)?;
+    program_state.total_offchain_deposits -= amount;
+    
+    emit!(OffchainRedemption {
+        minter: minter.authority,
+        amount,
+    });
+    
+    msg!("Minter {} burned {} USDv for off-chain redemption", minter.authority, amount);
+    
+    Ok(())
+}
+
+/// Turn allowlist-gated minting and redemption on or off (admin only)
+pub fn set_allowlist_mode(
+    ctx: Context<SetAllowlistMode>,
+    enabled: bool,
+) -> Result<()> {
+    let mut program_state = ctx.accounts.program_state.load_mut()?;
+    program_state.allowlist_mode = u8::from(enabled);
+    
+    emit!(AllowlistModeChanged {
+        admin: ctx.accounts.admin.key(),
+        enabled,
+    });
+    
+    msg!("Allowlist mode {}", if enabled { "enabled" } else { "disabled" });
+    
+    Ok(())
+}
+
+/// Add or remove many allowlist entries in one instruction (admin only)
+///
+/// `remaining_accounts` must hold the writable entry PDA of every update,
+/// in the same order as `updates`. Adding an existing wallet updates its
+/// expiry; removing closes the entry and refunds rent to the admin.
+pub fn update_allowlist<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateAllowlist<'info>>,
+    updates: Vec<AllowlistUpdate>,
+) -> Result<()> {
+    require!(
+        updates.len() == ctx.remaining_accounts.len(),
+        USDvError::InvalidAllowlistEntry
+    );
+    
+    let admin = ctx.accounts.admin.to_account_info();
+    let mut added: u32 = 0;
+    let mut removed: u32 = 0;
+    
+    for (update, entry_info) in updates.iter().zip(ctx.remaining_accounts.iter()) {
+        let wallet = update.wallet();
+        let (expected, bump) = Pubkey::find_program_address(
+            &[AllowlistEntry::SEED, wallet.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(entry_info.key(), expected, USDvError::InvalidAllowlistEntry);
+        
+        let exists = entry_info.owner == ctx.program_id;
+        
+        match update {
+            AllowlistUpdate::Add { wallet, expires_at } => {
+                if exists {
+                    let mut entry = Account::<AllowlistEntry>::try_from(entry_info)?;
+                    entry.expires_at = *expires_at;
+                    entry.exit(ctx.program_id)?;
+                } else {
+                    let space = 8 + AllowlistEntry::INIT_SPACE;
+                    let signer_seeds: &[&[&[u8]]] = &[&[
+                        AllowlistEntry::SEED,
+                        wallet.as_ref(),
+                        &[bump],
+                    ]];
+                    create_pda_account(
+                        &admin,
+                        entry_info,
+                        &ctx.accounts.system_program.to_account_info(),
+                        space,
+                        signer_seeds,
+                        ctx.program_id,
+                    )?;
+                    
+                    let entry = AllowlistEntry {
+                        wallet: *wallet,
+                        expires_at: *expires_at,
+                        bump,
+                    };
+                    let mut data = entry_info.try_borrow_mut_data()?;
+                    entry.try_serialize(&mut &mut data[..])?;
+                }
+                added += 1;
+            }
+            AllowlistUpdate::Remove { .. } => {
+                // Removing a wallet that is not listed is a no-op
+                if exists {
+                    Account::<AllowlistEntry>::try_from(entry_info)?.close(admin.clone())?;
+                    removed += 1;
+                }
+            }
+        }
+    }
+    
+    emit!(AllowlistUpdated {
+        admin: ctx.accounts.admin.key(),
+        added,
+        removed,
+    });
+    
+    msg!("Allowlist updated: {} added, {} removed", added, removed);
+    
+    Ok(())
+}
+
+/// Create `account`, a PDA signed for by `signer_seeds`, owned by `owner`
+///
+/// Anyone can send lamports to a PDA before it is created, and
+/// `create_account` rejects an address that holds any. As with Anchor's
+/// `init`, such an account is topped up to rent exemption, allocated and
+/// assigned instead.
+fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+    owner: &Pubkey,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let lamports = account.lamports();
+    
+    if lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            owner,
+        );
     }
     
+    let shortfall = rent.saturating_sub(lamports);
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: account.clone(),
+            },
+            signer_seeds,
+        ),
+        owner,
+    )
+}
+
+/// Check that `wallet` has a valid, unexpired allowlist entry
+fn require_allowlisted(entry: Option<&AllowlistEntry>, wallet: &Pubkey) -> Result<()> {
+    let entry = entry.ok_or(USDvError::NotAllowlisted)?;
+    require_keys_eq!(entry.wallet, *wallet, USDvError::NotAllowlisted);
+    require!(
+        entry.is_active(Clock::get()?.unix_timestamp),
+        USDvError::AllowlistEntryExpired
+    );
     Ok(())
 }
 
@@ -186,9 +798,11 @@ pub struct Initialize<'info> {
     #[account(
         init,
         payer = admin,
-        space = 8 + ProgramState::INIT_SPACE
+        space = 8 + ProgramState::LEN,
+        seeds = [ProgramState::SEED],
+        bump
     )]
-    pub program_state: Account<'info, ProgramState>,
+    pub program_state: AccountLoader<'info, ProgramState>,
     
     #[account(
         init,
@@ -215,27 +829,19 @@ pub struct Initialize<'info> {
 
 #[derive(Accounts)]
 pub struct DepositAndMint<'info> {
-    #[account(
-        mut,
-        constraint = program_state.is_initialized @ USDvError::NotInitialized
-    )]
-    pub program_state: Account<'info, ProgramState>,
+    /// Mints and vault authority are checked against it in the handler
+    #[account(mut)]
+    pub program_state: AccountLoader<'info, ProgramState>,
     
-    #[account(
-        constraint = usdc_mint.key() == program_state.usdc_mint @ USDvError::InvalidUSDCMint
-    )]
     pub usdc_mint: Account<'info, Mint>,
     
-    #[account(
-        mut,
-        constraint = usdv_mint.key() == program_state.usdv_mint
-    )]
+    #[account(mut)]
     pub usdv_mint: Account<'info, Mint>,
     
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = user,
+        token::mint = usdc_mint,
+        token::authority = user,
     )]
     pub user_usdc_account: Account<'info, TokenAccount>,
     
@@ -255,11 +861,7 @@ pub struct DepositAndMint<'info> {
     )]
     pub vault_usdc_account: Account<'info, TokenAccount>,
     
-    #[account(
-        seeds = [ProgramState::VAULT_AUTHORITY_SEED],
-        bump = program_state.vault_bump
-    )]
-    /// CHECK: This is a PDA used as authority
+    /// CHECK: Vault authority PDA, checked against `program_state` in the handler
     pub vault_authority: UncheckedAccount<'info>,
     
     #[account(mut)]
@@ -268,63 +870,232 @@ pub struct DepositAndMint<'info> {
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+    
+    /// User's allowlist entry, required only in allowlist mode
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
 }
 
 #[derive(Accounts)]
 pub struct BurnAndWithdraw<'info> {
+    /// Mints, vault and vault authority are checked against it in the handler
+    #[account(mut)]
+    pub program_state: AccountLoader<'info, ProgramState>,
+    
+    #[account(mut)]
+    pub usdv_mint: Account<'info, Mint>,
+    
     #[account(
         mut,
-        constraint = program_state.is_initialized @ USDvError::NotInitialized
+        token::mint = usdv_mint,
+        token::authority = user,
     )]
-    pub program_state: Account<'info, ProgramState>,
+    pub user_usdv_account: Account<'info, TokenAccount>,
     
+    /// Mint checked against `program_state` in the handler
     #[account(
         mut,
-        constraint = usdv_mint.key() == program_state.usdv_mint
+        token::authority = user,
     )]
-    pub usdv_mint: Account<'info, Mint>,
+    pub user_usdc_account: Account<'info, TokenAccount>,
+    
+    /// Checked against `program_state` in the handler
+    #[account(mut)]
+    pub vault_usdc_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Vault authority PDA, checked against `program_state` in the handler
+    pub vault_authority: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
     
+    /// User's allowlist entry, required only in allowlist mode
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProgramState<'info> {
     #[account(
         mut,
-        associated_token::mint = usdv_mint,
-        associated_token::authority = user,
+        has_one = admin @ USDvError::Unauthorized
     )]
-    pub user_usdv_account: Account<'info, TokenAccount>,
+    pub program_state: AccountLoader<'info, ProgramState>,
     
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitiateWindDown<'info> {
     #[account(
         mut,
-        associated_token::mint = program_state.usdc_mint,
-        associated_token::authority = user,
+        has_one = admin @ USDvError::Unauthorized,
+        constraint = program_state.load()?.is_initialized() @ USDvError::NotInitialized
     )]
-    pub user_usdc_account: Account<'info, TokenAccount>,
+    pub program_state: AccountLoader<'info, ProgramState>,
     
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SweepUnclaimed<'info> {
     #[account(
         mut,
-        associated_token::mint = program_state.usdc_mint,
-        associated_token::authority = vault_authority,
+        seeds = [ProgramState::SEED],
+        bump,
     )]
+    pub program_state: AccountLoader<'info, ProgramState>,
+    
+    /// Checked against `program_state` in the handler
+    #[account(mut)]
     pub vault_usdc_account: Account<'info, TokenAccount>,
     
+    /// Mint checked against `program_state` in the handler
+    #[account(mut)]
+    pub recovery_usdc_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Vault authority PDA, checked against `program_state` in the handler
+    pub vault_authority: UncheckedAccount<'info>,
+    
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RecoverTokens<'info> {
     #[account(
-        seeds = [ProgramState::VAULT_AUTHORITY_SEED],
-        bump = program_state.vault_bump
+        seeds = [ProgramState::SEED],
+        bump,
+        has_one = admin @ USDvError::Unauthorized,
+        has_one = vault_authority
     )]
-    /// CHECK: This is a PDA used as authority
+    pub program_state: AccountLoader<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        token::authority = vault_authority,
+    )]
+    pub source_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = source_token_account.mint,
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Vault authority PDA, checked against `program_state` via `has_one`
     pub vault_authority: UncheckedAccount<'info>,
     
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureMinter<'info> {
+    #[account(
+        has_one = admin @ USDvError::Unauthorized,
+        constraint = program_state.load()?.is_initialized() @ USDvError::NotInitialized
+    )]
+    pub program_state: AccountLoader<'info, ProgramState>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + Minter::INIT_SPACE,
+        seeds = [Minter::SEED, minter_authority.key().as_ref()],
+        bump
+    )]
+    pub minter: Account<'info, Minter>,
+    
+    /// CHECK: Only used as the minter PDA seed and stored as its authority
+    pub minter_authority: UncheckedAccount<'info>,
+    
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintAllowance<'info> {
+    /// USDv mint and vault authority are checked against it in the handler
+    #[account(mut)]
+    pub program_state: AccountLoader<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [Minter::SEED, minter_authority.key().as_ref()],
+        bump = minter.bump
+    )]
+    pub minter: Account<'info, Minter>,
+    
+    #[account(mut)]
+    pub usdv_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = usdv_mint,
+    )]
+    pub destination_usdv_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Vault authority PDA, checked against `program_state` in the handler
+    pub vault_authority: UncheckedAccount<'info>,
+    
+    pub minter_authority: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateProgramState<'info> {
+pub struct BurnForOffchainRedemption<'info> {
+    /// USDv mint is checked against it in the handler
+    #[account(mut)]
+    pub program_state: AccountLoader<'info, ProgramState>,
+    
     #[account(
         mut,
-        constraint = program_state.admin == admin.key() @ USDvError::Unauthorized
+        seeds = [Minter::SEED, minter_authority.key().as_ref()],
+        bump = minter.bump
     )]
-    pub program_state: Account<'info, ProgramState>,
+    pub minter: Account<'info, Minter>,
+    
+    #[account(mut)]
+    pub usdv_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = usdv_mint,
+        token::authority = minter_authority,
+    )]
+    pub minter_usdv_account: Account<'info, TokenAccount>,
+    
+    pub minter_authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetAllowlistMode<'info> {
+    #[account(
+        mut,
+        has_one = admin @ USDvError::Unauthorized
+    )]
+    pub program_state: AccountLoader<'info, ProgramState>,
     
     pub admin: Signer<'info>,
 }
+
+#[derive(Accounts)]
+pub struct UpdateAllowlist<'info> {
+    #[account(
+        has_one = admin @ USDvError::Unauthorized
+    )]
+    pub program_state: AccountLoader<'info, ProgramState>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -9,11 +9,52 @@
 //! - Users burn USDv tokens to withdraw USDC (1:1 ratio)
 //! - Only users can initiate burn operations (not the program)
 //! - All USDC is securely stored in a PDA vault
+//!
+//! ## Institutional minters
+//!
+//! Registered minters mint USDv against collateral received off-chain,
+//! bounded by an allowance set by the admin. These mints are tracked in
+//! `total_offchain_deposits`, separately from on-chain `total_usdc_deposits`.
+//!
+//! ## Allowlist mode
+//!
+//! For regulated launches the admin can restrict minting and redemption to
+//! wallets holding an unexpired allowlist entry. Holding and transferring
+//! USDv is never restricted.
+//!
+//! ## Composing via CPI
+//!
+//! Other programs can depend on this crate with the `cpi` feature, which
+//! disables the entrypoint and exposes typed wrappers under [`cpi`]:
+//!
+//! ```ignore
+//! let minted = usdv_program::cpi::deposit_and_mint(
+//!     CpiContext::new(usdv_program_info, usdv_program::cpi::accounts::DepositAndMint {
+//!         program_state, usdc_mint, usdv_mint, user_usdc_account,
+//!         user_usdv_account, vault_usdc_account, vault_authority, user,
+//!         token_program, allowlist_entry: None,
+//!     }),
+//!     amount,
+//! )?
+//! .get();
+//! ```
+//!
+//! `deposit_and_mint` and `burn_and_withdraw` set the amount minted or paid
+//! out as return data, read back through `Return::get`.
+//!
+//! ## Wind-down
+//!
+//! The admin can put the program into a one-way wind-down state for orderly
+//! decommissioning. Minting is permanently disabled, redemptions stay open,
+//! and after the claim deadline any unclaimed USDc can be swept to a
+//! recovery account.
 
 use anchor_lang::prelude::*;
 
+pub mod events;
 pub mod instructions;
 pub mod state;
+use instructions::*;
 pub use state::*;
 
 declare_id!("USDvCoinProgram1111111111111111111111111111");
@@ -21,6 +62,8 @@ declare_id!("USDvCoinProgram1111111111111111111111111111");
 /// Main program entry point
 #[program]
 pub mod usdv_program {
+    use super::*;
+
     /// Initialize the USDv stablecoin program
     /// 
     /// Creates the USDv mint with program as mint authority.
@@ -33,18 +76,22 @@ pub mod usdv_program {
     }
 
     /// Deposit USDc and mint USDv tokens (1:1 ratio)
+    ///
+    /// Sets the amount of USDv minted as return data.
     pub fn deposit_and_mint(
         ctx: Context<DepositAndMint>,
         amount: u64,
-    ) -> Result<()> {
+    ) -> Result<u64> {
         instructions::deposit_and_mint(ctx, amount)
     }
 
     /// Burn USDv tokens and withdraw USDc (1:1 ratio)
+    ///
+    /// Sets the amount of USDc paid out as return data.
     pub fn burn_and_withdraw(
         ctx: Context<BurnAndWithdraw>,
         amount: u64,
-    ) -> Result<()> {
+    ) -> Result<u64> {
         instructions::burn_and_withdraw(ctx, amount)
     }
 
@@ -55,5 +102,63 @@ pub mod usdv_program {
     ) -> Result<()> {
         instructions::update_program_state(ctx, new_admin)
     }
+
+    /// Put the program into wind-down (admin only, one-way)
+    pub fn initiate_wind_down(
+        ctx: Context<InitiateWindDown>,
+        claim_period_seconds: i64,
+    ) -> Result<()> {
+        instructions::initiate_wind_down(ctx, claim_period_seconds)
+    }
+
+    /// Sweep unclaimed USDc after the wind-down deadline (admin only)
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+        instructions::sweep_unclaimed(ctx)
+    }
+
+    /// Recover non-collateral tokens sent to the vault authority (admin only)
+    pub fn recover_tokens(ctx: Context<RecoverTokens>) -> Result<()> {
+        instructions::recover_tokens(ctx)
+    }
+
+    /// Register a minter or set its remaining allowance (admin only)
+    pub fn configure_minter(
+        ctx: Context<ConfigureMinter>,
+        allowance: u64,
+    ) -> Result<()> {
+        instructions::configure_minter(ctx, allowance)
+    }
+
+    /// Mint USDv against off-chain collateral (registered minters only)
+    pub fn mint_allowance(
+        ctx: Context<MintAllowance>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::mint_allowance(ctx, amount)
+    }
+
+    /// Burn USDv for an off-chain redemption (registered minters only)
+    pub fn burn_for_offchain_redemption(
+        ctx: Context<BurnForOffchainRedemption>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::burn_for_offchain_redemption(ctx, amount)
+    }
+
+    /// Turn allowlist-gated minting and redemption on or off (admin only)
+    pub fn set_allowlist_mode(
+        ctx: Context<SetAllowlistMode>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_allowlist_mode(ctx, enabled)
+    }
+
+    /// Add or remove many allowlist entries at once (admin only)
+    pub fn update_allowlist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateAllowlist<'info>>,
+        updates: Vec<AllowlistUpdate>,
+    ) -> Result<()> {
+        instructions::update_allowlist(ctx, updates)
+    }
 }
 
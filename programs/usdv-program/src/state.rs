@@ -1,17 +1,20 @@
 //! Program state definitions and account structures
 
 use anchor_lang::prelude::*;
+use usdv_utils::math::CollateralRatio;
 
 /// Global program state account
 /// 
 /// Stores configuration, statistics, and administrative information
-/// for the USDv stablecoin program.
-#[account]
-#[derive(InitSpace)]
+/// for the USDv stablecoin program. A singleton PDA at
+/// `[ProgramState::SEED]`, so only one deployment state can exist.
+///
+/// Zero-copy so deposits and burns read the fields they need in place
+/// instead of deserializing the whole account. Fields are ordered by
+/// alignment and flags are stored as `u8` (bool is not `Pod`), with
+/// explicit padding so the layout has no implicit gaps.
+#[account(zero_copy)]
 pub struct ProgramState {
-    /// Whether the program has been initialized
-    pub is_initialized: bool,
-    
     /// Admin public key (can update program parameters)
     pub admin: Pubkey,
     
@@ -24,37 +27,195 @@ pub struct ProgramState {
     /// Vault authority PDA public key
     pub vault_authority: Pubkey,
     
-    /// Bump seed for vault authority PDA
-    pub vault_bump: u8,
-    
     /// Total USDv tokens in circulation
     pub total_usdv_supply: u64,
     
     /// Total USDc deposited in vault
     pub total_usdc_deposits: u64,
+    
+    /// Total off-chain collateral attested by minters (USDv minted via allowances)
+    pub total_offchain_deposits: u64,
+    
+    /// Unix timestamp at which wind-down was initiated (0 if never)
+    pub wind_down_started_at: i64,
+    
+    /// Unix timestamp after which unclaimed USDc can be swept
+    pub wind_down_deadline: i64,
+    
+    /// Whether the program has been initialized (0 or 1)
+    pub initialized: u8,
+    
+    /// Bump seed for vault authority PDA
+    pub vault_bump: u8,
+    
+    /// Whether the program has entered the one-way wind-down state (0 or 1)
+    pub wound_down: u8,
+    
+    /// Whether only allowlisted wallets can mint and redeem (0 or 1)
+    pub allowlist_mode: u8,
+    
+    /// Reserved, keeps the struct 8-byte aligned without implicit padding
+    pub padding: [u8; 4],
 }
 
 impl ProgramState {
+    /// Size of the account data, excluding the discriminator
+    pub const LEN: usize = std::mem::size_of::<ProgramState>();
+    
+    /// Seed for deriving the program state PDA
+    pub const SEED: &'static [u8] = usdv_utils::PROGRAM_STATE_SEED;
+    
     /// Seed for deriving the vault authority PDA
-    pub const VAULT_AUTHORITY_SEED: &'static [u8] = b"vault_authority";
+    pub const VAULT_AUTHORITY_SEED: &'static [u8] = usdv_utils::VAULT_AUTHORITY_SEED;
+    
+    /// Minimum time holders get to redeem before unclaimed USDc can be swept (90 days)
+    pub const MIN_WIND_DOWN_CLAIM_PERIOD: i64 = 90 * 24 * 60 * 60;
+    
+    /// Whether `initialize` has run
+    pub fn is_initialized(&self) -> bool {
+        self.initialized != 0
+    }
+    
+    /// Whether the program is in wind-down
+    pub fn is_wound_down(&self) -> bool {
+        self.wound_down != 0
+    }
+    
+    /// Whether minting and redemption are restricted to allowlisted wallets
+    pub fn is_allowlist_enabled(&self) -> bool {
+        self.allowlist_mode != 0
+    }
     
     /// Check if the 1:1 peg is maintained
+    ///
+    /// Supply must be exactly covered by on-chain USDc plus attested
+    /// off-chain deposits.
     pub fn is_peg_maintained(&self) -> bool {
-        self.total_usdv_supply == self.total_usdc_deposits
+        self.total_backing() == Some(self.total_usdv_supply)
     }
     
-    /// Get the collateralization ratio (should always be 1.0 for healthy state)
-    pub fn collateralization_ratio(&self) -> Option<f64> {
-        if self.total_usdv_supply == 0 {
-            return Some(f64::INFINITY);
-        }
-        
-        Some(self.total_usdc_deposits as f64 / self.total_usdv_supply as f64)
+    /// Total collateral backing USDv, on-chain and attested off-chain
+    pub fn total_backing(&self) -> Option<u64> {
+        self.total_usdc_deposits.checked_add(self.total_offchain_deposits)
+    }
+    
+    /// USDv supply backed by USDc held on-chain in the vault
+    pub fn onchain_backed_supply(&self) -> u64 {
+        self.total_usdv_supply.saturating_sub(self.total_offchain_deposits)
+    }
+    
+    /// Collateralization ratio of total backing to supply, in basis points
+    ///
+    /// 10_000 bps for a healthy state; see [`CollateralRatio`] for the
+    /// zero-supply case.
+    pub fn collateralization_ratio(&self) -> CollateralRatio {
+        CollateralRatio::from_amounts(
+            self.total_backing().unwrap_or(u64::MAX),
+            self.total_usdv_supply,
+        )
     }
     
     /// Calculate maximum additional USDv that can be minted
+    ///
+    /// Only the on-chain backed supply draws on the vault; USDv minted
+    /// against off-chain collateral does not use up deposit headroom.
     pub fn max_additional_mint(&self, vault_usdc_balance: u64) -> u64 {
-        vault_usdc_balance.saturating_sub(self.total_usdv_supply)
+        if self.is_wound_down() {
+            return 0;
+        }
+        vault_usdc_balance.saturating_sub(self.onchain_backed_supply())
+    }
+    
+    /// Check if new USDv can be minted
+    pub fn is_minting_enabled(&self) -> bool {
+        self.is_initialized() && !self.is_wound_down()
+    }
+    
+    /// Check if the wind-down claim period has elapsed at `now`
+    pub fn is_claim_period_over(&self, now: i64) -> bool {
+        self.is_wound_down() && now >= self.wind_down_deadline
+    }
+}
+
+/// Registered institutional minter
+///
+/// Minters receive collateral off-chain (e.g. wires) and mint USDv against
+/// an allowance granted by the admin, without depositing USDc on-chain.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub struct Minter {
+    /// Key that signs `mint_allowance` / `burn_for_offchain_redemption`
+    pub authority: Pubkey,
+    
+    /// USDv that can still be minted
+    pub allowance: u64,
+    
+    /// Lifetime USDv minted by this minter
+    pub total_minted: u64,
+    
+    /// Lifetime USDv burned by this minter for off-chain redemptions
+    pub total_burned: u64,
+    
+    /// Bump seed for the minter PDA
+    pub bump: u8,
+}
+
+impl Minter {
+    /// Seed prefix for deriving minter PDAs
+    pub const SEED: &'static [u8] = usdv_utils::MINTER_SEED;
+}
+
+/// Allowlist entry for a verified (KYC'd) wallet
+///
+/// Only checked when `ProgramState::allowlist_mode` is set. Holding and
+/// transferring USDv never requires an entry.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub struct AllowlistEntry {
+    /// Verified wallet
+    pub wallet: Pubkey,
+    
+    /// Unix timestamp after which the entry is no longer valid (0 = never expires)
+    pub expires_at: i64,
+    
+    /// Bump seed for the allowlist entry PDA
+    pub bump: u8,
+}
+
+impl AllowlistEntry {
+    /// Seed prefix for deriving allowlist entry PDAs
+    pub const SEED: &'static [u8] = usdv_utils::ALLOWLIST_SEED;
+    
+    /// Check if the entry is valid at `now`
+    pub fn is_active(&self, now: i64) -> bool {
+        self.expires_at == 0 || now < self.expires_at
+    }
+}
+
+/// A single change to the allowlist, applied in bulk by `update_allowlist`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "client", derive(serde::Serialize, serde::Deserialize))]
+pub enum AllowlistUpdate {
+    /// Add a wallet, or change the expiry of an existing entry
+    Add {
+        wallet: Pubkey,
+        expires_at: i64,
+    },
+    /// Remove a wallet and close its entry
+    Remove {
+        wallet: Pubkey,
+    },
+}
+
+impl AllowlistUpdate {
+    /// Wallet targeted by this update
+    pub fn wallet(&self) -> &Pubkey {
+        match self {
+            AllowlistUpdate::Add { wallet, .. } => wallet,
+            AllowlistUpdate::Remove { wallet } => wallet,
+        }
     }
 }
 
@@ -65,46 +226,176 @@ mod tests {
     #[test]
     fn test_program_state_peg_maintenance() {
         let mut state = ProgramState {
-            is_initialized: true,
             admin: Pubkey::new_unique(),
             usdc_mint: Pubkey::new_unique(),
             usdv_mint: Pubkey::new_unique(),
             vault_authority: Pubkey::new_unique(),
-            vault_bump: 255,
             total_usdv_supply: 1000,
             total_usdc_deposits: 1000,
+            total_offchain_deposits: 0,
+            wind_down_started_at: 0,
+            wind_down_deadline: 0,
+            initialized: 1,
+            vault_bump: 255,
+            wound_down: 0,
+            allowlist_mode: 0,
+            padding: [0; 4],
         };
 
         // Test 1:1 peg maintenance
         assert!(state.is_peg_maintained());
-        assert_eq!(state.collateralization_ratio(), Some(1.0));
+        assert_eq!(state.collateralization_ratio(), CollateralRatio::Bps(10_000));
 
         // Test broken peg
         state.total_usdc_deposits = 900;
         assert!(!state.is_peg_maintained());
-        assert_eq!(state.collateralization_ratio(), Some(0.9));
+        assert_eq!(state.collateralization_ratio(), CollateralRatio::Bps(9_000));
 
         // Test over-collateralized
         state.total_usdc_deposits = 1100;
         assert!(!state.is_peg_maintained());
-        assert_eq!(state.collateralization_ratio(), Some(1.1));
+        assert_eq!(state.collateralization_ratio(), CollateralRatio::Bps(11_000));
+
+        // Zero supply has nothing to back
+        state.total_usdv_supply = 0;
+        assert_eq!(state.collateralization_ratio(), CollateralRatio::NoSupply);
     }
 
     #[test]
     fn test_max_additional_mint() {
         let state = ProgramState {
-            is_initialized: true,
             admin: Pubkey::new_unique(),
             usdc_mint: Pubkey::new_unique(),
             usdv_mint: Pubkey::new_unique(),
             vault_authority: Pubkey::new_unique(),
-            vault_bump: 255,
             total_usdv_supply: 500,
             total_usdc_deposits: 500,
+            total_offchain_deposits: 0,
+            wind_down_started_at: 0,
+            wind_down_deadline: 0,
+            initialized: 1,
+            vault_bump: 255,
+            wound_down: 0,
+            allowlist_mode: 0,
+            padding: [0; 4],
         };
 
         assert_eq!(state.max_additional_mint(1000), 500);
         assert_eq!(state.max_additional_mint(500), 0);
         assert_eq!(state.max_additional_mint(300), 0);
     }
+
+    #[test]
+    fn test_max_additional_mint_ignores_offchain_supply() {
+        let state = ProgramState {
+            admin: Pubkey::new_unique(),
+            usdc_mint: Pubkey::new_unique(),
+            usdv_mint: Pubkey::new_unique(),
+            vault_authority: Pubkey::new_unique(),
+            total_usdv_supply: 2500,
+            total_usdc_deposits: 500,
+            total_offchain_deposits: 2000,
+            wind_down_started_at: 0,
+            wind_down_deadline: 0,
+            initialized: 1,
+            vault_bump: 255,
+            wound_down: 0,
+            allowlist_mode: 0,
+            padding: [0; 4],
+        };
+
+        // Off-chain supply exceeds the vault balance but uses none of it
+        assert_eq!(state.onchain_backed_supply(), 500);
+        assert_eq!(state.max_additional_mint(1000), 500);
+        assert_eq!(state.max_additional_mint(500), 0);
+    }
+
+    #[test]
+    fn test_wind_down_state() {
+        let mut state = ProgramState {
+            admin: Pubkey::new_unique(),
+            usdc_mint: Pubkey::new_unique(),
+            usdv_mint: Pubkey::new_unique(),
+            vault_authority: Pubkey::new_unique(),
+            total_usdv_supply: 500,
+            total_usdc_deposits: 500,
+            total_offchain_deposits: 0,
+            wind_down_started_at: 0,
+            wind_down_deadline: 0,
+            initialized: 1,
+            vault_bump: 255,
+            wound_down: 0,
+            allowlist_mode: 0,
+            padding: [0; 4],
+        };
+
+        assert!(state.is_minting_enabled());
+        assert!(!state.is_claim_period_over(i64::MAX));
+
+        state.wound_down = 1;
+        state.wind_down_started_at = 1_000;
+        state.wind_down_deadline = 1_000 + ProgramState::MIN_WIND_DOWN_CLAIM_PERIOD;
+
+        // Minting is permanently disabled once wind-down starts
+        assert!(!state.is_minting_enabled());
+        assert_eq!(state.max_additional_mint(1000), 0);
+
+        // Sweeping is only possible once the deadline is reached
+        assert!(!state.is_claim_period_over(state.wind_down_deadline - 1));
+        assert!(state.is_claim_period_over(state.wind_down_deadline));
+    }
+
+    #[test]
+    fn test_offchain_backed_supply() {
+        let mut state = ProgramState {
+            admin: Pubkey::new_unique(),
+            usdc_mint: Pubkey::new_unique(),
+            usdv_mint: Pubkey::new_unique(),
+            vault_authority: Pubkey::new_unique(),
+            total_usdv_supply: 1500,
+            total_usdc_deposits: 1000,
+            total_offchain_deposits: 500,
+            wind_down_started_at: 0,
+            wind_down_deadline: 0,
+            initialized: 1,
+            vault_bump: 255,
+            wound_down: 0,
+            allowlist_mode: 0,
+            padding: [0; 4],
+        };
+
+        // Attested off-chain deposits count towards the peg
+        assert!(state.is_peg_maintained());
+        assert_eq!(state.total_backing(), Some(1500));
+        assert_eq!(state.onchain_backed_supply(), 1000);
+
+        state.total_offchain_deposits = 400;
+        assert!(!state.is_peg_maintained());
+    }
+
+    #[test]
+    fn test_program_state_layout() {
+        // No implicit padding, and the size the client and utils expect
+        assert_eq!(ProgramState::LEN, 176);
+        assert_eq!(
+            8 + ProgramState::LEN,
+            usdv_utils::account_space::PROGRAM_STATE
+        );
+    }
+
+    #[test]
+    fn test_allowlist_entry_expiry() {
+        let mut entry = AllowlistEntry {
+            wallet: Pubkey::new_unique(),
+            expires_at: 0,
+            bump: 255,
+        };
+
+        // Entries without expiry never lapse
+        assert!(entry.is_active(i64::MAX));
+
+        entry.expires_at = 1_000;
+        assert!(entry.is_active(999));
+        assert!(!entry.is_active(1_000));
+    }
 }
@@ -0,0 +1,579 @@
+//! Shared LiteSVM harness for the USDv program integration tests
+//!
+//! Tests load the compiled program from `target/deploy/usdv_program.so`,
+//! so they only run with the `test-sbf` feature. `cargo test-sbf` builds the
+//! program and enables it; after `anchor build`, `cargo test --features
+//! test-sbf` does the same.
+
+#![allow(dead_code)]
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::system_program;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account;
+use anchor_spl::token::spl_token;
+use litesvm::LiteSVM;
+use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use usdv_program::{AllowlistEntry, AllowlistUpdate, Minter, ProgramState};
+use usdv_results::USDvError;
+use usdv_utils::pda::{
+    derive_allowlist_entry_pda, derive_associated_token_account, derive_minter_pda,
+    derive_program_state_pda, derive_vault_authority_pda,
+};
+
+/// Path of the compiled program relative to this crate
+pub const PROGRAM_SO: &str = "../../target/deploy/usdv_program.so";
+
+/// 1 USDC / USDv in base units
+pub const ONE_TOKEN: u64 = 1_000_000;
+
+/// Outcome of a transaction; the failure is boxed as it is much larger than
+/// the success
+pub type TxResult = Result<TransactionMetadata, Box<FailedTransactionMetadata>>;
+
+/// An initialized USDv deployment inside LiteSVM
+pub struct TestEnv {
+    pub svm: LiteSVM,
+    pub admin: Keypair,
+    pub usdc_mint: Pubkey,
+    pub program_state: Pubkey,
+    pub usdv_mint: Pubkey,
+    pub vault_authority: Pubkey,
+}
+
+impl TestEnv {
+    /// Load the program, create a USDC mint and initialize USDv
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(usdv_program::ID, PROGRAM_SO)
+            .expect("build the program with `anchor build` before running tests");
+
+        let admin = Keypair::new();
+        svm.airdrop(&admin.pubkey(), 100_000_000_000).unwrap();
+
+        let usdc_mint = create_mint(&mut svm, &admin);
+
+        let (program_state, _) = derive_program_state_pda(&usdv_program::ID);
+        let (vault_authority, _) = derive_vault_authority_pda(&usdv_program::ID);
+        let usdv_mint = Keypair::new();
+
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts: usdv_program::accounts::Initialize {
+                program_state,
+                usdv_mint: usdv_mint.pubkey(),
+                vault_authority,
+                admin: admin.pubkey(),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: usdv_program::instruction::Initialize {
+                usdc_mint_key: usdc_mint,
+            }
+            .data(),
+        };
+        send(&mut svm, &[ix], &admin, &[&usdv_mint])
+            .expect("initialize failed");
+
+        Self {
+            svm,
+            admin,
+            usdc_mint,
+            program_state,
+            usdv_mint: usdv_mint.pubkey(),
+            vault_authority,
+        }
+    }
+
+    /// Send `initialize` again, creating a fresh USDv mint
+    pub fn initialize(&mut self, admin: &Keypair) -> TxResult {
+        let usdv_mint = Keypair::new();
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts: usdv_program::accounts::Initialize {
+                program_state: self.program_state,
+                usdv_mint: usdv_mint.pubkey(),
+                vault_authority: self.vault_authority,
+                admin: admin.pubkey(),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: usdv_program::instruction::Initialize {
+                usdc_mint_key: self.usdc_mint,
+            }
+            .data(),
+        };
+        send(&mut self.svm, &[ix], admin, &[&usdv_mint])
+    }
+
+    /// Create a funded user holding `usdc_amount` USDC
+    pub fn create_user(&mut self, usdc_amount: u64) -> Keypair {
+        let user = Keypair::new();
+        self.svm.airdrop(&user.pubkey(), 10_000_000_000).unwrap();
+
+        let create_ata = create_associated_token_account(
+            &user.pubkey(),
+            &user.pubkey(),
+            &self.usdc_mint,
+            &spl_token::ID,
+        );
+        send(&mut self.svm, &[create_ata], &user, &[]).unwrap();
+
+        if usdc_amount > 0 {
+            let user_usdc = self.usdc_ata(&user.pubkey());
+            self.mint_usdc(&user_usdc, usdc_amount);
+        }
+
+        user
+    }
+
+    /// Mint test USDC into `account`
+    pub fn mint_usdc(&mut self, account: &Pubkey, amount: u64) {
+        let usdc_mint = self.usdc_mint;
+        self.mint_tokens(&usdc_mint, account, amount);
+    }
+
+    /// Mint tokens of a mint created by [`create_mint`] with the admin as authority
+    pub fn mint_tokens(&mut self, mint: &Pubkey, account: &Pubkey, amount: u64) {
+        let ix = spl_token::instruction::mint_to(
+            &spl_token::ID,
+            mint,
+            account,
+            &self.admin.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap();
+        let admin = self.admin.insecure_clone();
+        send(&mut self.svm, &[ix], &admin, &[]).unwrap();
+    }
+
+    pub fn usdc_ata(&self, owner: &Pubkey) -> Pubkey {
+        derive_associated_token_account(owner, &self.usdc_mint)
+    }
+
+    pub fn usdv_ata(&self, owner: &Pubkey) -> Pubkey {
+        derive_associated_token_account(owner, &self.usdv_mint)
+    }
+
+    pub fn vault_usdc_account(&self) -> Pubkey {
+        derive_associated_token_account(&self.vault_authority, &self.usdc_mint)
+    }
+
+    pub fn deposit_and_mint(&mut self, user: &Keypair, amount: u64) -> TxResult {
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts: usdv_program::accounts::DepositAndMint {
+                program_state: self.program_state,
+                usdc_mint: self.usdc_mint,
+                usdv_mint: self.usdv_mint,
+                user_usdc_account: self.usdc_ata(&user.pubkey()),
+                user_usdv_account: self.usdv_ata(&user.pubkey()),
+                vault_usdc_account: self.vault_usdc_account(),
+                vault_authority: self.vault_authority,
+                user: user.pubkey(),
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+                allowlist_entry: self.existing_allowlist_entry(&user.pubkey()),
+            }
+            .to_account_metas(None),
+            data: usdv_program::instruction::DepositAndMint { amount }.data(),
+        };
+        send(&mut self.svm, &[ix], user, &[])
+    }
+
+    pub fn burn_and_withdraw(&mut self, user: &Keypair, amount: u64) -> TxResult {
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts: usdv_program::accounts::BurnAndWithdraw {
+                program_state: self.program_state,
+                usdv_mint: self.usdv_mint,
+                user_usdv_account: self.usdv_ata(&user.pubkey()),
+                user_usdc_account: self.usdc_ata(&user.pubkey()),
+                vault_usdc_account: self.vault_usdc_account(),
+                vault_authority: self.vault_authority,
+                user: user.pubkey(),
+                token_program: spl_token::ID,
+                allowlist_entry: self.existing_allowlist_entry(&user.pubkey()),
+            }
+            .to_account_metas(None),
+            data: usdv_program::instruction::BurnAndWithdraw { amount }.data(),
+        };
+        send(&mut self.svm, &[ix], user, &[])
+    }
+
+    pub fn update_program_state(
+        &mut self,
+        admin: &Keypair,
+        new_admin: Option<Pubkey>,
+    ) -> TxResult {
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts: usdv_program::accounts::UpdateProgramState {
+                program_state: self.program_state,
+                admin: admin.pubkey(),
+            }
+            .to_account_metas(None),
+            data: usdv_program::instruction::UpdateProgramState { new_admin }.data(),
+        };
+        send(&mut self.svm, &[ix], admin, &[])
+    }
+
+    pub fn initiate_wind_down(
+        &mut self,
+        admin: &Keypair,
+        claim_period_seconds: i64,
+    ) -> TxResult {
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts: usdv_program::accounts::InitiateWindDown {
+                program_state: self.program_state,
+                admin: admin.pubkey(),
+            }
+            .to_account_metas(None),
+            data: usdv_program::instruction::InitiateWindDown {
+                claim_period_seconds,
+            }
+            .data(),
+        };
+        send(&mut self.svm, &[ix], admin, &[])
+    }
+
+    pub fn sweep_unclaimed(&mut self, admin: &Keypair, recovery: &Pubkey) -> TxResult {
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts: usdv_program::accounts::SweepUnclaimed {
+                program_state: self.program_state,
+                vault_usdc_account: self.vault_usdc_account(),
+                recovery_usdc_account: *recovery,
+                vault_authority: self.vault_authority,
+                admin: admin.pubkey(),
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: usdv_program::instruction::SweepUnclaimed {}.data(),
+        };
+        send(&mut self.svm, &[ix], admin, &[])
+    }
+
+    pub fn recover_tokens(
+        &mut self,
+        admin: &Keypair,
+        source: &Pubkey,
+        destination: &Pubkey,
+    ) -> TxResult {
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts: usdv_program::accounts::RecoverTokens {
+                program_state: self.program_state,
+                source_token_account: *source,
+                destination_token_account: *destination,
+                vault_authority: self.vault_authority,
+                admin: admin.pubkey(),
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: usdv_program::instruction::RecoverTokens {}.data(),
+        };
+        send(&mut self.svm, &[ix], admin, &[])
+    }
+
+    pub fn minter_pda(&self, authority: &Pubkey) -> Pubkey {
+        derive_minter_pda(&usdv_program::ID, authority).0
+    }
+
+    pub fn configure_minter(
+        &mut self,
+        admin: &Keypair,
+        minter_authority: &Pubkey,
+        allowance: u64,
+    ) -> TxResult {
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts: usdv_program::accounts::ConfigureMinter {
+                program_state: self.program_state,
+                minter: self.minter_pda(minter_authority),
+                minter_authority: *minter_authority,
+                admin: admin.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: usdv_program::instruction::ConfigureMinter { allowance }.data(),
+        };
+        send(&mut self.svm, &[ix], admin, &[])
+    }
+
+    pub fn mint_allowance(
+        &mut self,
+        minter_authority: &Keypair,
+        destination: &Pubkey,
+        amount: u64,
+    ) -> TxResult {
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts: usdv_program::accounts::MintAllowance {
+                program_state: self.program_state,
+                minter: self.minter_pda(&minter_authority.pubkey()),
+                usdv_mint: self.usdv_mint,
+                destination_usdv_account: *destination,
+                vault_authority: self.vault_authority,
+                minter_authority: minter_authority.pubkey(),
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: usdv_program::instruction::MintAllowance { amount }.data(),
+        };
+        send(&mut self.svm, &[ix], minter_authority, &[])
+    }
+
+    pub fn burn_for_offchain_redemption(
+        &mut self,
+        minter_authority: &Keypair,
+        amount: u64,
+    ) -> TxResult {
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts: usdv_program::accounts::BurnForOffchainRedemption {
+                program_state: self.program_state,
+                minter: self.minter_pda(&minter_authority.pubkey()),
+                usdv_mint: self.usdv_mint,
+                minter_usdv_account: self.usdv_ata(&minter_authority.pubkey()),
+                minter_authority: minter_authority.pubkey(),
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: usdv_program::instruction::BurnForOffchainRedemption { amount }.data(),
+        };
+        send(&mut self.svm, &[ix], minter_authority, &[])
+    }
+
+    /// Create the USDv ATA of `owner`, paid by `owner`
+    pub fn create_usdv_ata(&mut self, owner: &Keypair) -> Pubkey {
+        let ix = create_associated_token_account(
+            &owner.pubkey(),
+            &owner.pubkey(),
+            &self.usdv_mint,
+            &spl_token::ID,
+        );
+        send(&mut self.svm, &[ix], owner, &[]).unwrap();
+        self.usdv_ata(&owner.pubkey())
+    }
+
+    /// Fetch and decode a minter account
+    pub fn minter(&self, authority: &Pubkey) -> Minter {
+        let account = self.svm.get_account(&self.minter_pda(authority)).unwrap();
+        Minter::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn allowlist_pda(&self, wallet: &Pubkey) -> Pubkey {
+        derive_allowlist_entry_pda(&usdv_program::ID, wallet).0
+    }
+
+    /// The wallet's allowlist entry, if it has one
+    pub fn existing_allowlist_entry(&self, wallet: &Pubkey) -> Option<Pubkey> {
+        let entry = self.allowlist_pda(wallet);
+        self.svm.get_account(&entry).map(|_| entry)
+    }
+
+    pub fn set_allowlist_mode(&mut self, admin: &Keypair, enabled: bool) -> TxResult {
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts: usdv_program::accounts::SetAllowlistMode {
+                program_state: self.program_state,
+                admin: admin.pubkey(),
+            }
+            .to_account_metas(None),
+            data: usdv_program::instruction::SetAllowlistMode { enabled }.data(),
+        };
+        send(&mut self.svm, &[ix], admin, &[])
+    }
+
+    pub fn update_allowlist(
+        &mut self,
+        admin: &Keypair,
+        updates: Vec<AllowlistUpdate>,
+    ) -> TxResult {
+        let mut accounts = usdv_program::accounts::UpdateAllowlist {
+            program_state: self.program_state,
+            admin: admin.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(
+            updates
+                .iter()
+                .map(|update| AccountMeta::new(self.allowlist_pda(update.wallet()), false)),
+        );
+        let ix = Instruction {
+            program_id: usdv_program::ID,
+            accounts,
+            data: usdv_program::instruction::UpdateAllowlist { updates }.data(),
+        };
+        send(&mut self.svm, &[ix], admin, &[])
+    }
+
+    /// Fetch and decode a wallet's allowlist entry, if any
+    pub fn allowlist_entry(&self, wallet: &Pubkey) -> Option<AllowlistEntry> {
+        self.svm.get_account(&self.allowlist_pda(wallet)).map(|account| {
+            AllowlistEntry::try_deserialize(&mut account.data.as_slice()).unwrap()
+        })
+    }
+
+    /// Current on-chain unix timestamp
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    /// Fetch and decode the program state account
+    pub fn program_state(&self) -> ProgramState {
+        let account = self.svm.get_account(&self.program_state).unwrap();
+        ProgramState::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Copy the program state to a fresh non-PDA address owned by the
+    /// program, with `admin` as its admin and an elapsed wind-down
+    pub fn forge_program_state(&mut self, admin: &Pubkey) -> Pubkey {
+        let mut account = self.svm.get_account(&self.program_state).unwrap();
+        let mut state: ProgramState = bytemuck::pod_read_unaligned(&account.data[8..]);
+        state.admin = *admin;
+        state.wound_down = 1;
+        state.wind_down_deadline = 0;
+        account.data[8..].copy_from_slice(bytemuck::bytes_of(&state));
+
+        let forged = Pubkey::new_unique();
+        self.svm.set_account(forged, account).unwrap();
+        forged
+    }
+
+    /// Token balance of an SPL token account (0 if it does not exist)
+    pub fn token_balance(&self, account: &Pubkey) -> u64 {
+        self.svm
+            .get_account(account)
+            .map(|a| spl_token::state::Account::unpack(&a.data).unwrap().amount)
+            .unwrap_or(0)
+    }
+
+    /// Move the on-chain clock forward by `seconds`
+    pub fn advance_clock(&mut self, seconds: i64) {
+        let mut clock: Clock = self.svm.get_sysvar();
+        clock.unix_timestamp += seconds;
+        clock.slot += 1;
+        self.svm.set_sysvar(&clock);
+        self.svm.expire_blockhash();
+    }
+}
+
+/// Create a 6-decimal mint with `authority` as mint authority
+pub fn create_mint(svm: &mut LiteSVM, authority: &Keypair) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    let state = spl_token::state::Mint {
+        mint_authority: COption::Some(authority.pubkey()),
+        supply: 0,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint::pack(state, &mut data).unwrap();
+    set_token_program_account(svm, mint, data);
+    mint
+}
+
+/// Create an SPL token account for `owner` at a fresh address
+pub fn create_token_account(svm: &mut LiteSVM, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    let account = Pubkey::new_unique();
+    let state = spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount: 0,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    };
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account::pack(state, &mut data).unwrap();
+    set_token_program_account(svm, account, data);
+    account
+}
+
+fn set_token_program_account(svm: &mut LiteSVM, address: Pubkey, data: Vec<u8>) {
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(address, account).unwrap();
+}
+
+/// Sign and send a transaction with `payer` as fee payer
+pub fn send(
+    svm: &mut LiteSVM,
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> TxResult {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx).map_err(Box::new);
+    svm.expire_blockhash();
+    result
+}
+
+/// Assert that a transaction failed with the given Anchor framework error
+pub fn assert_anchor_error(result: TxResult, expected: ErrorCode) {
+    let expected_code: u32 = expected.into();
+    match result {
+        Err(failed) => match failed.err {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+                assert_eq!(
+                    code, expected_code,
+                    "expected {} ({}), logs: {:#?}",
+                    expected, expected_code, failed.meta.logs
+                );
+            }
+            other => panic!("expected {} but got {:?}", expected, other),
+        },
+        Ok(_) => panic!("expected {} but transaction succeeded", expected),
+    }
+}
+
+/// Assert that a transaction failed with the given USDv program error
+pub fn assert_usdv_error(result: TxResult, expected: USDvError) {
+    let expected_code: u32 = expected.into();
+    match result {
+        Err(failed) => match failed.err {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+                assert_eq!(
+                    code, expected_code,
+                    "expected {} ({}), logs: {:#?}",
+                    expected, expected_code, failed.meta.logs
+                );
+            }
+            other => panic!("expected {} but got {:?}", expected, other),
+        },
+        Ok(_) => panic!("expected {} but transaction succeeded", expected),
+    }
+}
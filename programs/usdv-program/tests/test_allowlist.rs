@@ -0,0 +1,187 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::*;
+    use solana_sdk::signature::{Keypair, Signer};
+    use usdv_program::{AllowlistUpdate, ProgramState};
+    use usdv_results::USDvError;
+
+    fn add(wallet: &Keypair, expires_at: i64) -> AllowlistUpdate {
+        AllowlistUpdate::Add {
+            wallet: wallet.pubkey(),
+            expires_at,
+        }
+    }
+
+    fn remove(wallet: &Keypair) -> AllowlistUpdate {
+        AllowlistUpdate::Remove {
+            wallet: wallet.pubkey(),
+        }
+    }
+
+    /// Deployment in allowlist mode
+    fn allowlisted_env() -> TestEnv {
+        let mut env = TestEnv::new();
+        let admin = env.admin.insecure_clone();
+        env.set_allowlist_mode(&admin, true).unwrap();
+        env
+    }
+
+    #[test]
+    fn test_allowlist_mode_disabled_by_default() {
+        let mut env = TestEnv::new();
+        let user = env.create_user(10 * ONE_TOKEN);
+
+        assert!(!env.program_state().is_allowlist_enabled());
+        env.deposit_and_mint(&user, 10 * ONE_TOKEN).unwrap();
+        env.burn_and_withdraw(&user, 10 * ONE_TOKEN).unwrap();
+    }
+
+    #[test]
+    fn test_set_allowlist_mode_requires_admin() {
+        let mut env = TestEnv::new();
+        let attacker = env.create_user(0);
+
+        let result = env.set_allowlist_mode(&attacker, true);
+        assert_usdv_error(result, USDvError::Unauthorized);
+    }
+
+    #[test]
+    fn test_unlisted_wallet_cannot_mint() {
+        let mut env = allowlisted_env();
+        let user = env.create_user(10 * ONE_TOKEN);
+
+        let result = env.deposit_and_mint(&user, 10 * ONE_TOKEN);
+        assert_usdv_error(result, USDvError::NotAllowlisted);
+    }
+
+    #[test]
+    fn test_listed_wallet_can_mint_and_redeem() {
+        let mut env = allowlisted_env();
+        let admin = env.admin.insecure_clone();
+        let user = env.create_user(10 * ONE_TOKEN);
+
+        env.update_allowlist(&admin, vec![add(&user, 0)]).unwrap();
+
+        env.deposit_and_mint(&user, 10 * ONE_TOKEN).unwrap();
+        env.burn_and_withdraw(&user, 10 * ONE_TOKEN).unwrap();
+    }
+
+    #[test]
+    fn test_unlisted_holder_cannot_redeem() {
+        let mut env = TestEnv::new();
+        let admin = env.admin.insecure_clone();
+        let user = env.create_user(10 * ONE_TOKEN);
+        env.deposit_and_mint(&user, 10 * ONE_TOKEN).unwrap();
+
+        env.set_allowlist_mode(&admin, true).unwrap();
+
+        let result = env.burn_and_withdraw(&user, 10 * ONE_TOKEN);
+        assert_usdv_error(result, USDvError::NotAllowlisted);
+    }
+
+    #[test]
+    fn test_expired_entry_rejected() {
+        let mut env = allowlisted_env();
+        let admin = env.admin.insecure_clone();
+        let user = env.create_user(10 * ONE_TOKEN);
+
+        let expires_at = env.now() + 3_600;
+        env.update_allowlist(&admin, vec![add(&user, expires_at)]).unwrap();
+        env.deposit_and_mint(&user, 5 * ONE_TOKEN).unwrap();
+
+        env.advance_clock(3_600);
+        let result = env.deposit_and_mint(&user, 5 * ONE_TOKEN);
+        assert_usdv_error(result, USDvError::AllowlistEntryExpired);
+        let result = env.burn_and_withdraw(&user, 5 * ONE_TOKEN);
+        assert_usdv_error(result, USDvError::AllowlistEntryExpired);
+
+        // Re-adding the wallet extends the existing entry
+        env.update_allowlist(&admin, vec![add(&user, 0)]).unwrap();
+        assert_eq!(env.allowlist_entry(&user.pubkey()).unwrap().expires_at, 0);
+        env.burn_and_withdraw(&user, 5 * ONE_TOKEN).unwrap();
+    }
+
+    #[test]
+    fn test_bulk_add_and_remove() {
+        let mut env = allowlisted_env();
+        let admin = env.admin.insecure_clone();
+        let wallets: Vec<Keypair> = (0..10).map(|_| Keypair::new()).collect();
+
+        let updates = wallets.iter().map(|wallet| add(wallet, 0)).collect();
+        env.update_allowlist(&admin, updates).unwrap();
+        for wallet in &wallets {
+            let entry = env.allowlist_entry(&wallet.pubkey()).unwrap();
+            assert_eq!(entry.wallet, wallet.pubkey());
+        }
+
+        // Mixed batch: remove half, re-add one with an expiry
+        let mut updates: Vec<AllowlistUpdate> = wallets[..5].iter().map(remove).collect();
+        updates.push(add(&wallets[9], 42));
+        env.update_allowlist(&admin, updates).unwrap();
+
+        for wallet in &wallets[..5] {
+            assert!(env.allowlist_entry(&wallet.pubkey()).is_none());
+        }
+        assert_eq!(env.allowlist_entry(&wallets[9].pubkey()).unwrap().expires_at, 42);
+    }
+
+    #[test]
+    fn test_add_wallet_with_prefunded_entry() {
+        let mut env = allowlisted_env();
+        let admin = env.admin.insecure_clone();
+        let user = env.create_user(10 * ONE_TOKEN);
+
+        // Anyone can send lamports to the entry address before it exists
+        let entry = env.allowlist_pda(&user.pubkey());
+        env.svm.airdrop(&entry, 1_000).unwrap();
+
+        env.update_allowlist(&admin, vec![add(&user, 0)]).unwrap();
+        let account = env.svm.get_account(&entry).unwrap();
+        assert_eq!(account.owner, usdv_program::ID);
+        assert_eq!(env.allowlist_entry(&user.pubkey()).unwrap().wallet, user.pubkey());
+
+        env.deposit_and_mint(&user, 10 * ONE_TOKEN).unwrap();
+    }
+
+    #[test]
+    fn test_removed_wallet_cannot_mint() {
+        let mut env = allowlisted_env();
+        let admin = env.admin.insecure_clone();
+        let user = env.create_user(10 * ONE_TOKEN);
+
+        env.update_allowlist(&admin, vec![add(&user, 0)]).unwrap();
+        env.update_allowlist(&admin, vec![remove(&user)]).unwrap();
+
+        let result = env.deposit_and_mint(&user, 10 * ONE_TOKEN);
+        assert_usdv_error(result, USDvError::NotAllowlisted);
+    }
+
+    #[test]
+    fn test_update_allowlist_requires_admin() {
+        let mut env = allowlisted_env();
+        let attacker = env.create_user(10 * ONE_TOKEN);
+
+        let result = env.update_allowlist(&attacker, vec![add(&attacker, 0)]);
+        assert_usdv_error(result, USDvError::Unauthorized);
+        assert!(env.allowlist_entry(&attacker.pubkey()).is_none());
+    }
+
+    #[test]
+    fn test_redemptions_open_to_all_after_wind_down() {
+        let mut env = TestEnv::new();
+        let admin = env.admin.insecure_clone();
+        let user = env.create_user(10 * ONE_TOKEN);
+        env.deposit_and_mint(&user, 10 * ONE_TOKEN).unwrap();
+
+        env.set_allowlist_mode(&admin, true).unwrap();
+        env.initiate_wind_down(&admin, ProgramState::MIN_WIND_DOWN_CLAIM_PERIOD)
+            .unwrap();
+
+        env.burn_and_withdraw(&user, 10 * ONE_TOKEN).unwrap();
+    }
+}
@@ -0,0 +1,172 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+/// Compute-unit budgets per instruction
+///
+/// A change that pushes an instruction past its budget fails the test
+/// suite. Raise a budget only together with the change that justifies it.
+#[cfg(test)]
+mod budgets {
+    pub const DEPOSIT_AND_MINT: u64 = 45_000;
+    pub const DEPOSIT_AND_MINT_CREATING_ATA: u64 = 80_000;
+    pub const DEPOSIT_AND_MINT_ALLOWLISTED: u64 = 50_000;
+    pub const BURN_AND_WITHDRAW: u64 = 35_000;
+    pub const UPDATE_PROGRAM_STATE: u64 = 8_000;
+    pub const INITIATE_WIND_DOWN: u64 = 15_000;
+    pub const SWEEP_UNCLAIMED: u64 = 30_000;
+    pub const RECOVER_TOKENS: u64 = 35_000;
+    pub const CONFIGURE_MINTER: u64 = 30_000;
+    pub const MINT_ALLOWANCE: u64 = 30_000;
+    pub const BURN_FOR_OFFCHAIN_REDEMPTION: u64 = 25_000;
+    pub const SET_ALLOWLIST_MODE: u64 = 10_000;
+    pub const UPDATE_ALLOWLIST_SINGLE_ADD: u64 = 30_000;
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::budgets;
+    use super::common::*;
+    use solana_sdk::signature::{Keypair, Signer};
+    use usdv_program::{AllowlistUpdate, ProgramState};
+
+    /// Assert that a successful transaction stayed within `budget`
+    fn assert_within_budget(instruction: &str, result: TxResult, budget: u64) {
+        let consumed = result.unwrap().compute_units_consumed;
+        println!("{instruction}: {consumed} CU (budget {budget})");
+        assert!(
+            consumed <= budget,
+            "{instruction} consumed {consumed} CU, over its budget of {budget}"
+        );
+    }
+
+    /// Deployment with one holder that already minted 100 USDv
+    fn env_with_holder() -> (TestEnv, Keypair) {
+        let mut env = TestEnv::new();
+        let holder = env.create_user(200 * ONE_TOKEN);
+        env.deposit_and_mint(&holder, 100 * ONE_TOKEN).unwrap();
+        (env, holder)
+    }
+
+    #[test]
+    fn test_deposit_and_mint_compute_units() {
+        let mut env = TestEnv::new();
+        let user = env.create_user(200 * ONE_TOKEN);
+
+        // First deposit also creates the user's USDv ATA and the vault ATA
+        let result = env.deposit_and_mint(&user, 100 * ONE_TOKEN);
+        assert_within_budget(
+            "deposit_and_mint (creating ATAs)",
+            result,
+            budgets::DEPOSIT_AND_MINT_CREATING_ATA,
+        );
+
+        let result = env.deposit_and_mint(&user, 100 * ONE_TOKEN);
+        assert_within_budget("deposit_and_mint", result, budgets::DEPOSIT_AND_MINT);
+    }
+
+    #[test]
+    fn test_deposit_and_mint_allowlisted_compute_units() {
+        let (mut env, holder) = env_with_holder();
+        let admin = env.admin.insecure_clone();
+        env.set_allowlist_mode(&admin, true).unwrap();
+        env.update_allowlist(
+            &admin,
+            vec![AllowlistUpdate::Add {
+                wallet: holder.pubkey(),
+                expires_at: 0,
+            }],
+        )
+        .unwrap();
+
+        let result = env.deposit_and_mint(&holder, 100 * ONE_TOKEN);
+        assert_within_budget(
+            "deposit_and_mint (allowlist mode)",
+            result,
+            budgets::DEPOSIT_AND_MINT_ALLOWLISTED,
+        );
+    }
+
+    #[test]
+    fn test_burn_and_withdraw_compute_units() {
+        let (mut env, holder) = env_with_holder();
+
+        let result = env.burn_and_withdraw(&holder, 50 * ONE_TOKEN);
+        assert_within_budget("burn_and_withdraw", result, budgets::BURN_AND_WITHDRAW);
+    }
+
+    #[test]
+    fn test_admin_compute_units() {
+        let (mut env, _) = env_with_holder();
+        let admin = env.admin.insecure_clone();
+
+        let result = env.update_program_state(&admin, None);
+        assert_within_budget("update_program_state", result, budgets::UPDATE_PROGRAM_STATE);
+
+        let result = env.set_allowlist_mode(&admin, true);
+        assert_within_budget("set_allowlist_mode", result, budgets::SET_ALLOWLIST_MODE);
+
+        let result = env.update_allowlist(
+            &admin,
+            vec![AllowlistUpdate::Add {
+                wallet: Keypair::new().pubkey(),
+                expires_at: 0,
+            }],
+        );
+        assert_within_budget(
+            "update_allowlist (single add)",
+            result,
+            budgets::UPDATE_ALLOWLIST_SINGLE_ADD,
+        );
+    }
+
+    #[test]
+    fn test_wind_down_compute_units() {
+        let (mut env, _) = env_with_holder();
+        let admin = env.admin.insecure_clone();
+
+        let result = env.initiate_wind_down(&admin, ProgramState::MIN_WIND_DOWN_CLAIM_PERIOD);
+        assert_within_budget("initiate_wind_down", result, budgets::INITIATE_WIND_DOWN);
+
+        let recovery = create_token_account(&mut env.svm, &env.usdc_mint, &admin.pubkey());
+        env.advance_clock(ProgramState::MIN_WIND_DOWN_CLAIM_PERIOD + 1);
+
+        let result = env.sweep_unclaimed(&admin, &recovery);
+        assert_within_budget("sweep_unclaimed", result, budgets::SWEEP_UNCLAIMED);
+    }
+
+    #[test]
+    fn test_recover_tokens_compute_units() {
+        let mut env = TestEnv::new();
+        let admin = env.admin.insecure_clone();
+        let stray_mint = create_mint(&mut env.svm, &admin);
+        let stray_account = create_token_account(&mut env.svm, &stray_mint, &env.vault_authority);
+        env.mint_tokens(&stray_mint, &stray_account, 50 * ONE_TOKEN);
+        let destination = create_token_account(&mut env.svm, &stray_mint, &admin.pubkey());
+
+        let result = env.recover_tokens(&admin, &stray_account, &destination);
+        assert_within_budget("recover_tokens", result, budgets::RECOVER_TOKENS);
+    }
+
+    #[test]
+    fn test_minter_compute_units() {
+        let mut env = TestEnv::new();
+        let admin = env.admin.insecure_clone();
+        let minter = env.create_user(0);
+        let destination = env.create_usdv_ata(&minter);
+
+        let result = env.configure_minter(&admin, &minter.pubkey(), 1_000 * ONE_TOKEN);
+        assert_within_budget("configure_minter", result, budgets::CONFIGURE_MINTER);
+
+        let result = env.mint_allowance(&minter, &destination, 100 * ONE_TOKEN);
+        assert_within_budget("mint_allowance", result, budgets::MINT_ALLOWANCE);
+
+        let result = env.burn_for_offchain_redemption(&minter, 40 * ONE_TOKEN);
+        assert_within_budget(
+            "burn_for_offchain_redemption",
+            result,
+            budgets::BURN_FOR_OFFCHAIN_REDEMPTION,
+        );
+    }
+}
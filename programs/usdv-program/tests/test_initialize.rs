@@ -0,0 +1,33 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::*;
+    use solana_sdk::signature::Signer;
+    use usdv_utils::pda::derive_program_state_pda;
+
+    #[test]
+    fn test_program_state_is_singleton_pda() {
+        let env = TestEnv::new();
+
+        assert_eq!(env.program_state, derive_program_state_pda(&usdv_program::ID).0);
+
+        let state = env.program_state();
+        assert!(state.is_initialized());
+        assert_eq!(state.admin, env.admin.pubkey());
+        assert_eq!(state.usdv_mint, env.usdv_mint);
+    }
+
+    #[test]
+    fn test_second_initialize_rejected() {
+        let mut env = TestEnv::new();
+        let attacker = env.create_user(0);
+
+        // A second state (and with it a second admin) can never be created
+        assert!(env.initialize(&attacker).is_err());
+        assert_eq!(env.program_state().admin, env.admin.pubkey());
+    }
+}
@@ -0,0 +1,213 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::*;
+    use solana_sdk::signature::{Keypair, Signer};
+    use usdv_program::ProgramState;
+    use usdv_results::USDvError;
+    use usdv_utils::MAX_TOTAL_SUPPLY;
+
+    /// Deployment with a registered minter holding a 1,000 USDv allowance
+    fn env_with_minter() -> (TestEnv, Keypair) {
+        let mut env = TestEnv::new();
+        let admin = env.admin.insecure_clone();
+        let minter = env.create_user(0);
+        env.create_usdv_ata(&minter);
+        env.configure_minter(&admin, &minter.pubkey(), 1_000 * ONE_TOKEN)
+            .unwrap();
+        (env, minter)
+    }
+
+    #[test]
+    fn test_configure_minter() {
+        let (mut env, minter) = env_with_minter();
+        let admin = env.admin.insecure_clone();
+
+        let info = env.minter(&minter.pubkey());
+        assert_eq!(info.authority, minter.pubkey());
+        assert_eq!(info.allowance, 1_000 * ONE_TOKEN);
+
+        // Reconfiguring replaces the remaining allowance
+        env.configure_minter(&admin, &minter.pubkey(), 250 * ONE_TOKEN)
+            .unwrap();
+        assert_eq!(env.minter(&minter.pubkey()).allowance, 250 * ONE_TOKEN);
+    }
+
+    #[test]
+    fn test_configure_minter_requires_admin() {
+        let mut env = TestEnv::new();
+        let attacker = env.create_user(0);
+
+        let result = env.configure_minter(&attacker, &attacker.pubkey(), u64::MAX);
+        assert_usdv_error(result, USDvError::Unauthorized);
+    }
+
+    #[test]
+    fn test_mint_allowance_tracks_offchain_deposits() {
+        let (mut env, minter) = env_with_minter();
+        let depositor = env.create_user(100 * ONE_TOKEN);
+        env.deposit_and_mint(&depositor, 100 * ONE_TOKEN).unwrap();
+
+        let destination = env.usdv_ata(&minter.pubkey());
+        env.mint_allowance(&minter, &destination, 400 * ONE_TOKEN)
+            .unwrap();
+
+        assert_eq!(env.token_balance(&destination), 400 * ONE_TOKEN);
+        assert_eq!(env.minter(&minter.pubkey()).allowance, 600 * ONE_TOKEN);
+        assert_eq!(env.minter(&minter.pubkey()).total_minted, 400 * ONE_TOKEN);
+
+        // On-chain and off-chain backed supply are reported separately
+        let state = env.program_state();
+        assert_eq!(state.total_usdv_supply, 500 * ONE_TOKEN);
+        assert_eq!(state.total_usdc_deposits, 100 * ONE_TOKEN);
+        assert_eq!(state.total_offchain_deposits, 400 * ONE_TOKEN);
+        assert_eq!(state.onchain_backed_supply(), 100 * ONE_TOKEN);
+        assert!(state.is_peg_maintained());
+
+        // No USDC moved into the vault for the allowance mint
+        assert_eq!(env.token_balance(&env.vault_usdc_account()), 100 * ONE_TOKEN);
+    }
+
+    #[test]
+    fn test_mint_allowance_can_mint_to_client_account() {
+        let (mut env, minter) = env_with_minter();
+        let client = env.create_user(0);
+        let destination = env.create_usdv_ata(&client);
+
+        env.mint_allowance(&minter, &destination, 10 * ONE_TOKEN)
+            .unwrap();
+        assert_eq!(env.token_balance(&destination), 10 * ONE_TOKEN);
+    }
+
+    #[test]
+    fn test_mint_allowance_exceeded() {
+        let (mut env, minter) = env_with_minter();
+        let destination = env.usdv_ata(&minter.pubkey());
+
+        env.mint_allowance(&minter, &destination, 1_000 * ONE_TOKEN)
+            .unwrap();
+        let result = env.mint_allowance(&minter, &destination, 1);
+        assert_usdv_error(result, USDvError::MinterAllowanceExceeded);
+    }
+
+    #[test]
+    fn test_mint_allowance_rejects_unregistered_minter() {
+        let (mut env, _) = env_with_minter();
+        let stranger = env.create_user(0);
+        let destination = env.create_usdv_ata(&stranger);
+
+        // No minter PDA exists for the stranger
+        let result = env.mint_allowance(&stranger, &destination, ONE_TOKEN);
+        assert!(result.is_err());
+        assert_eq!(env.token_balance(&destination), 0);
+    }
+
+    #[test]
+    fn test_mint_allowance_respects_supply_limit() {
+        let mut env = TestEnv::new();
+        let admin = env.admin.insecure_clone();
+        let minter = env.create_user(0);
+        let destination = env.create_usdv_ata(&minter);
+        env.configure_minter(&admin, &minter.pubkey(), u64::MAX)
+            .unwrap();
+
+        let result = env.mint_allowance(&minter, &destination, MAX_TOTAL_SUPPLY + 1);
+        assert_usdv_error(result, USDvError::SupplyLimitExceeded);
+
+        env.mint_allowance(&minter, &destination, MAX_TOTAL_SUPPLY)
+            .unwrap();
+        let result = env.mint_allowance(&minter, &destination, 1);
+        assert_usdv_error(result, USDvError::SupplyLimitExceeded);
+        assert_eq!(env.program_state().total_usdv_supply, MAX_TOTAL_SUPPLY);
+    }
+
+    #[test]
+    fn test_offchain_minted_usdv_limited_to_onchain_deposits() {
+        let (mut env, minter) = env_with_minter();
+        let depositor = env.create_user(100 * ONE_TOKEN);
+        env.deposit_and_mint(&depositor, 100 * ONE_TOKEN).unwrap();
+
+        let destination = env.usdv_ata(&minter.pubkey());
+        env.mint_allowance(&minter, &destination, 400 * ONE_TOKEN)
+            .unwrap();
+
+        // USDC sent straight to the vault covers the burn, but only the 100
+        // USDC deposited on-chain can be paid out against USDv
+        let vault = env.vault_usdc_account();
+        env.mint_usdc(&vault, 300 * ONE_TOKEN);
+        let result = env.burn_and_withdraw(&minter, 400 * ONE_TOKEN);
+        assert_usdv_error(result, USDvError::InsufficientOnchainDeposits);
+        assert_eq!(env.token_balance(&destination), 400 * ONE_TOKEN);
+        assert_eq!(env.token_balance(&vault), 400 * ONE_TOKEN);
+
+        let state = env.program_state();
+        assert_eq!(state.total_usdc_deposits, 100 * ONE_TOKEN);
+        assert_eq!(state.total_offchain_deposits, 400 * ONE_TOKEN);
+
+        env.burn_and_withdraw(&depositor, 100 * ONE_TOKEN).unwrap();
+        assert_eq!(env.program_state().total_usdc_deposits, 0);
+    }
+
+    #[test]
+    fn test_burn_for_offchain_redemption() {
+        let (mut env, minter) = env_with_minter();
+        let destination = env.usdv_ata(&minter.pubkey());
+        env.mint_allowance(&minter, &destination, 400 * ONE_TOKEN)
+            .unwrap();
+
+        env.burn_for_offchain_redemption(&minter, 150 * ONE_TOKEN)
+            .unwrap();
+
+        assert_eq!(env.token_balance(&destination), 250 * ONE_TOKEN);
+        assert_eq!(env.minter(&minter.pubkey()).total_burned, 150 * ONE_TOKEN);
+
+        let state = env.program_state();
+        assert_eq!(state.total_usdv_supply, 250 * ONE_TOKEN);
+        assert_eq!(state.total_offchain_deposits, 250 * ONE_TOKEN);
+        assert_eq!(state.total_usdc_deposits, 0);
+    }
+
+    #[test]
+    fn test_burn_for_offchain_redemption_limited_to_offchain_deposits() {
+        let (mut env, minter) = env_with_minter();
+
+        // The minter also holds on-chain backed USDv from a regular deposit
+        let minter_usdc = env.usdc_ata(&minter.pubkey());
+        env.mint_usdc(&minter_usdc, 100 * ONE_TOKEN);
+        env.deposit_and_mint(&minter, 100 * ONE_TOKEN).unwrap();
+        let destination = env.usdv_ata(&minter.pubkey());
+        env.mint_allowance(&minter, &destination, 10 * ONE_TOKEN).unwrap();
+
+        let result = env.burn_for_offchain_redemption(&minter, 11 * ONE_TOKEN);
+        assert_usdv_error(result, USDvError::InsufficientOffchainDeposits);
+        assert_eq!(env.program_state().total_offchain_deposits, 10 * ONE_TOKEN);
+    }
+
+    #[test]
+    fn test_minters_after_wind_down() {
+        let (mut env, minter) = env_with_minter();
+        let admin = env.admin.insecure_clone();
+        let destination = env.usdv_ata(&minter.pubkey());
+        env.mint_allowance(&minter, &destination, 100 * ONE_TOKEN)
+            .unwrap();
+
+        env.initiate_wind_down(&admin, ProgramState::MIN_WIND_DOWN_CLAIM_PERIOD)
+            .unwrap();
+
+        // Minting and raising allowances are disabled
+        let result = env.mint_allowance(&minter, &destination, ONE_TOKEN);
+        assert_usdv_error(result, USDvError::WindDownActive);
+        let result = env.configure_minter(&admin, &minter.pubkey(), 1);
+        assert_usdv_error(result, USDvError::WindDownActive);
+
+        // Revoking and redeeming stay available
+        env.configure_minter(&admin, &minter.pubkey(), 0).unwrap();
+        env.burn_for_offchain_redemption(&minter, 100 * ONE_TOKEN)
+            .unwrap();
+        assert_eq!(env.program_state().total_usdv_supply, 0);
+    }
+}
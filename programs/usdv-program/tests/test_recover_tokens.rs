@@ -0,0 +1,129 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::*;
+    use anchor_lang::error::ErrorCode;
+    use solana_sdk::{pubkey::Pubkey, signature::Signer};
+    use usdv_program::ProgramState;
+    use usdv_results::USDvError;
+
+    /// Deployment with 100 USDC of collateral and 50 stray tokens of another
+    /// mint sitting in an account owned by the vault authority
+    fn env_with_stray_tokens() -> (TestEnv, Pubkey, Pubkey) {
+        let mut env = TestEnv::new();
+        let holder = env.create_user(100 * ONE_TOKEN);
+        env.deposit_and_mint(&holder, 100 * ONE_TOKEN).unwrap();
+
+        let admin = env.admin.insecure_clone();
+        let stray_mint = create_mint(&mut env.svm, &admin);
+        let stray_account =
+            create_token_account(&mut env.svm, &stray_mint, &env.vault_authority);
+        env.mint_tokens(&stray_mint, &stray_account, 50 * ONE_TOKEN);
+
+        (env, stray_mint, stray_account)
+    }
+
+    #[test]
+    fn test_recover_tokens() {
+        let (mut env, stray_mint, stray_account) = env_with_stray_tokens();
+        let admin = env.admin.insecure_clone();
+        let destination = create_token_account(&mut env.svm, &stray_mint, &admin.pubkey());
+
+        let meta = env.recover_tokens(&admin, &stray_account, &destination).unwrap();
+
+        assert_eq!(env.token_balance(&stray_account), 0);
+        assert_eq!(env.token_balance(&destination), 50 * ONE_TOKEN);
+
+        // Collateral and accounting are untouched
+        assert_eq!(env.token_balance(&env.vault_usdc_account()), 100 * ONE_TOKEN);
+        assert_eq!(env.program_state().total_usdc_deposits, 100 * ONE_TOKEN);
+
+        // The recovery is logged as an event for audit
+        assert!(meta.logs.iter().any(|log| log.starts_with("Program data: ")));
+    }
+
+    #[test]
+    fn test_recover_tokens_rejects_usdc_vault_ata() {
+        let (mut env, _, _) = env_with_stray_tokens();
+        let admin = env.admin.insecure_clone();
+        let destination = create_token_account(&mut env.svm, &env.usdc_mint, &admin.pubkey());
+
+        let vault = env.vault_usdc_account();
+        let result = env.recover_tokens(&admin, &vault, &destination);
+        assert_usdv_error(result, USDvError::CollateralNotRecoverable);
+        assert_eq!(env.token_balance(&vault), 100 * ONE_TOKEN);
+    }
+
+    #[test]
+    fn test_recover_tokens_rejects_other_collateral_accounts() {
+        let (mut env, _, _) = env_with_stray_tokens();
+        let admin = env.admin.insecure_clone();
+
+        // A non-ATA USDC account owned by the vault authority is still collateral
+        let side_vault =
+            create_token_account(&mut env.svm, &env.usdc_mint, &env.vault_authority);
+        env.mint_usdc(&side_vault, 10 * ONE_TOKEN);
+        let destination = create_token_account(&mut env.svm, &env.usdc_mint, &admin.pubkey());
+
+        let result = env.recover_tokens(&admin, &side_vault, &destination);
+        assert_usdv_error(result, USDvError::CollateralNotRecoverable);
+        assert_eq!(env.token_balance(&side_vault), 10 * ONE_TOKEN);
+    }
+
+    #[test]
+    fn test_recover_tokens_requires_admin() {
+        let (mut env, stray_mint, stray_account) = env_with_stray_tokens();
+        let attacker = env.create_user(0);
+        let destination = create_token_account(&mut env.svm, &stray_mint, &attacker.pubkey());
+
+        let result = env.recover_tokens(&attacker, &stray_account, &destination);
+        assert_usdv_error(result, USDvError::Unauthorized);
+        assert_eq!(env.token_balance(&stray_account), 50 * ONE_TOKEN);
+    }
+
+    #[test]
+    fn test_recover_tokens_rejects_forged_program_state() {
+        let (mut env, stray_mint, stray_account) = env_with_stray_tokens();
+        let attacker = env.create_user(0);
+        let destination = create_token_account(&mut env.svm, &stray_mint, &attacker.pubkey());
+
+        env.program_state = env.forge_program_state(&attacker.pubkey());
+        let result = env.recover_tokens(&attacker, &stray_account, &destination);
+        assert_anchor_error(result, ErrorCode::ConstraintSeeds);
+        assert_eq!(env.token_balance(&stray_account), 50 * ONE_TOKEN);
+    }
+
+    #[test]
+    fn test_recover_tokens_rejects_empty_account() {
+        let (mut env, stray_mint, stray_account) = env_with_stray_tokens();
+        let admin = env.admin.insecure_clone();
+        let destination = create_token_account(&mut env.svm, &stray_mint, &admin.pubkey());
+
+        env.recover_tokens(&admin, &stray_account, &destination).unwrap();
+        let result = env.recover_tokens(&admin, &stray_account, &destination);
+        assert_usdv_error(result, USDvError::InvalidAmount);
+    }
+
+    #[test]
+    fn test_recover_tokens_after_wind_down() {
+        let (mut env, stray_mint, stray_account) = env_with_stray_tokens();
+        let admin = env.admin.insecure_clone();
+        env.initiate_wind_down(&admin, ProgramState::MIN_WIND_DOWN_CLAIM_PERIOD)
+            .unwrap();
+
+        let destination = create_token_account(&mut env.svm, &stray_mint, &admin.pubkey());
+        env.recover_tokens(&admin, &stray_account, &destination).unwrap();
+        assert_eq!(env.token_balance(&destination), 50 * ONE_TOKEN);
+
+        // Collateral stays locked for holders during wind-down
+        let usdc_destination =
+            create_token_account(&mut env.svm, &env.usdc_mint, &admin.pubkey());
+        let vault = env.vault_usdc_account();
+        let result = env.recover_tokens(&admin, &vault, &usdc_destination);
+        assert_usdv_error(result, USDvError::CollateralNotRecoverable);
+    }
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::*;
+    use anchor_lang::AnchorDeserialize;
+
+    fn returned_amount(result: TxResult) -> u64 {
+        let meta = result.unwrap();
+        assert_eq!(meta.return_data.program_id, usdv_program::ID);
+        u64::try_from_slice(&meta.return_data.data).unwrap()
+    }
+
+    #[test]
+    fn test_deposit_and_mint_returns_minted_amount() {
+        let mut env = TestEnv::new();
+        let user = env.create_user(100 * ONE_TOKEN);
+
+        let minted = returned_amount(env.deposit_and_mint(&user, 75 * ONE_TOKEN));
+        assert_eq!(minted, 75 * ONE_TOKEN);
+    }
+
+    #[test]
+    fn test_burn_and_withdraw_returns_paid_out_amount() {
+        let mut env = TestEnv::new();
+        let user = env.create_user(100 * ONE_TOKEN);
+        env.deposit_and_mint(&user, 100 * ONE_TOKEN).unwrap();
+
+        let paid_out = returned_amount(env.burn_and_withdraw(&user, 40 * ONE_TOKEN));
+        assert_eq!(paid_out, 40 * ONE_TOKEN);
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::*;
+    use solana_sdk::signature::Signer;
+    use usdv_results::USDvError;
+    use usdv_utils::MIN_DEPOSIT_AMOUNT;
+
+    #[test]
+    fn test_deposit_below_minimum_rejected() {
+        let mut env = TestEnv::new();
+        let user = env.create_user(ONE_TOKEN);
+
+        let result = env.deposit_and_mint(&user, MIN_DEPOSIT_AMOUNT - 1);
+        assert_usdv_error(result, USDvError::InvalidAmount);
+
+        env.deposit_and_mint(&user, MIN_DEPOSIT_AMOUNT).unwrap();
+    }
+
+    #[test]
+    fn test_zero_burn_rejected() {
+        let mut env = TestEnv::new();
+        let user = env.create_user(10 * ONE_TOKEN);
+        env.deposit_and_mint(&user, 10 * ONE_TOKEN).unwrap();
+
+        let result = env.burn_and_withdraw(&user, 0);
+        assert_usdv_error(result, USDvError::InvalidAmount);
+    }
+
+    #[test]
+    fn test_deposit_with_foreign_usdc_mint_rejected() {
+        let mut env = TestEnv::new();
+        let admin = env.admin.insecure_clone();
+
+        // A look-alike mint the user holds, with its own vault ATA
+        env.usdc_mint = create_mint(&mut env.svm, &admin);
+        let user = env.create_user(10 * ONE_TOKEN);
+
+        let result = env.deposit_and_mint(&user, 10 * ONE_TOKEN);
+        assert_usdv_error(result, USDvError::InvalidUSDCMint);
+        assert_eq!(env.token_balance(&env.usdc_ata(&user.pubkey())), 10 * ONE_TOKEN);
+    }
+
+    #[test]
+    fn test_deposit_over_balance_uses_usdc_error() {
+        let mut env = TestEnv::new();
+        let user = env.create_user(10 * ONE_TOKEN);
+
+        let result = env.deposit_and_mint(&user, 20 * ONE_TOKEN);
+        assert_usdv_error(result, USDvError::InsufficientUSDCBalance);
+    }
+
+    #[test]
+    fn test_burn_over_balance_uses_usdv_error() {
+        let mut env = TestEnv::new();
+        let user = env.create_user(10 * ONE_TOKEN);
+        env.deposit_and_mint(&user, 10 * ONE_TOKEN).unwrap();
+
+        let result = env.burn_and_withdraw(&user, 20 * ONE_TOKEN);
+        assert_usdv_error(result, USDvError::InsufficientUSDvBalance);
+    }
+}
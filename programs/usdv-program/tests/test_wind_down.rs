@@ -0,0 +1,201 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+
+    use super::common::*;
+    use anchor_lang::error::ErrorCode;
+    use solana_sdk::signature::{Keypair, Signer};
+    use usdv_program::ProgramState;
+    use usdv_results::USDvError;
+    use usdv_utils::pda::derive_program_state_pda;
+
+    const CLAIM_PERIOD: i64 = ProgramState::MIN_WIND_DOWN_CLAIM_PERIOD;
+
+    /// Deployment with one holder that minted 100 USDv, already in wind-down
+    fn wound_down_env() -> (TestEnv, Keypair) {
+        let mut env = TestEnv::new();
+        let holder = env.create_user(100 * ONE_TOKEN);
+        env.deposit_and_mint(&holder, 100 * ONE_TOKEN).unwrap();
+
+        let admin = env.admin.insecure_clone();
+        env.initiate_wind_down(&admin, CLAIM_PERIOD).unwrap();
+
+        (env, holder)
+    }
+
+    #[test]
+    fn test_initiate_wind_down() {
+        let mut env = TestEnv::new();
+        let admin = env.admin.insecure_clone();
+
+        env.initiate_wind_down(&admin, CLAIM_PERIOD).unwrap();
+
+        let state = env.program_state();
+        assert!(state.is_wound_down());
+        assert!(!state.is_minting_enabled());
+        assert_eq!(
+            state.wind_down_deadline - state.wind_down_started_at,
+            CLAIM_PERIOD
+        );
+    }
+
+    #[test]
+    fn test_initiate_wind_down_requires_admin() {
+        let mut env = TestEnv::new();
+        let attacker = env.create_user(0);
+
+        let result = env.initiate_wind_down(&attacker, CLAIM_PERIOD);
+        assert_usdv_error(result, USDvError::Unauthorized);
+        assert!(!env.program_state().is_wound_down());
+    }
+
+    #[test]
+    fn test_initiate_wind_down_rejects_short_claim_period() {
+        let mut env = TestEnv::new();
+        let admin = env.admin.insecure_clone();
+
+        let result = env.initiate_wind_down(&admin, CLAIM_PERIOD - 1);
+        assert_usdv_error(result, USDvError::InvalidClaimPeriod);
+    }
+
+    #[test]
+    fn test_minting_disabled_after_wind_down() {
+        let (mut env, _) = wound_down_env();
+        let user = env.create_user(10 * ONE_TOKEN);
+
+        let result = env.deposit_and_mint(&user, 10 * ONE_TOKEN);
+        assert_usdv_error(result, USDvError::WindDownActive);
+        assert_eq!(env.token_balance(&env.usdc_ata(&user.pubkey())), 10 * ONE_TOKEN);
+    }
+
+    #[test]
+    fn test_redemptions_stay_open_after_wind_down() {
+        let (mut env, holder) = wound_down_env();
+
+        env.burn_and_withdraw(&holder, 40 * ONE_TOKEN).unwrap();
+        env.burn_and_withdraw(&holder, 60 * ONE_TOKEN).unwrap();
+
+        assert_eq!(env.token_balance(&env.usdc_ata(&holder.pubkey())), 100 * ONE_TOKEN);
+        assert_eq!(env.token_balance(&env.usdv_ata(&holder.pubkey())), 0);
+        assert_eq!(env.program_state().total_usdv_supply, 0);
+    }
+
+    #[test]
+    fn test_redemptions_stay_open_after_deadline() {
+        let (mut env, holder) = wound_down_env();
+        env.advance_clock(CLAIM_PERIOD + 1);
+
+        env.burn_and_withdraw(&holder, 100 * ONE_TOKEN).unwrap();
+        assert_eq!(env.token_balance(&env.usdc_ata(&holder.pubkey())), 100 * ONE_TOKEN);
+    }
+
+    // Every admin instruction after wind-down
+
+    #[test]
+    fn test_admin_initiate_wind_down_again_rejected() {
+        let (mut env, _) = wound_down_env();
+        let admin = env.admin.insecure_clone();
+        let deadline = env.program_state().wind_down_deadline;
+
+        env.advance_clock(10);
+        let result = env.initiate_wind_down(&admin, CLAIM_PERIOD);
+        assert_usdv_error(result, USDvError::WindDownActive);
+
+        // The deadline cannot be pushed back by re-entering wind-down
+        assert_eq!(env.program_state().wind_down_deadline, deadline);
+    }
+
+    #[test]
+    fn test_admin_update_program_state_after_wind_down() {
+        let (mut env, _) = wound_down_env();
+        let admin = env.admin.insecure_clone();
+        let new_admin = Keypair::new();
+
+        // Rotating the admin key is still possible and gives no power over funds
+        env.update_program_state(&admin, Some(new_admin.pubkey())).unwrap();
+
+        let state = env.program_state();
+        assert_eq!(state.admin, new_admin.pubkey());
+        assert!(state.is_wound_down());
+    }
+
+    #[test]
+    fn test_admin_sweep_before_deadline_rejected() {
+        let (mut env, _) = wound_down_env();
+        let admin = env.admin.insecure_clone();
+        let recovery = create_token_account(&mut env.svm, &env.usdc_mint, &admin.pubkey());
+
+        env.advance_clock(CLAIM_PERIOD - 10);
+        let result = env.sweep_unclaimed(&admin, &recovery);
+        assert_usdv_error(result, USDvError::ClaimPeriodNotElapsed);
+        assert_eq!(env.token_balance(&env.vault_usdc_account()), 100 * ONE_TOKEN);
+    }
+
+    #[test]
+    fn test_admin_sweep_requires_wind_down() {
+        let mut env = TestEnv::new();
+        let holder = env.create_user(100 * ONE_TOKEN);
+        env.deposit_and_mint(&holder, 100 * ONE_TOKEN).unwrap();
+
+        let admin = env.admin.insecure_clone();
+        let recovery = create_token_account(&mut env.svm, &env.usdc_mint, &admin.pubkey());
+
+        let result = env.sweep_unclaimed(&admin, &recovery);
+        assert_usdv_error(result, USDvError::WindDownNotActive);
+    }
+
+    #[test]
+    fn test_admin_sweep_requires_admin() {
+        let (mut env, holder) = wound_down_env();
+        env.advance_clock(CLAIM_PERIOD + 1);
+
+        let attacker = env.create_user(0);
+        let recovery = env.usdc_ata(&attacker.pubkey());
+
+        let result = env.sweep_unclaimed(&attacker, &recovery);
+        assert_usdv_error(result, USDvError::Unauthorized);
+
+        // The holder can still redeem everything
+        env.burn_and_withdraw(&holder, 100 * ONE_TOKEN).unwrap();
+    }
+
+    #[test]
+    fn test_admin_sweep_rejects_forged_program_state() {
+        let (mut env, holder) = wound_down_env();
+        let attacker = env.create_user(0);
+        let recovery = env.usdc_ata(&attacker.pubkey());
+
+        // A program-owned state naming the attacker as admin, pointing at
+        // the shared vault, must not be accepted in place of the PDA
+        env.program_state = env.forge_program_state(&attacker.pubkey());
+        let result = env.sweep_unclaimed(&attacker, &recovery);
+        assert_anchor_error(result, ErrorCode::ConstraintSeeds);
+        assert_eq!(env.token_balance(&env.vault_usdc_account()), 100 * ONE_TOKEN);
+
+        env.program_state = derive_program_state_pda(&usdv_program::ID).0;
+        env.burn_and_withdraw(&holder, 100 * ONE_TOKEN).unwrap();
+    }
+
+    #[test]
+    fn test_admin_sweep_after_deadline() {
+        let (mut env, holder) = wound_down_env();
+        let admin = env.admin.insecure_clone();
+
+        // Holder claims part of their collateral during the claim period
+        env.burn_and_withdraw(&holder, 30 * ONE_TOKEN).unwrap();
+
+        let recovery = create_token_account(&mut env.svm, &env.usdc_mint, &admin.pubkey());
+        env.advance_clock(CLAIM_PERIOD + 1);
+        env.sweep_unclaimed(&admin, &recovery).unwrap();
+
+        assert_eq!(env.token_balance(&recovery), 70 * ONE_TOKEN);
+        assert_eq!(env.token_balance(&env.vault_usdc_account()), 0);
+
+        let state = env.program_state();
+        assert_eq!(state.total_usdc_deposits, 0);
+        assert_eq!(state.total_usdv_supply, 70 * ONE_TOKEN);
+    }
+}
@@ -1,16 +1,253 @@
-//! Error definitions for the USDv stablecoin program
+//! Error definitions for the USDv client
+//!
+//! [`USDvClientError`] groups failures by where they happened: talking to
+//! the RPC node, validating inputs before anything is built, signing, and
+//! executing a transaction that was sent. Execution failures keep the
+//! transaction logs, and custom errors raised by the USDv program are
+//! decoded back into the exact [`USDvError`] variant.
+
+use crate::{Retryability, USDvError};
 use thiserror::Error;
+use usdv_utils::ValidationError;
 
 // For client-side errors (off-chain)
 #[derive(Error, Debug)]
 pub enum USDvClientError {
+    /// The RPC node could not be reached or returned an error
+    #[cfg(feature = "rpc")]
     #[error("Solana client error: {0}")]
-    SolanaClientError(#[from] solana_client::client_error::ClientError),
+    SolanaClientError(Box<solana_client::client_error::ClientError>),
+
+    /// A websocket subscription could not be set up or was lost
+    #[cfg(feature = "rpc")]
+    #[error("Solana pubsub error: {0}")]
+    PubsubError(Box<solana_client::pubsub_client::PubsubClientError>),
+
+    /// An input failed the checks shared with the program
+    #[error("Validation failed: {0}")]
+    Validation(#[from] ValidationError),
+
+    /// Account data could not be decoded
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+
+    /// The transaction could not be signed
+    #[error("Signing error: {0}")]
+    SigningError(String),
+
+    /// The client configuration is malformed or does not match the deployment
+    #[error("Configuration error: {0}")]
+    ConfigurationError(String),
+
+    /// The USDv program rejected the transaction
+    ///
+    /// `signature` is `None` when the failure was reported by preflight
+    /// simulation and the transaction never landed.
+    #[cfg(feature = "rpc")]
+    #[error("USDv program error in instruction {instruction_index}: {error}")]
+    Program {
+        error: USDvError,
+        instruction_index: u8,
+        signature: Option<solana_signature::Signature>,
+        logs: Vec<String>,
+    },
+
+    /// The transaction landed and failed outside the USDv program
+    #[cfg(feature = "rpc")]
+    #[error("Transaction {signature} failed: {error}")]
+    TransactionFailed {
+        signature: solana_signature::Signature,
+        error: solana_transaction_error::TransactionError,
+        logs: Vec<String>,
+    },
+
+    /// The transaction never landed
+    ///
+    /// `error` is set when preflight simulation rejected it.
+    #[cfg(feature = "rpc")]
+    #[error("Transaction did not land ({reason}), signatures tried: {signatures:?}")]
+    TransactionNotLanded {
+        signatures: Vec<solana_signature::Signature>,
+        reason: String,
+        error: Option<solana_transaction_error::TransactionError>,
+        logs: Vec<String>,
+    },
+}
+
+impl USDvClientError {
+    /// The decoded USDv program error, if the program rejected the transaction
+    pub fn program_error(&self) -> Option<USDvError> {
+        match self {
+            #[cfg(feature = "rpc")]
+            USDvClientError::Program { error, .. } => Some(*error),
+            _ => None,
+        }
+    }
+
+    /// Whether and how the failed operation can be retried
+    pub fn retryability(&self) -> Retryability {
+        match self {
+            #[cfg(feature = "rpc")]
+            USDvClientError::SolanaClientError(error) => client_error_retryability(error),
+            #[cfg(feature = "rpc")]
+            USDvClientError::PubsubError(error) => pubsub_error_retryability(error),
+            USDvClientError::Validation(error) => match error {
+                // Decimals come from the mint configuration, not the caller
+                ValidationError::InvalidDecimals(_) => Retryability::Fatal,
+                ValidationError::InvalidAmount { .. }
+                | ValidationError::InvalidPublicKey(_)
+                | ValidationError::AmountTooSmall { .. }
+                | ValidationError::AmountTooLarge { .. }
+                | ValidationError::ZeroAmount
+                | ValidationError::SupplyLimitExceeded { .. }
+                | ValidationError::InsufficientBalance { .. }
+                | ValidationError::InsufficientVaultBalance { .. } => Retryability::UserFixable,
+            },
+            USDvClientError::SerializationError(_)
+            | USDvClientError::SigningError(_)
+            | USDvClientError::ConfigurationError(_) => Retryability::Fatal,
+            #[cfg(feature = "rpc")]
+            USDvClientError::Program { error, .. } => error.retryability(),
+            #[cfg(feature = "rpc")]
+            USDvClientError::TransactionFailed { error, .. } => {
+                transaction_error_retryability(error)
+            }
+            // Never landed for lack of time or retries: resubmit with a fresh blockhash
+            #[cfg(feature = "rpc")]
+            USDvClientError::TransactionNotLanded { error, .. } => error
+                .as_ref()
+                .map_or(Retryability::RetryableAfterRefresh, transaction_error_retryability),
+        }
+    }
+
+    /// Transaction logs, empty for errors raised before execution
+    pub fn logs(&self) -> &[String] {
+        match self {
+            #[cfg(feature = "rpc")]
+            USDvClientError::Program { logs, .. }
+            | USDvClientError::TransactionFailed { logs, .. }
+            | USDvClientError::TransactionNotLanded { logs, .. } => logs,
+            _ => &[],
+        }
+    }
+}
 
-    #[error("Invalid public key: {0}")]
-    InvalidPublicKey(String),
+/// Instruction index and [`USDvError`] of a custom instruction error
+///
+/// The caller must check that the instruction at the returned index belongs
+/// to the USDv program: other Anchor programs use the same code range.
+#[cfg(feature = "rpc")]
+pub fn decode_custom_error(
+    error: &solana_transaction_error::TransactionError,
+) -> Option<(u8, USDvError)> {
+    use solana_instruction::error::InstructionError;
+    use solana_transaction_error::TransactionError;
 
-    #[error("Insufficient balance: required {required}, available {available}")]
-    InsufficientBalance { required: u64, available: u64 },
+    match error {
+        TransactionError::InstructionError(index, InstructionError::Custom(code)) => {
+            Some((*index, USDvError::from_code(*code)?))
+        }
+        _ => None,
+    }
+}
+
+/// Whether and how a transaction that failed with `error` can be retried
+///
+/// Custom instruction errors are classified as fatal here: decode them
+/// with [`decode_custom_error`] first when they come from the USDv program.
+#[cfg(feature = "rpc")]
+pub fn transaction_error_retryability(
+    error: &solana_transaction_error::TransactionError,
+) -> Retryability {
+    use solana_instruction::error::InstructionError;
+    use solana_transaction_error::TransactionError;
+
+    match error {
+        TransactionError::BlockhashNotFound
+        | TransactionError::InstructionError(_, InstructionError::ComputationalBudgetExceeded)
+        | TransactionError::InstructionError(_, InstructionError::ProgramFailedToComplete) => {
+            Retryability::RetryableAfterRefresh
+        }
+        TransactionError::AccountInUse
+        | TransactionError::WouldExceedMaxBlockCostLimit
+        | TransactionError::WouldExceedMaxAccountCostLimit
+        | TransactionError::WouldExceedAccountDataBlockLimit
+        | TransactionError::WouldExceedAccountDataTotalLimit
+        | TransactionError::ClusterMaintenance
+        | TransactionError::ProgramExecutionTemporarilyRestricted { .. } => Retryability::Retryable,
+        TransactionError::InsufficientFundsForFee
+        | TransactionError::InsufficientFundsForRent { .. }
+        | TransactionError::AccountNotFound
+        | TransactionError::InstructionError(_, InstructionError::InsufficientFunds) => {
+            Retryability::UserFixable
+        }
+        _ => Retryability::Fatal,
+    }
+}
+
+/// Whether and how an RPC call that failed with `error` can be retried
+#[cfg(feature = "rpc")]
+fn client_error_retryability(error: &solana_client::client_error::ClientError) -> Retryability {
+    use solana_client::client_error::ClientErrorKind;
+    use solana_client::rpc_custom_error::{
+        JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+        JSON_RPC_SERVER_ERROR_BLOCK_STATUS_NOT_AVAILABLE_YET,
+        JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED, JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY,
+    };
+    use solana_client::rpc_request::RpcError;
+
+    if let Some(transaction_error) = error.get_transaction_error() {
+        return transaction_error_retryability(&transaction_error);
+    }
+
+    match error.kind() {
+        ClientErrorKind::Io(_) | ClientErrorKind::Middleware(_) => Retryability::Retryable,
+        // 429 and 5xx are transient; other HTTP errors will repeat
+        ClientErrorKind::Reqwest(error) => match error.status() {
+            Some(status) if status.is_client_error() && status.as_u16() != 429 => {
+                Retryability::Fatal
+            }
+            _ => Retryability::Retryable,
+        },
+        ClientErrorKind::RpcError(RpcError::RpcRequestError(_)) => Retryability::Retryable,
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code:
+                JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY
+                | JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE
+                | JSON_RPC_SERVER_ERROR_BLOCK_STATUS_NOT_AVAILABLE_YET
+                | JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED,
+            ..
+        }) => Retryability::Retryable,
+        _ => Retryability::Fatal,
+    }
+}
+
+/// Whether a websocket subscription that failed with `error` can be set up again
+#[cfg(feature = "rpc")]
+fn pubsub_error_retryability(
+    error: &solana_client::pubsub_client::PubsubClientError,
+) -> Retryability {
+    use solana_client::pubsub_client::PubsubClientError;
+
+    match error {
+        // A malformed URL or a subscription the node refuses will not change
+        PubsubClientError::UrlParseError(_) | PubsubClientError::SubscribeFailed { .. } => {
+            Retryability::Fatal
+        }
+        _ => Retryability::Retryable,
+    }
+}
+
+#[cfg(feature = "rpc")]
+impl From<solana_client::client_error::ClientError> for USDvClientError {
+    fn from(e: solana_client::client_error::ClientError) -> Self {
+        USDvClientError::SolanaClientError(Box::new(e))
+    }
+}
 
+#[cfg(feature = "rpc")]
+impl From<solana_client::pubsub_client::PubsubClientError> for USDvClientError {
+    fn from(e: solana_client::pubsub_client::PubsubClientError) -> Self {
+        USDvClientError::PubsubError(Box::new(e))
+    }
 }
@@ -1,6 +1,10 @@
+#[cfg(feature = "client")]
 pub mod client_errors;
+#[cfg(feature = "client")]
 pub use client_errors::*;
 
 pub mod program_errors;
 pub use program_errors::*;
 
+pub mod retryability;
+pub use retryability::Retryability;
@@ -1,8 +1,23 @@
 //! Error definitions for the USDv stablecoin program
 use anchor_lang::prelude::*;
 
-#[error_code]
-pub enum USDvError {
+/// Declares [`USDvError`] and `USDvError::ALL` from one list of variants,
+/// so the two cannot drift apart
+macro_rules! usdv_errors {
+    ($($(#[$meta:meta])* $variant:ident,)*) => {
+        #[error_code]
+        pub enum USDvError {
+            $($(#[$meta])* $variant,)*
+        }
+
+        impl USDvError {
+            /// Every variant, in declaration order, so `ALL[i]` has code `6000 + i`
+            pub const ALL: [USDvError; [$(stringify!($variant)),*].len()] = [$(USDvError::$variant),*];
+        }
+    };
+}
+
+usdv_errors! {
     #[msg("Invalid USDC mint address")]
     InvalidUSDCMint,
     
@@ -78,6 +93,50 @@ pub enum USDvError {
 
     #[msg("Internal system error")]
     InternalError,
+
+    #[msg("Program is winding down: minting is permanently disabled")]
+    WindDownActive,
+
+    #[msg("Program is not in wind-down")]
+    WindDownNotActive,
+
+    #[msg("Wind-down claim period is shorter than the minimum")]
+    InvalidClaimPeriod,
+
+    #[msg("Wind-down claim period has not elapsed yet")]
+    ClaimPeriodNotElapsed,
+
+    #[msg("Accepted collateral cannot be recovered")]
+    CollateralNotRecoverable,
+
+    #[msg("Mint amount exceeds the minter's remaining allowance")]
+    MinterAllowanceExceeded,
+
+    #[msg("Burn exceeds the attested off-chain deposits")]
+    InsufficientOffchainDeposits,
+
+    #[msg("Wallet is not on the allowlist")]
+    NotAllowlisted,
+
+    #[msg("Allowlist entry has expired")]
+    AllowlistEntryExpired,
+
+    #[msg("Allowlist entry accounts do not match the requested updates")]
+    InvalidAllowlistEntry,
+
+    #[msg("Mint would exceed the maximum total supply")]
+    SupplyLimitExceeded,
+
+    #[msg("Redemption exceeds the USDv backed by on-chain USDC deposits")]
+    InsufficientOnchainDeposits,
+}
+
+impl USDvError {
+    /// Decode a custom program error code reported on-chain
+    pub fn from_code(code: u32) -> Option<USDvError> {
+        let index = code.checked_sub(anchor_lang::error::ERROR_CODE_OFFSET)?;
+        USDvError::ALL.get(usize::try_from(index).ok()?).copied()
+    }
 }
 
 impl From<USDvError> for ProgramError {
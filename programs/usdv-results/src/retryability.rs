@@ -0,0 +1,84 @@
+//! Whether and how a failed operation can be retried
+
+/// How a caller should react to an error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Retryability {
+    /// Transient; retry the same operation, with backoff
+    Retryable,
+    /// Retry after refreshing chain state (blockhash, accounts, compute
+    /// budget) and rebuilding the transaction
+    RetryableAfterRefresh,
+    /// The caller must change something first: inputs, balances,
+    /// allowlist status or timing
+    UserFixable,
+    /// Retrying cannot succeed without a code, configuration or program change
+    Fatal,
+}
+
+impl Retryability {
+    /// Whether an automatic retry can succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Retryability::Retryable | Retryability::RetryableAfterRefresh
+        )
+    }
+
+    /// Whether chain state must be refreshed before retrying
+    pub fn needs_refresh(&self) -> bool {
+        *self == Retryability::RetryableAfterRefresh
+    }
+}
+
+impl crate::USDvError {
+    /// How to react when the program returns this error
+    ///
+    /// Deliberately matches every variant without a wildcard, so a new
+    /// variant does not compile until it is classified.
+    pub fn retryability(&self) -> Retryability {
+        use crate::USDvError::*;
+
+        match self {
+            InsufficientUSDCBalance
+            | InsufficientUSDvBalance
+            | InsufficientVaultBalance
+            | InsufficientBalance
+            | InsufficientOffchainDeposits
+            | InsufficientOnchainDeposits
+            | ArithmeticOverflow
+            | InvalidPublicKey
+            | InvalidAmount
+            | AccountNotFound
+            | InvalidClaimPeriod
+            | ClaimPeriodNotElapsed
+            | MinterAllowanceExceeded
+            | NotAllowlisted
+            | AllowlistEntryExpired
+            | SupplyLimitExceeded => Retryability::UserFixable,
+
+            SolanaClientError | NetworkError | TimeoutError | RateLimitExceeded => {
+                Retryability::Retryable
+            }
+
+            // The entry accounts were derived from stale allowlist state
+            InvalidAllowlistEntry => Retryability::RetryableAfterRefresh,
+
+            InvalidUSDCMint
+            | InvalidVaultAuthority
+            | Unauthorized
+            | AlreadyInitialized
+            | NotInitialized
+            | InvalidPegRatio
+            | InvalidInstructionData
+            | AccountValidationError
+            | TransactionFailed
+            | InvalidMintAddress
+            | SerializationError
+            | ConfigurationError
+            | InternalError
+            | WindDownActive
+            | WindDownNotActive
+            | CollateralNotRecoverable => Retryability::Fatal,
+        }
+    }
+}
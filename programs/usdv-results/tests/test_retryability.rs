@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+
+    use usdv_results::{Retryability, USDvError};
+    use Retryability::*;
+
+    /// Classification of every variant
+    ///
+    /// The match has no catch-all arm, so a new variant does not compile
+    /// until it is given a deliberate classification here.
+    fn expected(error: USDvError) -> Retryability {
+        match error {
+            USDvError::InvalidUSDCMint => Fatal,
+            USDvError::InsufficientUSDCBalance => UserFixable,
+            USDvError::InsufficientUSDvBalance => UserFixable,
+            USDvError::InvalidVaultAuthority => Fatal,
+            USDvError::ArithmeticOverflow => UserFixable,
+            USDvError::Unauthorized => Fatal,
+            USDvError::AlreadyInitialized => Fatal,
+            USDvError::NotInitialized => Fatal,
+            USDvError::InvalidPegRatio => Fatal,
+            USDvError::InsufficientVaultBalance => UserFixable,
+            USDvError::InvalidInstructionData => Fatal,
+            USDvError::AccountValidationError => Fatal,
+            USDvError::SolanaClientError => Retryable,
+            USDvError::InvalidPublicKey => UserFixable,
+            USDvError::InsufficientBalance => UserFixable,
+            USDvError::TransactionFailed => Fatal,
+            USDvError::AccountNotFound => UserFixable,
+            USDvError::InvalidMintAddress => Fatal,
+            USDvError::InvalidAmount => UserFixable,
+            USDvError::SerializationError => Fatal,
+            USDvError::NetworkError => Retryable,
+            USDvError::ConfigurationError => Fatal,
+            USDvError::TimeoutError => Retryable,
+            USDvError::RateLimitExceeded => Retryable,
+            USDvError::InternalError => Fatal,
+            USDvError::WindDownActive => Fatal,
+            USDvError::WindDownNotActive => Fatal,
+            USDvError::InvalidClaimPeriod => UserFixable,
+            USDvError::ClaimPeriodNotElapsed => UserFixable,
+            USDvError::CollateralNotRecoverable => Fatal,
+            USDvError::MinterAllowanceExceeded => UserFixable,
+            USDvError::InsufficientOffchainDeposits => UserFixable,
+            USDvError::NotAllowlisted => UserFixable,
+            USDvError::AllowlistEntryExpired => UserFixable,
+            USDvError::InvalidAllowlistEntry => RetryableAfterRefresh,
+            USDvError::SupplyLimitExceeded => UserFixable,
+            USDvError::InsufficientOnchainDeposits => UserFixable,
+        }
+    }
+
+    #[test]
+    fn test_every_variant_is_pinned() {
+        for error in USDvError::ALL {
+            assert_eq!(error.retryability(), expected(error), "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn test_retryability_predicates() {
+        assert!(Retryable.is_retryable());
+        assert!(RetryableAfterRefresh.is_retryable());
+        assert!(!UserFixable.is_retryable());
+        assert!(!Fatal.is_retryable());
+
+        assert!(RetryableAfterRefresh.needs_refresh());
+        assert!(!Retryable.needs_refresh());
+    }
+}
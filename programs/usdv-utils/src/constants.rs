@@ -1,6 +1,6 @@
 //! Program-wide constants and configuration values
 
-use solana_sdk::pubkey::Pubkey;
+use solana_pubkey::Pubkey;
 
 /// USDC token decimals (6 decimals)
 pub const USDC_DECIMALS: u8 = 6;
@@ -23,6 +23,23 @@ pub const VAULT_AUTHORITY_SEED: &[u8] = b"vault_authority";
 /// Seed for program state PDA
 pub const PROGRAM_STATE_SEED: &[u8] = b"program_state";
 
+/// Seed prefix for institutional minter PDAs
+pub const MINTER_SEED: &[u8] = b"minter";
+
+/// Seed prefix for allowlist entry PDAs
+pub const ALLOWLIST_SEED: &[u8] = b"allowlist";
+
+/// System program ID
+pub const SYSTEM_PROGRAM_ID: Pubkey = solana_pubkey::pubkey!("11111111111111111111111111111111");
+
+/// SPL Token program ID
+pub const TOKEN_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// SPL Associated Token Account program ID
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
 /// Maximum number of retries for RPC calls
 pub const MAX_RPC_RETRIES: u32 = 3;
 
@@ -41,15 +58,24 @@ pub const DEVNET_USDC_MINT: &str = "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU
 /// Account space calculations
 pub mod account_space {
     /// Space required for ProgramState account (in bytes)
+    ///
+    /// The account is zero-copy (`repr(C)`), so fields are laid out by
+    /// alignment with explicit trailing padding.
     pub const PROGRAM_STATE: usize = 8 + // discriminator
-        1 +  // is_initialized
         32 + // admin
         32 + // usdc_mint
         32 + // usdv_mint
         32 + // vault_authority
-        1 +  // vault_bump
         8 +  // total_usdv_supply
-        8;   // total_usdc_deposits
+        8 +  // total_usdc_deposits
+        8 +  // total_offchain_deposits
+        8 +  // wind_down_started_at
+        8 +  // wind_down_deadline
+        1 +  // initialized
+        1 +  // vault_bump
+        1 +  // wound_down
+        1 +  // allowlist_mode
+        4;   // padding
 }
 
 /// Fee calculations (for future use)
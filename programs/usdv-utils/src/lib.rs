@@ -5,9 +5,17 @@
 //! ## Features
 //!
 //! - **Mathematical Operations**: Safe arithmetic with overflow protection
+//!   and integer basis-point collateralization ratios
 //! - **Address Derivation**: PDA and associated token account utilities
 //! - **Constants**: Program-wide constants and configuration values
 //! - **Validation**: Input validation and sanitization functions
+//!
+//! The core is `no_std` (with `alloc`) so the program can share it; the
+//! `client` feature adds std, serde and the decimal string helpers.
+
+#![cfg_attr(not(feature = "client"), no_std)]
+
+extern crate alloc;
 
 pub mod constants;
 pub mod math;
@@ -15,8 +23,10 @@ pub mod pda;
 pub mod validation;
 
 pub use constants::*;
+pub use math::*;
+pub use pda::*;
 pub use validation::*;
 
 // Re-export commonly used types
-pub use solana_sdk::pubkey::Pubkey;
+pub use solana_pubkey::Pubkey;
 
@@ -1,2 +1,279 @@
+//! Integer math shared by the program and the client
+//!
+//! Everything here is deterministic integer arithmetic so the same inputs
+//! give the same answer on-chain and off-chain.
+//!
+//! ## Rounding policy
+//!
+//! Whenever a result cannot be exact, round against the user and in favor
+//! of the vault: amounts paid out to users use [`Rounding::Floor`], amounts
+//! charged to users (fees) use [`Rounding::Ceil`]. [`Rounding::HalfEven`] is
+//! for reporting only.
 
-// placeholder
+#[cfg(feature = "client")]
+use crate::constants::USDV_DECIMALS;
+use alloc::string::String;
+use core::fmt;
+#[cfg(feature = "client")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Basis points in one whole (100%)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Result type for math operations
+pub type MathResult<T> = Result<T, MathError>;
+
+/// Math error types
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MathError {
+    #[error("Arithmetic overflow")]
+    Overflow,
+    
+    #[error("Arithmetic underflow")]
+    Underflow,
+    
+    #[error("Division by zero")]
+    DivisionByZero,
+    
+    #[error("Invalid decimal amount: '{0}'")]
+    InvalidDecimal(String),
+    
+    #[error("Too many decimal places in '{value}': maximum is {max}")]
+    TooManyDecimals { value: String, max: u8 },
+    
+    #[error("Unsupported number of decimals: {0}")]
+    UnsupportedDecimals(u8),
+}
+
+/// Overflow-checked addition
+pub fn safe_add(a: u64, b: u64) -> MathResult<u64> {
+    a.checked_add(b).ok_or(MathError::Overflow)
+}
+
+/// Underflow-checked subtraction
+pub fn safe_sub(a: u64, b: u64) -> MathResult<u64> {
+    a.checked_sub(b).ok_or(MathError::Underflow)
+}
+
+/// Overflow-checked multiplication
+pub fn safe_mul(a: u64, b: u64) -> MathResult<u64> {
+    a.checked_mul(b).ok_or(MathError::Overflow)
+}
+
+/// Rounding mode for [`mul_div`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "client", derive(Serialize, Deserialize))]
+pub enum Rounding {
+    /// Round towards zero; use for amounts paid out to users
+    Floor,
+    /// Round away from zero; use for amounts charged to users
+    Ceil,
+    /// Round to nearest, ties to even; use for reporting only
+    HalfEven,
+}
+
+/// Compute `value * numerator / denominator` with a u128 intermediate
+///
+/// Fails if `denominator` is zero or the rounded result does not fit in u64.
+pub fn mul_div(
+    value: u64,
+    numerator: u64,
+    denominator: u64,
+    rounding: Rounding,
+) -> MathResult<u64> {
+    if denominator == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    
+    let product = value as u128 * numerator as u128;
+    let denominator = denominator as u128;
+    let quotient = product / denominator;
+    let remainder = product % denominator;
+    
+    let round_up = match rounding {
+        Rounding::Floor => false,
+        Rounding::Ceil => remainder > 0,
+        Rounding::HalfEven => {
+            let twice = remainder * 2;
+            twice > denominator || (twice == denominator && quotient % 2 == 1)
+        }
+    };
+    
+    let result = if round_up { quotient + 1 } else { quotient };
+    u64::try_from(result).map_err(|_| MathError::Overflow)
+}
+
+/// Fee of `fee_bps` basis points on `amount`, rounded up
+///
+/// The vault never collects less than the exact fee.
+pub fn fee_amount(amount: u64, fee_bps: u16) -> MathResult<u64> {
+    mul_div(amount, fee_bps as u64, BPS_DENOMINATOR, Rounding::Ceil)
+}
+
+/// `amount` minus its fee, i.e. what is paid out to the user
+pub fn amount_after_fee(amount: u64, fee_bps: u16) -> MathResult<u64> {
+    safe_sub(amount, fee_amount(amount, fee_bps)?)
+}
+
+/// 10^decimals, for up to 19 decimals
+#[cfg(feature = "client")]
+fn scale(decimals: u8) -> MathResult<u64> {
+    10_u64
+        .checked_pow(decimals as u32)
+        .ok_or(MathError::UnsupportedDecimals(decimals))
+}
+
+/// Parse a decimal string such as `"12.345"` into base units, exactly
+///
+/// Accepts digits with an optional fractional part. Signs, exponents,
+/// separators and more than `decimals` fractional digits are rejected
+/// rather than rounded.
+#[cfg(feature = "client")]
+pub fn parse_decimal(value: &str, decimals: u8) -> MathResult<u64> {
+    let invalid = || MathError::InvalidDecimal(value.to_string());
+    let scale = scale(decimals)?;
+    
+    let (whole, fraction) = match value.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (value, ""),
+    };
+    
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty())
+        || !is_digits(whole)
+        || !is_digits(fraction)
+        || (value.contains('.') && fraction.is_empty())
+    {
+        return Err(invalid());
+    }
+    
+    if fraction.len() > decimals as usize {
+        return Err(MathError::TooManyDecimals {
+            value: value.to_string(),
+            max: decimals,
+        });
+    }
+    
+    let whole: u64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| MathError::Overflow)?
+    };
+    let fraction: u64 = if fraction.is_empty() {
+        0
+    } else {
+        let padded = format!("{:0<width$}", fraction, width = decimals as usize);
+        padded.parse().map_err(|_| invalid())?
+    };
+    
+    safe_add(safe_mul(whole, scale)?, fraction)
+}
+
+/// Format base units as an exact decimal string, without trailing zeros
+#[cfg(feature = "client")]
+pub fn format_decimal(amount: u64, decimals: u8) -> MathResult<String> {
+    let scale = scale(decimals)?;
+    let whole = amount / scale;
+    let fraction = amount % scale;
+    
+    if fraction == 0 {
+        return Ok(whole.to_string());
+    }
+    
+    let fraction = format!("{:0>width$}", fraction, width = decimals as usize);
+    Ok(format!("{}.{}", whole, fraction.trim_end_matches('0')))
+}
+
+/// Parse a 6-decimal USDC/USDv amount such as `"12.5"` into base units
+#[cfg(feature = "client")]
+pub fn parse_amount(value: &str) -> MathResult<u64> {
+    parse_decimal(value, USDV_DECIMALS)
+}
+
+/// Format a 6-decimal USDC/USDv amount in base units
+#[cfg(feature = "client")]
+pub fn format_amount(amount: u64) -> String {
+    format_decimal(amount, USDV_DECIMALS).expect("6 decimals is supported")
+}
+
+/// Convert base units to a UI amount, for display only
+#[cfg(feature = "client")]
+pub fn base_to_ui_amount(amount: u64, decimals: u8) -> f64 {
+    amount as f64 / 10_f64.powi(decimals as i32)
+}
+
+/// Convert a UI amount to base units
+///
+/// The float is first rounded to `decimals` places, then parsed exactly.
+/// Prefer [`parse_decimal`] for user input.
+#[cfg(feature = "client")]
+pub fn ui_to_base_amount(ui_amount: f64, decimals: u8) -> MathResult<u64> {
+    if !ui_amount.is_finite() || ui_amount < 0.0 {
+        return Err(MathError::InvalidDecimal(ui_amount.to_string()));
+    }
+    
+    let rounded = format!("{:.*}", decimals as usize, ui_amount);
+    parse_decimal(&rounded, decimals)
+}
+
+/// Collateralization ratio in basis points (10_000 = 100%)
+///
+/// Computed with u128 intermediates and rounded down, so a vault is never
+/// reported as better collateralized than it is. Zero supply has nothing
+/// to back and is its own variant rather than an "infinite" ratio; it
+/// orders above every finite ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "client", derive(Serialize, Deserialize))]
+pub enum CollateralRatio {
+    /// Collateral divided by supply, in basis points
+    Bps(u64),
+    /// No supply outstanding
+    NoSupply,
+}
+
+impl CollateralRatio {
+    /// Ratio of `collateral` to `supply`, both in base units
+    ///
+    /// Saturates at `u64::MAX` bps for extreme over-collateralization.
+    pub fn from_amounts(collateral: u64, supply: u64) -> Self {
+        if supply == 0 {
+            return CollateralRatio::NoSupply;
+        }
+
+        let bps = collateral as u128 * BPS_DENOMINATOR as u128 / supply as u128;
+        CollateralRatio::Bps(u64::try_from(bps).unwrap_or(u64::MAX))
+    }
+
+    /// Ratio in basis points, `None` when there is no supply
+    pub fn bps(&self) -> Option<u64> {
+        match self {
+            CollateralRatio::Bps(bps) => Some(*bps),
+            CollateralRatio::NoSupply => None,
+        }
+    }
+
+    /// Whether collateral covers the whole supply
+    pub fn is_fully_collateralized(&self) -> bool {
+        !self.is_below(BPS_DENOMINATOR)
+    }
+
+    /// Whether the ratio is strictly below `threshold_bps`
+    ///
+    /// Zero supply is never below any threshold.
+    pub fn is_below(&self, threshold_bps: u64) -> bool {
+        match self {
+            CollateralRatio::Bps(bps) => *bps < threshold_bps,
+            CollateralRatio::NoSupply => false,
+        }
+    }
+}
+
+impl fmt::Display for CollateralRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollateralRatio::Bps(bps) => write!(f, "{}.{:02}%", bps / 100, bps % 100),
+            CollateralRatio::NoSupply => write!(f, "n/a (no supply)"),
+        }
+    }
+}
@@ -1,2 +1,90 @@
+//! Program address derivation
+//!
+//! Every PDA and associated token account used by the USDv program is
+//! derived here, from the seeds in [`crate::constants`]. The program, the
+//! client and the tests all go through these functions.
 
-// placeholder
+use crate::constants::{
+    ALLOWLIST_SEED, ASSOCIATED_TOKEN_PROGRAM_ID, MINTER_SEED, PROGRAM_STATE_SEED,
+    TOKEN_PROGRAM_ID, VAULT_AUTHORITY_SEED,
+};
+use solana_pubkey::Pubkey;
+
+/// Every address involved in a user's deposit or burn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressBundle {
+    /// Global program state PDA
+    pub program_state: Pubkey,
+    /// Program state PDA bump seed
+    pub program_state_bump: u8,
+    /// Vault authority PDA (mint authority of USDv, owner of the vault)
+    pub vault_authority: Pubkey,
+    /// Vault authority PDA bump seed
+    pub vault_authority_bump: u8,
+    /// Vault USDC associated token account
+    pub vault_usdc_account: Pubkey,
+    /// User USDC associated token account
+    pub user_usdc_account: Pubkey,
+    /// User USDv associated token account
+    pub user_usdv_account: Pubkey,
+    /// User allowlist entry PDA (may not exist)
+    pub user_allowlist_entry: Pubkey,
+}
+
+/// Derive the global program state PDA
+pub fn derive_program_state_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_STATE_SEED], program_id)
+}
+
+/// Derive the vault authority PDA
+pub fn derive_vault_authority_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_AUTHORITY_SEED], program_id)
+}
+
+/// Derive the PDA of the minter whose signing key is `authority`
+pub fn derive_minter_pda(program_id: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINTER_SEED, authority.as_ref()], program_id)
+}
+
+/// Derive the allowlist entry PDA of `wallet`
+pub fn derive_allowlist_entry_pda(program_id: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ALLOWLIST_SEED, wallet.as_ref()], program_id)
+}
+
+/// Derive the SPL Token associated token account of `owner` for `mint`
+pub fn derive_associated_token_account(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Derive the vault's USDC token account
+pub fn derive_vault_usdc_account(program_id: &Pubkey, usdc_mint: &Pubkey) -> Pubkey {
+    let (vault_authority, _) = derive_vault_authority_pda(program_id);
+    derive_associated_token_account(&vault_authority, usdc_mint)
+}
+
+/// Derive every address a user needs to deposit or burn
+pub fn derive_all_addresses(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    usdc_mint: &Pubkey,
+    usdv_mint: &Pubkey,
+) -> AddressBundle {
+    let (program_state, program_state_bump) = derive_program_state_pda(program_id);
+    let (vault_authority, vault_authority_bump) = derive_vault_authority_pda(program_id);
+    let (user_allowlist_entry, _) = derive_allowlist_entry_pda(program_id, user);
+
+    AddressBundle {
+        program_state,
+        program_state_bump,
+        vault_authority,
+        vault_authority_bump,
+        vault_usdc_account: derive_associated_token_account(&vault_authority, usdc_mint),
+        user_usdc_account: derive_associated_token_account(user, usdc_mint),
+        user_usdv_account: derive_associated_token_account(user, usdv_mint),
+        user_allowlist_entry,
+    }
+}
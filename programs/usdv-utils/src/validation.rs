@@ -1,6 +1,11 @@
 //! Input validation and sanitization utilities
-use crate::constants::{MIN_DEPOSIT_AMOUNT, MAX_DEPOSIT_AMOUNT, MAX_TOTAL_SUPPLY};
-use solana_sdk::pubkey::Pubkey;
+//!
+//! The deposit and burn checks here are the same ones `usdv_program`
+//! enforces on-chain, so client-side pre-flight checks cannot drift from
+//! the program's rules.
+use crate::constants::{MIN_DEPOSIT_AMOUNT, MAX_DEPOSIT_AMOUNT, MAX_TOTAL_SUPPLY, SYSTEM_PROGRAM_ID};
+use alloc::string::{String, ToString};
+use solana_pubkey::Pubkey;
 use thiserror::Error;
 
 /// Result type for validation operations
@@ -32,6 +37,9 @@ pub enum ValidationError {
     
     #[error("Insufficient balance: required {required}, available {available}")]
     InsufficientBalance { required: u64, available: u64 },
+    
+    #[error("Insufficient vault balance: required {required}, available {available}")]
+    InsufficientVaultBalance { required: u64, available: u64 },
 }
 
 /// Validate deposit amount
@@ -116,7 +124,7 @@ pub fn validate_mint_supply_limit(mint_amount: u64, current_supply: u64) -> Vali
 /// * `ValidationResult<()>` - Success or validation error
 pub fn validate_pubkey(pubkey: &Pubkey) -> ValidationResult<()> {
     // Check if it's the system program (invalid for most use cases)
-    if *pubkey == solana_sdk::system_program::id() {
+    if *pubkey == SYSTEM_PROGRAM_ID {
         return Err(ValidationError::InvalidPublicKey(
             "System program ID not allowed".to_string()
         ));
@@ -206,7 +214,7 @@ pub fn validate_burn_operation(
     validate_burn_amount(amount, user_usdv_balance)?;
     
     if amount > vault_usdc_balance {
-        return Err(ValidationError::InsufficientBalance {
+        return Err(ValidationError::InsufficientVaultBalance {
             required: amount,
             available: vault_usdc_balance,
         });
@@ -0,0 +1,199 @@
+#[cfg(test)]
+mod tests {
+
+    use proptest::prelude::*;
+    use usdv_utils::math::*;
+
+    #[test]
+    fn test_collateral_ratio_from_amounts() {
+        assert_eq!(CollateralRatio::from_amounts(1_000, 1_000), CollateralRatio::Bps(10_000));
+        assert_eq!(CollateralRatio::from_amounts(950, 1_000), CollateralRatio::Bps(9_500));
+        assert_eq!(CollateralRatio::from_amounts(0, 1_000), CollateralRatio::Bps(0));
+
+        // Rounds down: 2/3 is 6666.67 bps
+        assert_eq!(CollateralRatio::from_amounts(2, 3), CollateralRatio::Bps(6_666));
+    }
+
+    #[test]
+    fn test_collateral_ratio_large_amounts() {
+        // u128 intermediates: no overflow at the top of the u64 range
+        assert_eq!(
+            CollateralRatio::from_amounts(u64::MAX, u64::MAX),
+            CollateralRatio::Bps(BPS_DENOMINATOR)
+        );
+        assert_eq!(
+            CollateralRatio::from_amounts(u64::MAX, 1),
+            CollateralRatio::Bps(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_collateral_ratio_zero_supply() {
+        let ratio = CollateralRatio::from_amounts(0, 0);
+        assert_eq!(ratio, CollateralRatio::NoSupply);
+        assert_eq!(ratio.bps(), None);
+        assert!(ratio.is_fully_collateralized());
+        assert!(!ratio.is_below(u64::MAX));
+
+        // Orders above every finite ratio
+        assert!(ratio > CollateralRatio::Bps(u64::MAX));
+    }
+
+    #[test]
+    fn test_collateral_ratio_thresholds() {
+        let ratio = CollateralRatio::Bps(9_999);
+        assert!(!ratio.is_fully_collateralized());
+        assert!(ratio.is_below(BPS_DENOMINATOR));
+        assert!(!ratio.is_below(9_999));
+
+        assert!(CollateralRatio::Bps(10_000).is_fully_collateralized());
+    }
+
+    #[test]
+    fn test_collateral_ratio_display() {
+        assert_eq!(CollateralRatio::Bps(10_000).to_string(), "100.00%");
+        assert_eq!(CollateralRatio::Bps(9_505).to_string(), "95.05%");
+        assert_eq!(CollateralRatio::NoSupply.to_string(), "n/a (no supply)");
+    }
+
+    #[test]
+    fn test_safe_arithmetic() {
+        assert_eq!(safe_add(1, 2), Ok(3));
+        assert_eq!(safe_add(u64::MAX, 1), Err(MathError::Overflow));
+        assert_eq!(safe_sub(1, 2), Err(MathError::Underflow));
+        assert_eq!(safe_mul(u64::MAX, 2), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_mul_div_rounding() {
+        assert_eq!(mul_div(10, 1, 4, Rounding::Floor), Ok(2));
+        assert_eq!(mul_div(10, 1, 4, Rounding::Ceil), Ok(3));
+
+        // Ties go to the even neighbour
+        assert_eq!(mul_div(10, 1, 4, Rounding::HalfEven), Ok(2));
+        assert_eq!(mul_div(14, 1, 4, Rounding::HalfEven), Ok(4));
+        assert_eq!(mul_div(11, 1, 4, Rounding::HalfEven), Ok(3));
+
+        // u128 intermediate
+        assert_eq!(mul_div(u64::MAX, u64::MAX, u64::MAX, Rounding::Floor), Ok(u64::MAX));
+        assert_eq!(mul_div(u64::MAX, 2, 1, Rounding::Floor), Err(MathError::Overflow));
+        assert_eq!(mul_div(1, 1, 0, Rounding::Floor), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_fee_amount() {
+        // 0.3% of 1 USDC is exactly 3000 base units
+        assert_eq!(fee_amount(1_000_000, 30), Ok(3_000));
+        // 0.3% of 1 base unit rounds up to a whole unit
+        assert_eq!(fee_amount(1, 30), Ok(1));
+        assert_eq!(amount_after_fee(1, 30), Ok(0));
+        assert_eq!(fee_amount(1_000_000, 0), Ok(0));
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_amount("12"), Ok(12_000_000));
+        assert_eq!(parse_amount("12.5"), Ok(12_500_000));
+        assert_eq!(parse_amount("0.000001"), Ok(1));
+        assert_eq!(parse_amount(".5"), Ok(500_000));
+        assert_eq!(parse_decimal("7", 0), Ok(7));
+
+        for invalid in ["", ".", "1.", "-1", "+1", "1e6", "1,000", "1.2.3", " 1"] {
+            assert!(
+                matches!(parse_amount(invalid), Err(MathError::InvalidDecimal(_))),
+                "{invalid:?} should be rejected"
+            );
+        }
+
+        assert!(matches!(
+            parse_amount("0.0000001"),
+            Err(MathError::TooManyDecimals { max: 6, .. })
+        ));
+        assert_eq!(parse_amount("18446744073710"), Err(MathError::Overflow));
+        assert_eq!(parse_decimal("1", 20), Err(MathError::UnsupportedDecimals(20)));
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_format_decimal() {
+        assert_eq!(format_amount(12_000_000), "12");
+        assert_eq!(format_amount(12_500_000), "12.5");
+        assert_eq!(format_amount(1), "0.000001");
+        assert_eq!(format_amount(0), "0");
+        assert_eq!(format_decimal(u64::MAX, 19), Ok("1.8446744073709551615".to_string()));
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_ui_amount_conversion() {
+        assert_eq!(base_to_ui_amount(1_500_000, 6), 1.5);
+        assert_eq!(ui_to_base_amount(1.5, 6), Ok(1_500_000));
+
+        // 0.1 + 0.2 is not exactly 0.3 in binary, but rounds back to it
+        assert_eq!(ui_to_base_amount(0.1 + 0.2, 6), Ok(300_000));
+
+        assert!(ui_to_base_amount(-1.0, 6).is_err());
+        assert!(ui_to_base_amount(f64::NAN, 6).is_err());
+        assert_eq!(ui_to_base_amount(1e30, 6), Err(MathError::Overflow));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_floor_never_pays_more_than_exact(
+            value: u64,
+            numerator in 0..=BPS_DENOMINATOR,
+            denominator in 1..=BPS_DENOMINATOR,
+        ) {
+            let exact = value as u128 * numerator as u128;
+            if let Ok(floor) = mul_div(value, numerator, denominator, Rounding::Floor) {
+                prop_assert!(floor as u128 * denominator as u128 <= exact);
+                prop_assert!((floor as u128 + 1) * denominator as u128 > exact);
+            }
+        }
+
+        #[test]
+        fn prop_ceil_never_charges_less_than_exact(
+            value: u64,
+            numerator in 0..=BPS_DENOMINATOR,
+            denominator in 1..=BPS_DENOMINATOR,
+        ) {
+            let exact = value as u128 * numerator as u128;
+            if let Ok(ceil) = mul_div(value, numerator, denominator, Rounding::Ceil) {
+                prop_assert!(ceil as u128 * denominator as u128 >= exact);
+                let floor = mul_div(value, numerator, denominator, Rounding::Floor).unwrap();
+                prop_assert!(ceil - floor <= 1);
+            }
+        }
+
+        #[test]
+        fn prop_half_even_is_nearest(
+            value: u64,
+            numerator in 0..=BPS_DENOMINATOR,
+            denominator in 1..=BPS_DENOMINATOR,
+        ) {
+            let exact = value as u128 * numerator as u128;
+            if let Ok(rounded) = mul_div(value, numerator, denominator, Rounding::HalfEven) {
+                let scaled = rounded as u128 * denominator as u128;
+                prop_assert!(scaled.abs_diff(exact) * 2 <= denominator as u128);
+            }
+        }
+
+        #[test]
+        fn prop_fee_never_favors_user(amount: u64, fee_bps in 0..=10_000u16) {
+            let fee = fee_amount(amount, fee_bps).unwrap();
+            let paid_out = amount_after_fee(amount, fee_bps).unwrap();
+
+            // The vault collects at least the exact fee and pays out at most the exact net
+            prop_assert!(fee as u128 * BPS_DENOMINATOR as u128 >= amount as u128 * fee_bps as u128);
+            prop_assert_eq!(fee + paid_out, amount);
+        }
+
+        #[cfg(feature = "client")]
+        #[test]
+        fn prop_decimal_round_trip(amount: u64, decimals in 0u8..=19) {
+            let formatted = format_decimal(amount, decimals).unwrap();
+            prop_assert_eq!(parse_decimal(&formatted, decimals), Ok(amount));
+        }
+    }
+}
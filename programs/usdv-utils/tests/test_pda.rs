@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+
+    use spl_associated_token_account::get_associated_token_address;
+    use usdv_utils::*;
+
+    #[test]
+    fn test_pda_derivation_is_deterministic() {
+        let program_id = Pubkey::new_unique();
+
+        assert_eq!(
+            derive_vault_authority_pda(&program_id),
+            derive_vault_authority_pda(&program_id)
+        );
+        assert_ne!(
+            derive_vault_authority_pda(&program_id).0,
+            derive_program_state_pda(&program_id).0
+        );
+        assert_ne!(
+            derive_vault_authority_pda(&program_id).0,
+            derive_vault_authority_pda(&Pubkey::new_unique()).0
+        );
+    }
+
+    #[test]
+    fn test_pda_seeds() {
+        let program_id = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+
+        let (vault_authority, bump) = derive_vault_authority_pda(&program_id);
+        assert_eq!(
+            Pubkey::create_program_address(&[VAULT_AUTHORITY_SEED, &[bump]], &program_id),
+            Ok(vault_authority)
+        );
+
+        let (entry, bump) = derive_allowlist_entry_pda(&program_id, &wallet);
+        assert_eq!(
+            Pubkey::create_program_address(&[ALLOWLIST_SEED, wallet.as_ref(), &[bump]], &program_id),
+            Ok(entry)
+        );
+
+        // Minter and allowlist PDAs of the same key never collide
+        assert_ne!(derive_minter_pda(&program_id, &wallet).0, entry);
+    }
+
+    #[test]
+    fn test_associated_token_accounts_match_spl() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        assert_eq!(
+            derive_associated_token_account(&owner, &mint),
+            get_associated_token_address(&owner, &mint)
+        );
+    }
+
+    #[test]
+    fn test_derive_all_addresses() {
+        let program_id = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let usdc_mint = Pubkey::new_unique();
+        let usdv_mint = Pubkey::new_unique();
+
+        let addresses = derive_all_addresses(&program_id, &user, &usdc_mint, &usdv_mint);
+
+        assert_eq!(addresses.program_state, derive_program_state_pda(&program_id).0);
+        assert_eq!(
+            (addresses.vault_authority, addresses.vault_authority_bump),
+            derive_vault_authority_pda(&program_id)
+        );
+        assert_eq!(
+            addresses.vault_usdc_account,
+            get_associated_token_address(&addresses.vault_authority, &usdc_mint)
+        );
+        assert_eq!(
+            addresses.vault_usdc_account,
+            derive_vault_usdc_account(&program_id, &usdc_mint)
+        );
+        assert_eq!(addresses.user_usdc_account, get_associated_token_address(&user, &usdc_mint));
+        assert_eq!(addresses.user_usdv_account, get_associated_token_address(&user, &usdv_mint));
+        assert_eq!(
+            addresses.user_allowlist_entry,
+            derive_allowlist_entry_pda(&program_id, &user).0
+        );
+    }
+}
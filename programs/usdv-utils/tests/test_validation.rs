@@ -79,7 +79,7 @@ mod tests {
         let valid_pubkey = Pubkey::new_unique();
         assert!(validate_pubkey(&valid_pubkey).is_ok());
         
-        let system_program = solana_sdk::system_program::id();
+        let system_program = SYSTEM_PROGRAM_ID;
         assert_eq!(
             validate_pubkey(&system_program),
             Err(ValidationError::InvalidPublicKey(
@@ -128,10 +128,16 @@ mod tests {
         ).is_ok());
         
         // Invalid burn operation (insufficient vault balance)
-        assert!(validate_burn_operation(
-            2_000_000,      // 2 USDv burn
-            3_000_000,      // 3 USDv user balance
-            1_000_000       // 1 USDC vault balance
-        ).is_err());
+        assert_eq!(
+            validate_burn_operation(
+                2_000_000,      // 2 USDv burn
+                3_000_000,      // 3 USDv user balance
+                1_000_000       // 1 USDC vault balance
+            ),
+            Err(ValidationError::InsufficientVaultBalance {
+                required: 2_000_000,
+                available: 1_000_000,
+            })
+        );
     }
 }
@@ -1,2 +1,53 @@
+#!/usr/bin/env bash
+# Build, deploy and initialize the USDv program, then record the deployment
+# as a profile of the TOML manifest read by `USDvConfig::load`.
+#
+# Usage: scripts/deploy.sh <profile> <usdc-mint> [manifest]
+#
+#   profile    manifest profile; also the cluster unless USDV_CLUSTER is set
+#              (localnet, devnet, testnet, mainnet-beta or an RPC URL)
+#   usdc-mint  USDC mint the program is initialized with
+#   manifest   manifest to write, default usdv.toml
+#
+# The admin keypair pays for deployment and initialization:
+# ADMIN_KEYPAIR, default ~/.config/solana/id.json.
 
-# placeholder
+set -euo pipefail
+
+if [ $# -lt 2 ]; then
+    sed -n '5,13p' "$0" | sed 's/^# \{0,1\}//'
+    exit 1
+fi
+
+PROFILE=$1
+USDC_MINT=$2
+MANIFEST=$(realpath -m "${3:-usdv.toml}")
+CLUSTER=${USDV_CLUSTER:-$PROFILE}
+ADMIN_KEYPAIR=${ADMIN_KEYPAIR:-$HOME/.config/solana/id.json}
+
+case "$CLUSTER" in
+    localnet) RPC_URL=http://localhost:8899 ;;
+    devnet | testnet | mainnet-beta) RPC_URL=https://api.$CLUSTER.solana.com ;;
+    *) RPC_URL=$CLUSTER ;;
+esac
+
+cd "$(dirname "$0")/.."
+
+anchor build
+
+PROGRAM_KEYPAIR=target/deploy/usdv_program-keypair.json
+PROGRAM_ID=$(solana address -k "$PROGRAM_KEYPAIR")
+if ! grep -q "declare_id!(\"$PROGRAM_ID\")" programs/usdv-program/src/lib.rs; then
+    echo "declare_id! does not match $PROGRAM_KEYPAIR ($PROGRAM_ID)." >&2
+    echo "Run 'anchor keys sync' and deploy again." >&2
+    exit 1
+fi
+
+solana program deploy \
+    --url "$RPC_URL" \
+    --keypair "$ADMIN_KEYPAIR" \
+    --program-id "$PROGRAM_KEYPAIR" \
+    target/deploy/usdv_program.so
+
+cargo run -q -p usdv_client --example record_deployment -- \
+    "$CLUSTER" "$USDC_MINT" "$ADMIN_KEYPAIR" "$MANIFEST" "$PROFILE"